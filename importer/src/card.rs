@@ -1,12 +1,14 @@
-use crate::coder::{DataCoder, DataCoderBoxUuid};
+use crate::coder::{DataCoder, DataCoderBoxUuid, DataCoderOptionUuid, NameCoder};
+use crate::filter::Filter;
 use bevy::asset::Handle;
 use bevy::image::Image;
 use bevy::pbr::StandardMaterial;
+use bevy::reflect::Reflect;
 use bevy::ui::widget::ImageNode;
 use bitcode::{Decode, Encode};
 use enumset::{EnumSet, EnumSetType};
 use std::cmp::Ordering;
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::mem;
 use std::slice::{Iter, IterMut};
 use std::sync::Arc;
@@ -40,30 +42,112 @@ pub struct CardData {
     pub set_cn: Box<str>,
     #[bitcode(with = "DataCoderBoxUuid")]
     pub tokens: Box<[Uuid]>,
+    #[bitcode(with = "DataCoderOptionUuid")]
+    pub meld_result: Option<Uuid>,
     pub front: CardInfo,
     pub back: Option<Box<CardInfo>>,
     pub layout: Layout,
+    pub rarity: Rarity,
+    pub legalities: Legalities,
+}
+#[derive(PartialEq, Debug, Default, Clone, Encode, Decode)]
+pub struct Legalities {
+    #[bitcode(with = "NameCoder")]
+    pub legal: EnumSet<Format>,
+    #[bitcode(with = "NameCoder")]
+    pub restricted: EnumSet<Format>,
+    #[bitcode(with = "NameCoder")]
+    pub banned: EnumSet<Format>,
+}
+#[derive(Debug, Default, EnumSetType, Reflect)]
+pub enum Format {
+    Standard,
+    Future,
+    Historic,
+    Timeless,
+    Gladiator,
+    Pioneer,
+    Modern,
+    Legacy,
+    Pauper,
+    Vintage,
+    Penny,
+    #[default]
+    Commander,
+    Oathbreaker,
+    StandardBrawl,
+    Brawl,
+    Alchemy,
+    PauperCommander,
+    Duel,
+    OldSchool,
+    Premodern,
+    Predh,
 }
 #[derive(PartialEq, Debug, Default, Clone, Copy, Encode, Decode)]
 pub enum Layout {
     #[default]
     Normal,
+    Split,
     Flip,
-    Side,
+    Transform,
+    ModalDfc,
+    Meld,
+    Leveler,
+    Class,
+    Case,
+    Saga,
+    Adventure,
+    Mutate,
+    Prototype,
+    Battle,
+    Planar,
+    Scheme,
+    Vanguard,
+    Token,
+    DoubleFacedToken,
+    Emblem,
+    Augment,
+    Host,
+    ArtSeries,
+    ReversibleCard,
 }
-#[derive(PartialEq, Default, Clone, Copy, Encode, Decode)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Default, Clone, Copy, Encode, Decode)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Special,
+    Mythic,
+    Bonus,
+}
+#[derive(PartialEq, Default, Clone, Encode, Decode)]
 pub struct Cost {
     pub has_cost: bool,
-    pub white: u8,
-    pub blue: u8,
-    pub black: u8,
-    pub red: u8,
-    pub green: u8,
-    pub colorless: u8,
-    pub any: u8,
-    pub pay: u8,
-    pub var: u8,
-    pub hybrid: u8,
+    pub symbols: Vec<Mana>,
+}
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Encode, Decode)]
+pub enum Mana {
+    Generic(u32),
+    Variable(Variable),
+    Infinite,
+    Half(Option<Color>),
+    Color(Color),
+    Colorless,
+    Snow,
+    Phyrexian(Option<Color>),
+    ColorlessPhyrexian,
+    Hybrid(Color, Color),
+    PhyrexianHybrid(Color, Color),
+    TwoBrid(Color),
+    ColorlessHybrid(Color),
+}
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Encode, Decode)]
+pub enum Variable {
+    X,
+    Y,
+    Z,
 }
 #[derive(PartialEq, Debug, Clone, Default, Encode, Decode)]
 pub struct CardInfo {
@@ -92,28 +176,28 @@ pub struct Handles {
     pub image: Handle<Image>,
     pub material: Handle<StandardMaterial>,
 }
-#[derive(Default, Clone, PartialOrd, Encode, Decode, Eq, PartialEq)]
+#[derive(Default, Clone, Encode, Decode, Eq, PartialEq)]
 pub struct Types {
     pub super_type: SuperTypes,
     pub main_type: MainTypes,
     pub sub_type: SubTypes,
 }
-#[derive(Debug, Default, Clone, PartialOrd, Encode, Decode, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Encode, Decode, Eq, PartialEq)]
 pub struct SuperTypes {
-    #[bitcode(with = "DataCoder<EnumSet<SuperType>>")]
+    #[bitcode(with = "NameCoder")]
     pub types: EnumSet<SuperType>,
 }
-#[derive(Debug, Default, Clone, PartialOrd, Encode, Decode, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Encode, Decode, Eq, PartialEq)]
 pub struct MainTypes {
-    #[bitcode(with = "DataCoder<EnumSet<MainType>>")]
+    #[bitcode(with = "NameCoder")]
     pub types: EnumSet<MainType>,
 }
-#[derive(Debug, Default, Clone, PartialOrd, Encode, Decode, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Encode, Decode, Eq, PartialEq)]
 pub struct SubTypes {
-    #[bitcode(with = "DataCoder<EnumSet<SubType>>")]
+    #[bitcode(with = "NameCoder")]
     pub types: EnumSet<SubType>,
 }
-#[derive(Default, Clone, Copy, PartialOrd, Encode, Decode, PartialEq)]
+#[derive(Default, Clone, Copy, Encode, Decode, PartialEq)]
 pub struct Colors {
     #[bitcode(with = "DataCoder<EnumSet<Color>>")]
     pub colors: EnumSet<Color>,
@@ -136,29 +220,6 @@ pub struct CardIterMut<'a> {
     pub equiped: IterMut<'a, SubCard>,
     pub started: bool,
 }
-#[derive(Debug)]
-pub enum Order {
-    Greater,
-    Less,
-    Equal,
-    GreaterEqual,
-    LessEqual,
-}
-#[derive(Debug, Clone, Copy)]
-pub enum SearchKey {
-    Name,
-    Cmc,
-    Type,
-    SuperType,
-    MainType,
-    SubType,
-    Text,
-    Color,
-    Identity,
-    Power,
-    Toughness,
-    Loyalty,
-}
 impl MaybeHandles {
     #[must_use]
     pub fn handles(&self) -> Option<Handles> {
@@ -226,55 +287,271 @@ impl Debug for Colors {
 }
 impl Debug for Cost {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "\"")?;
-        if self.has_cost {
-            if self.total() == 0 {
-                write!(f, "0")?;
+        write!(f, "\"{self}\"")
+    }
+}
+impl Display for Types {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for ty in self.super_type.types {
+            write!(f, "{ty} ")?;
+        }
+        for ty in self.main_type.types {
+            if first {
+                first = false;
             } else {
-                for _ in 0..self.var {
-                    write!(f, "x")?;
-                }
-                if self.any != 0 {
-                    write!(f, "{}", self.any)?;
-                }
-                for _ in 0..self.colorless {
-                    write!(f, "c")?;
-                }
-                for _ in 0..self.white {
-                    write!(f, "w")?;
-                }
-                for _ in 0..self.blue {
-                    write!(f, "u")?;
-                }
-                for _ in 0..self.black {
-                    write!(f, "b")?;
-                }
-                for _ in 0..self.red {
-                    write!(f, "r")?;
-                }
-                for _ in 0..self.green {
-                    write!(f, "g")?;
-                }
-                for _ in 0..self.pay {
-                    write!(f, "p")?;
-                }
-                for _ in 0..self.hybrid {
-                    write!(f, "h")?;
-                }
+                write!(f, " ")?;
             }
+            write!(f, "{ty}")?;
+        }
+        if !self.sub_type.types.is_empty() {
+            write!(f, " \u{2014}")?;
+            for ty in self.sub_type.types {
+                write!(f, " {ty}")?;
+            }
+        }
+        Ok(())
+    }
+}
+impl Display for Cost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for symbol in &self.symbols {
+            write!(f, "{{{symbol}}}")?;
+        }
+        Ok(())
+    }
+}
+impl Display for Mana {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Generic(n) => write!(f, "{n}"),
+            Self::Variable(v) => write!(f, "{v:?}"),
+            Self::Infinite => write!(f, "\u{221e}"),
+            Self::Half(None) => write!(f, "\u{bd}"),
+            Self::Half(Some(c)) => write!(f, "H{}", c.symbol()),
+            Self::Color(c) => write!(f, "{}", c.symbol()),
+            Self::Colorless => write!(f, "C"),
+            Self::Snow => write!(f, "S"),
+            Self::Phyrexian(None) => write!(f, "H"),
+            Self::Phyrexian(Some(c)) => write!(f, "{}/P", c.symbol()),
+            Self::ColorlessPhyrexian => write!(f, "C/P"),
+            Self::Hybrid(a, b) => write!(f, "{}/{}", a.symbol(), b.symbol()),
+            Self::PhyrexianHybrid(a, b) => write!(f, "{}/{}/P", a.symbol(), b.symbol()),
+            Self::TwoBrid(c) => write!(f, "2/{}", c.symbol()),
+            Self::ColorlessHybrid(c) => write!(f, "C/{}", c.symbol()),
         }
-        write!(f, "\"")
     }
 }
 impl From<&str> for Layout {
     fn from(value: &str) -> Self {
-        match value {
+        Self::from_name(value).unwrap_or_default()
+    }
+}
+impl Layout {
+    #[must_use]
+    pub fn from_name(value: &str) -> Option<Self> {
+        Some(match value {
+            "normal" => Self::Normal,
+            "split" => Self::Split,
             "flip" => Self::Flip,
-            "planar" | "split" => Self::Side,
-            _ => Self::Normal,
+            "transform" => Self::Transform,
+            "modal_dfc" => Self::ModalDfc,
+            "meld" => Self::Meld,
+            "leveler" => Self::Leveler,
+            "class" => Self::Class,
+            "case" => Self::Case,
+            "saga" => Self::Saga,
+            "adventure" => Self::Adventure,
+            "mutate" => Self::Mutate,
+            "prototype" => Self::Prototype,
+            "battle" => Self::Battle,
+            "planar" => Self::Planar,
+            "scheme" => Self::Scheme,
+            "vanguard" => Self::Vanguard,
+            "token" => Self::Token,
+            "double_faced_token" => Self::DoubleFacedToken,
+            "emblem" => Self::Emblem,
+            "augment" => Self::Augment,
+            "host" => Self::Host,
+            "art_series" => Self::ArtSeries,
+            "reversible_card" => Self::ReversibleCard,
+            _ => return None,
+        })
+    }
+    #[must_use]
+    pub fn is_token(self) -> bool {
+        matches!(self, Self::Token | Self::DoubleFacedToken)
+    }
+}
+impl Format {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Future => "future",
+            Self::Historic => "historic",
+            Self::Timeless => "timeless",
+            Self::Gladiator => "gladiator",
+            Self::Pioneer => "pioneer",
+            Self::Modern => "modern",
+            Self::Legacy => "legacy",
+            Self::Pauper => "pauper",
+            Self::Vintage => "vintage",
+            Self::Penny => "penny",
+            Self::Commander => "commander",
+            Self::Oathbreaker => "oathbreaker",
+            Self::StandardBrawl => "standardbrawl",
+            Self::Brawl => "brawl",
+            Self::Alchemy => "alchemy",
+            Self::PauperCommander => "paupercommander",
+            Self::Duel => "duel",
+            Self::OldSchool => "oldschool",
+            Self::Premodern => "premodern",
+            Self::Predh => "predh",
+        }
+    }
+    #[must_use]
+    pub fn has_commander(self) -> bool {
+        matches!(
+            self,
+            Self::Commander
+                | Self::Oathbreaker
+                | Self::StandardBrawl
+                | Self::Brawl
+                | Self::PauperCommander
+                | Self::Duel
+                | Self::Predh
+        )
+    }
+    #[must_use]
+    pub fn is_singleton(self) -> bool {
+        self.has_commander() || self == Self::Gladiator
+    }
+    #[must_use]
+    pub fn deck_size(self) -> (u32, Option<u32>) {
+        match self {
+            Self::Commander
+            | Self::Brawl
+            | Self::PauperCommander
+            | Self::Duel
+            | Self::Predh
+            | Self::Gladiator => (100, Some(100)),
+            Self::Oathbreaker | Self::StandardBrawl => (60, Some(60)),
+            _ => (60, None),
+        }
+    }
+    #[must_use]
+    pub fn starting_life(self) -> i32 {
+        match self {
+            Self::Commander | Self::Predh => 40,
+            Self::Brawl | Self::StandardBrawl => 25,
+            _ => 20,
         }
     }
 }
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+impl TryFrom<&str> for Format {
+    type Error = ();
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        EnumSet::<Self>::all()
+            .iter()
+            .find(|format| format.name().eq_ignore_ascii_case(value))
+            .ok_or(())
+    }
+}
+impl Legalities {
+    #[must_use]
+    pub fn is_legal(&self, format: Format) -> bool {
+        self.legal.contains(format) || self.restricted.contains(format)
+    }
+}
+impl Rarity {
+    #[must_use]
+    pub fn from_name(value: &str) -> Option<Self> {
+        Some(match value {
+            "common" | "c" => Self::Common,
+            "uncommon" | "u" => Self::Uncommon,
+            "rare" | "r" => Self::Rare,
+            "special" | "s" => Self::Special,
+            "mythic" | "m" => Self::Mythic,
+            "bonus" | "b" => Self::Bonus,
+            _ => return None,
+        })
+    }
+}
+pub fn subset_cmp<T: EnumSetType>(a: EnumSet<T>, b: EnumSet<T>) -> Option<Ordering> {
+    if a == b {
+        Some(Ordering::Equal)
+    } else if a.is_subset(b) {
+        Some(Ordering::Less)
+    } else if a.is_superset(b) {
+        Some(Ordering::Greater)
+    } else {
+        None
+    }
+}
+fn combine_cmp(orders: impl IntoIterator<Item = Option<Ordering>>) -> Option<Ordering> {
+    let mut ret = Ordering::Equal;
+    for order in orders {
+        match (ret, order?) {
+            (_, Ordering::Equal) => {}
+            (Ordering::Equal, o) => ret = o,
+            (a, b) if a != b => return None,
+            _ => {}
+        }
+    }
+    Some(ret)
+}
+impl PartialOrd for Types {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        combine_cmp([
+            self.super_type.partial_cmp(&other.super_type),
+            self.main_type.partial_cmp(&other.main_type),
+            self.sub_type.partial_cmp(&other.sub_type),
+        ])
+    }
+}
+impl PartialOrd for SuperTypes {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        subset_cmp(self.types, other.types)
+    }
+}
+impl PartialOrd for MainTypes {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        subset_cmp(self.types, other.types)
+    }
+}
+impl PartialOrd for SubTypes {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        subset_cmp(self.types, other.types)
+    }
+}
+impl PartialOrd for Colors {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        subset_cmp(self.colors, other.colors)
+    }
+}
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let mine = self.counts();
+        let theirs = other.counts();
+        let count = |counts: &[(Mana, u32)], symbol: Mana| {
+            counts
+                .iter()
+                .find(|(s, _)| *s == symbol)
+                .map_or(0, |(_, n)| *n)
+        };
+        combine_cmp(
+            mine.iter()
+                .chain(&theirs)
+                .map(|&(symbol, _)| count(&mine, symbol).partial_cmp(&count(&theirs, symbol))),
+        )
+    }
+}
 impl MainType {
     #[must_use]
     pub fn is_permanent(self) -> bool {
@@ -312,6 +589,52 @@ impl From<&str> for Types {
         ret
     }
 }
+impl Types {
+    #[must_use]
+    pub fn parse_exact(s: &str) -> Option<Self> {
+        let mut ret = Self::default();
+        for word in s.split(' ').filter(|w| !w.is_empty() && *w != "—") {
+            if let Ok(super_type) = SuperType::try_from(word) {
+                ret.super_type.types.insert(super_type);
+            } else if let Ok(ty) = MainType::try_from(word) {
+                ret.main_type.types.insert(ty);
+            } else {
+                ret.sub_type.types.insert(SubType::try_from(word).ok()?);
+            }
+        }
+        Some(ret)
+    }
+}
+impl SuperTypes {
+    #[must_use]
+    pub fn parse_exact(s: &str) -> Option<Self> {
+        let mut ret = Self::default();
+        for word in s.split(' ').filter(|w| !w.is_empty()) {
+            ret.types.insert(SuperType::try_from(word).ok()?);
+        }
+        Some(ret)
+    }
+}
+impl MainTypes {
+    #[must_use]
+    pub fn parse_exact(s: &str) -> Option<Self> {
+        let mut ret = Self::default();
+        for word in s.split(' ').filter(|w| !w.is_empty()) {
+            ret.types.insert(MainType::try_from(word).ok()?);
+        }
+        Some(ret)
+    }
+}
+impl SubTypes {
+    #[must_use]
+    pub fn parse_exact(s: &str) -> Option<Self> {
+        let mut ret = Self::default();
+        for word in s.split(' ').filter(|w| !w.is_empty()) {
+            ret.types.insert(SubType::try_from(word).ok()?);
+        }
+        Some(ret)
+    }
+}
 impl From<&str> for SuperTypes {
     fn from(s: &str) -> Self {
         let mut ret = Self::default();
@@ -348,8 +671,37 @@ impl From<&str> for SubTypes {
 impl TryFrom<&str> for Colors {
     type Error = ();
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let letters = match value {
+            "c" | "colorless" => "",
+            "white" => "w",
+            "blue" => "u",
+            "black" => "b",
+            "red" => "r",
+            "green" => "g",
+            "azorius" => "wu",
+            "dimir" => "ub",
+            "rakdos" => "br",
+            "gruul" => "rg",
+            "selesnya" => "gw",
+            "orzhov" => "wb",
+            "izzet" => "ur",
+            "golgari" => "bg",
+            "boros" => "rw",
+            "simic" => "gu",
+            "bant" => "gwu",
+            "esper" => "wub",
+            "grixis" => "ubr",
+            "jund" => "brg",
+            "naya" => "rgw",
+            "abzan" => "wbg",
+            "jeskai" => "urw",
+            "sultai" => "bgu",
+            "mardu" => "rwb",
+            "temur" => "gur",
+            value => value,
+        };
         let mut cost = Self::default();
-        for c in value.chars() {
+        for c in letters.chars() {
             match c {
                 'w' => {
                     cost.colors.insert(Color::White);
@@ -428,40 +780,156 @@ impl Colors {
 }
 impl From<&str> for Cost {
     fn from(value: &str) -> Self {
-        let mut cost = Self::default();
-        if value.is_empty() {
-            return cost;
-        }
-        cost.has_cost = true;
-        let valueinner = &value[1..value.len() - 1];
-        for c in valueinner.split("}{") {
-            let mut next = false;
-            for c in c.split('/') {
-                if next && matches!(c, "W" | "U" | "B" | "R" | "G") {
-                    cost.hybrid += 1;
-                }
-                match c {
-                    "W" => cost.white += 1,
-                    "U" => cost.blue += 1,
-                    "B" => cost.black += 1,
-                    "R" => cost.red += 1,
-                    "G" => cost.green += 1,
-                    "C" => cost.colorless += 1,
-                    "P" => cost.pay += 1,
-                    "X" => cost.var += 1,
-                    c => cost.any += c.parse::<u8>().unwrap(),
-                }
-                next = true;
+        let mut cost = Self {
+            has_cost: !value.trim().is_empty(),
+            symbols: Vec::new(),
+        };
+        let mut rest = value;
+        while let Some((_, after)) = rest.split_once('{')
+            && let Some((symbol, next)) = after.split_once('}')
+        {
+            if let Some(mana) = Mana::parse(symbol) {
+                cost.symbols.push(mana);
             }
+            rest = next;
         }
         cost
     }
 }
 impl Cost {
     #[must_use]
-    pub fn total(&self) -> u8 {
-        self.white + self.blue + self.black + self.red + self.green + self.colorless + self.any
-            - self.hybrid
+    pub fn parse(value: &str) -> Option<Self> {
+        let symbols = if value.is_empty() {
+            Vec::new()
+        } else {
+            value
+                .strip_prefix('{')?
+                .strip_suffix('}')?
+                .split("}{")
+                .map(Mana::parse)
+                .collect::<Option<_>>()?
+        };
+        Some(Self {
+            has_cost: !value.is_empty(),
+            symbols,
+        })
+    }
+    #[must_use]
+    pub fn mana_value(&self) -> f32 {
+        self.symbols
+            .iter()
+            .fold(0.0, |total, symbol| total + symbol.mana_value())
+    }
+    #[must_use]
+    pub fn colors(&self) -> EnumSet<Color> {
+        self.symbols.iter().map(|symbol| symbol.colors()).collect()
+    }
+    fn counts(&self) -> Vec<(Mana, u32)> {
+        let mut counts: Vec<(Mana, u32)> = Vec::new();
+        for &symbol in &self.symbols {
+            let (key, n) = match symbol {
+                Mana::Generic(n) => (Mana::Generic(0), n),
+                other => (other, 1),
+            };
+            if let Some((_, count)) = counts.iter_mut().find(|(s, _)| *s == key) {
+                *count += n;
+            } else {
+                counts.push((key, n));
+            }
+        }
+        counts
+    }
+}
+impl Mana {
+    #[must_use]
+    pub fn parse(symbol: &str) -> Option<Self> {
+        let parts: Vec<&str> = symbol.split('/').collect();
+        let color = Color::from_symbol;
+        Some(match parts[..] {
+            ["X"] => Self::Variable(Variable::X),
+            ["Y"] => Self::Variable(Variable::Y),
+            ["Z"] => Self::Variable(Variable::Z),
+            ["\u{221e}"] => Self::Infinite,
+            ["\u{bd}"] => Self::Half(None),
+            ["C"] => Self::Colorless,
+            ["S"] => Self::Snow,
+            ["H"] => Self::Phyrexian(None),
+            [single] => match (color(single), single.strip_prefix('H')) {
+                (Some(c), _) => Self::Color(c),
+                (None, Some(half)) => Self::Half(Some(color(half)?)),
+                (None, None) if single.bytes().all(|b| b.is_ascii_digit()) => {
+                    Self::Generic(single.parse().ok()?)
+                }
+                (None, None) => return None,
+            },
+            ["C", "P"] => Self::ColorlessPhyrexian,
+            [c, "P"] => Self::Phyrexian(Some(color(c)?)),
+            ["2", c] => Self::TwoBrid(color(c)?),
+            ["C", c] => Self::ColorlessHybrid(color(c)?),
+            [a, b] => Self::Hybrid(color(a)?, color(b)?),
+            [a, b, "P"] => Self::PhyrexianHybrid(color(a)?, color(b)?),
+            _ => return None,
+        })
+    }
+    #[must_use]
+    pub fn mana_value(self) -> f32 {
+        match self {
+            Self::Generic(n) => n as f32,
+            Self::Variable(_) => 0.0,
+            Self::Infinite => f32::INFINITY,
+            Self::Half(_) => 0.5,
+            Self::TwoBrid(_) => 2.0,
+            Self::Color(_)
+            | Self::Colorless
+            | Self::Snow
+            | Self::Phyrexian(_)
+            | Self::ColorlessPhyrexian
+            | Self::Hybrid(..)
+            | Self::PhyrexianHybrid(..)
+            | Self::ColorlessHybrid(_) => 1.0,
+        }
+    }
+    #[must_use]
+    pub fn colors(self) -> EnumSet<Color> {
+        match self {
+            Self::Half(Some(c))
+            | Self::Color(c)
+            | Self::Phyrexian(Some(c))
+            | Self::TwoBrid(c)
+            | Self::ColorlessHybrid(c) => EnumSet::only(c),
+            Self::Hybrid(a, b) | Self::PhyrexianHybrid(a, b) => a | b,
+            Self::Generic(_)
+            | Self::Variable(_)
+            | Self::Infinite
+            | Self::Half(None)
+            | Self::Colorless
+            | Self::Snow
+            | Self::Phyrexian(None)
+            | Self::ColorlessPhyrexian => EnumSet::empty(),
+        }
+    }
+}
+impl Color {
+    #[must_use]
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        Some(match symbol {
+            "W" => Self::White,
+            "U" => Self::Blue,
+            "B" => Self::Black,
+            "R" => Self::Red,
+            "G" => Self::Green,
+            _ => return None,
+        })
+    }
+    #[must_use]
+    pub fn symbol(self) -> char {
+        match self {
+            Self::White => 'W',
+            Self::Blue => 'U',
+            Self::Black => 'B',
+            Self::Red => 'R',
+            Self::Green => 'G',
+        }
     }
 }
 impl Card {
@@ -478,8 +946,8 @@ impl Card {
             || self.misc.is_some()
     }
     #[must_use]
-    pub fn filter(&self, text: &str) -> bool {
-        self.subcard.filter(text)
+    pub fn filter(&self, filter: &Filter) -> bool {
+        self.subcard.filter(filter)
     }
     #[must_use]
     pub fn flatten(mut self) -> Vec<SubCard> {
@@ -596,8 +1064,21 @@ impl DoubleEndedIterator for CardIterMut<'_> {
 }
 impl SubCard {
     #[must_use]
-    pub fn filter(&self, text: &str) -> bool {
-        self.data.front.filter(text) || self.data.back.as_ref().is_some_and(|c| c.filter(text))
+    pub fn filter(&self, filter: &Filter) -> bool {
+        self.data.filter(filter)
+    }
+    #[must_use]
+    pub fn is_sideways(&self) -> bool {
+        match self.data.layout {
+            Layout::Planar => true,
+            Layout::Battle => !self.flipped,
+            Layout::Split => !self
+                .data
+                .back
+                .as_ref()
+                .is_some_and(|back| back.oracle_text.starts_with("Aftermath")),
+            _ => false,
+        }
     }
     #[must_use]
     pub fn face(&self) -> &CardInfo {
@@ -652,242 +1133,22 @@ impl SubCard {
         }
     }
 }
-impl CardInfo {
+impl CardData {
     #[must_use]
-    pub fn filter(&self, text: &str) -> bool {
-        let textlower = text.to_ascii_lowercase();
-        let texttrim = textlower.trim();
-        let pairs = get_pairs(texttrim);
-        pairs
-            .into_iter()
-            .all(|(n, k, v, o)| self.filter_pair(n, k, v, o))
+    pub fn filter(&self, filter: &Filter) -> bool {
+        filter.matches(self, &self.front)
+            || self.back.as_ref().is_some_and(|c| filter.matches(self, c))
     }
     #[must_use]
-    pub fn filter_pair(&self, negate: bool, key: SearchKey, value: &str, ordering: Order) -> bool {
-        let res = match key {
-            SearchKey::Name => self.name.to_ascii_lowercase().contains(value),
-            SearchKey::Cmc => {
-                if let Ok(v) = value.parse() {
-                    self.mana_cost.total().cmp(&v) == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::Type => {
-                if let Ok(count) = value.parse::<usize>() {
-                    self.type_line.len() == count
-                } else if let Some(order) = self.type_line.partial_cmp(&Types::from(value)) {
-                    order == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::SuperType => {
-                if let Ok(count) = value.parse::<usize>() {
-                    self.type_line.super_type.types.len() == count
-                } else if let Some(order) = self
-                    .type_line
-                    .super_type
-                    .partial_cmp(&SuperTypes::from(value))
-                {
-                    order == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::MainType => {
-                if let Ok(count) = value.parse::<usize>() {
-                    self.type_line.main_type.types.len() == count
-                } else if let Some(order) = self
-                    .type_line
-                    .main_type
-                    .partial_cmp(&MainTypes::from(value))
-                {
-                    order == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::SubType => {
-                if let Ok(count) = value.parse::<usize>() {
-                    self.type_line.sub_type.types.len() == count
-                } else if let Some(order) =
-                    self.type_line.sub_type.partial_cmp(&SubTypes::from(value))
-                {
-                    order == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::Text => self.oracle_text.to_ascii_lowercase().contains(value),
-            SearchKey::Color => {
-                if let Ok(count) = value.parse::<usize>() {
-                    self.colors.len() == count
-                } else if let Ok(col) = Colors::try_from(value)
-                    && let Some(order) = self.colors.partial_cmp(&col)
-                {
-                    order == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::Identity => {
-                if let Ok(count) = value.parse::<usize>() {
-                    self.color_identity.len() == count
-                } else if let Ok(col) = Colors::try_from(value)
-                    && let Some(order) = self.color_identity.partial_cmp(&col)
-                {
-                    order == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::Power => {
-                if let Some(power) = self.power
-                    && let Ok(v) = value.parse()
-                {
-                    power.cmp(&v) == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::Loyalty => {
-                if let Some(loyalty) = self.loyalty
-                    && let Ok(v) = value.parse()
-                {
-                    loyalty.cmp(&v) == ordering
-                } else {
-                    return false;
-                }
-            }
-            SearchKey::Toughness => {
-                if let Some(toughness) = self.toughness
-                    && let Ok(v) = value.parse()
-                {
-                    toughness.cmp(&v) == ordering
-                } else {
-                    return false;
-                }
-            }
-        };
-        if negate { !res } else { res }
-    }
-}
-impl PartialEq<Order> for Ordering {
-    fn eq(&self, other: &Order) -> bool {
-        match other {
-            Order::Greater => matches!(self, Ordering::Greater),
-            Order::Less => matches!(self, Ordering::Less),
-            Order::Equal => matches!(self, Ordering::Equal),
-            Order::GreaterEqual => matches!(self, Ordering::Greater | Ordering::Equal),
-            Order::LessEqual => matches!(self, Ordering::Less | Ordering::Equal),
-        }
-    }
-}
-#[must_use]
-fn get_pairs(text: &str) -> Vec<(bool, SearchKey, &str, Order)> {
-    let mut quotes = false;
-    let mut quoted = false;
-    let mut order = None;
-    let mut k = 0;
-    let mut v = 0;
-    let mut pairs = Vec::new();
-    let mut key = None;
-    let mut negate = false;
-    for (i, c) in text.char_indices() {
-        match c {
-            '!' => negate = true,
-            '\"' => {
-                quoted = true;
-                quotes = !quotes;
-            }
-            '=' if !quotes => {
-                v = i + 1;
-                if order.is_none() {
-                    key = get_key(&text[if negate { k + 1 } else { k }..i]);
-                    if key.is_some() {
-                        order = Some(Order::Equal);
-                    }
-                } else if matches!(order, Some(Order::Greater)) {
-                    order = Some(Order::GreaterEqual);
-                } else if matches!(order, Some(Order::Less)) {
-                    order = Some(Order::LessEqual);
-                }
-            }
-            '<' if !quotes => {
-                v = i + 1;
-                if order.is_none() {
-                    key = get_key(&text[if negate { k + 1 } else { k }..i]);
-                    if key.is_some() {
-                        order = Some(Order::Less);
-                    }
-                }
-            }
-            '>' if !quotes => {
-                v = i + 1;
-                if order.is_none() {
-                    key = get_key(&text[if negate { k + 1 } else { k }..i]);
-                    if key.is_some() {
-                        order = Some(Order::Greater);
-                    }
-                }
-            }
-            ' ' if !quotes => {
-                if let Some(order_inner) = order
-                    && let Some(key_inner) = key
-                {
-                    pairs.push((
-                        negate,
-                        key_inner,
-                        if quoted {
-                            &text[v + 1..i - 1]
-                        } else {
-                            &text[v..i]
-                        },
-                        order_inner,
-                    ));
-                    k = i + 1;
-                }
-                order = None;
-                quoted = false;
-                negate = false;
-            }
-            _ => {}
-        }
+    pub fn set(&self) -> &str {
+        self.set_cn
+            .split_once('/')
+            .map_or(&*self.set_cn, |(set, _)| set)
+    }
+    #[must_use]
+    pub fn collector_number(&self) -> &str {
+        self.set_cn.split_once('/').map_or("", |(_, cn)| cn)
     }
-    if let Some(order_inner) = order
-        && let Some(key_inner) = key
-    {
-        pairs.push((
-            negate,
-            key_inner,
-            if quoted {
-                &text[v + 1..text.len() - 1]
-            } else {
-                &text[v..]
-            },
-            order_inner,
-        ));
-    } else {
-        pairs.push((false, SearchKey::Name, &text[k..], Order::Equal));
-    }
-    pairs
-}
-fn get_key(key: &str) -> Option<SearchKey> {
-    Some(match key {
-        "name" | "n" => SearchKey::Name,
-        "cmc" | "cost" => SearchKey::Cmc,
-        "type" | "t" => SearchKey::Type,
-        "super_type" | "ut" => SearchKey::SuperType,
-        "main_type" | "mt" => SearchKey::MainType,
-        "sub_type" | "st" => SearchKey::SubType,
-        "text" | "o" => SearchKey::Text,
-        "color" | "c" => SearchKey::Color,
-        "power" | "p" => SearchKey::Power,
-        "loyalty" | "l" => SearchKey::Loyalty,
-        "toughness" | "h" => SearchKey::Toughness,
-        _ => return None,
-    })
 }
 impl From<SubCard> for Card {
     fn from(subcard: SubCard) -> Self {
@@ -1,26 +1,71 @@
 use crate::card::{CardData, MaybeHandles};
-use crate::scryfall::Side;
+use crate::coder::DataCoder;
+use crate::config::Config;
+use crate::custom::{CUSTOM_QUALITY, is_custom};
+use crate::format::{seal, unseal};
+use crate::image::{from_compressed_bytes, to_compressed_bytes};
+use crate::names::NameIndex;
+use crate::scryfall::{Quality, Side};
+use crate::storage::{STORAGE, Storage, blocking};
 use bevy::asset::Handle;
 use bevy::image::Image;
-use bevy::platform::dirs::preferences_dir;
+use bevy::log::warn;
 use bimap::BiHashMap;
-use bitcode::{decode, encode};
+use bitcode::{Decode, Encode, decode, encode};
 use rustc_hash::FxBuildHasher;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::fs;
 use uuid::Uuid;
-pub const CACHE_FOLDER: &str = "cache";
+pub const INDEX: &str = "index.data";
 pub const DATA: &str = "card.data";
-pub const FRONT: &str = "front.png";
-pub const BACK: &str = "back.png";
+pub const NAMES: &str = "names.data";
+#[cfg(not(target_family = "wasm"))]
+pub const DEFAULT_LIMIT: u64 = 1 << 30;
+#[cfg(target_family = "wasm")]
+pub const DEFAULT_LIMIT: u64 = 3 << 20;
+const SAVE_INTERVAL: usize = 64;
 pub struct CardCache {
     pub cards: HashMap<Uuid, CardInCache, FxBuildHasher>,
-    pub in_storage: HashSet<Uuid, FxBuildHasher>,
+    pub in_storage: HashMap<Uuid, StorageEntry, FxBuildHasher>,
     pub in_progress: HashSet<Uuid, FxBuildHasher>,
     pub in_progress_set_cn: HashSet<Box<str>, FxBuildHasher>,
     pub set_cn: BiHashMap<Box<str>, Uuid, FxBuildHasher, FxBuildHasher>,
+    pub names: NameIndex,
+    pub bytes: u64,
+    pub limit: u64,
+    storage: Arc<dyn Storage>,
+    clock: u64,
+    changes: usize,
+}
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StorageEntry {
+    pub bytes: u64,
+    pub last_used: u64,
+}
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Written {
+    pub replaced: u64,
+    pub bytes: u64,
+}
+#[derive(Encode, Decode)]
+struct IndexEntry {
+    set_cn: Box<str>,
+    #[bitcode(with = "DataCoder<Uuid>")]
+    uuid: Uuid,
+    bytes: u64,
+    last_used: u64,
+}
+#[derive(Encode, Decode)]
+struct NameEntry {
+    name: Box<str>,
+    #[bitcode(with = "DataCoder<Uuid>")]
+    uuid: Uuid,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct CacheStats {
+    pub count: usize,
+    pub bytes: u64,
+    pub limit: u64,
 }
 #[derive(Clone)]
 pub enum CacheImage {
@@ -46,9 +91,6 @@ pub struct CacheRead {
     pub front_image: CacheReadImage,
     pub back_image: CacheReadImage,
 }
-fn folder() -> Option<PathBuf> {
-    preferences_dir().map(|p| p.join(crate::app_name()).join(CACHE_FOLDER))
-}
 impl CardData {
     pub fn folder_path(&self) -> String {
         folder_path(&self.set_cn, self.id)
@@ -57,39 +99,35 @@ impl CardData {
 fn folder_path(set_cn: &str, id: Uuid) -> String {
     format!("{set_cn}_{id}")
 }
+pub fn image_file(side: Side, quality: Quality) -> String {
+    format!("{side}_{}.{}", quality.name(), quality.extension())
+}
+pub fn compressed_file(side: Side, quality: Quality) -> String {
+    format!("{side}_{}.bc7", quality.name())
+}
+fn scan(keys: Vec<(Box<str>, u64)>) -> Vec<IndexEntry> {
+    let mut entries: HashMap<Uuid, IndexEntry, FxBuildHasher> = HashMap::default();
+    for (key, bytes) in keys {
+        if let Some((folder, _)) = key.rsplit_once('/')
+            && let Some((set_cn, uuid_str)) = folder.rsplit_once('_')
+            && let Ok(uuid) = uuid_str.parse()
+        {
+            entries
+                .entry(uuid)
+                .or_insert_with(|| IndexEntry {
+                    set_cn: set_cn.into(),
+                    uuid,
+                    bytes: 0,
+                    last_used: 0,
+                })
+                .bytes += bytes;
+        }
+    }
+    entries.into_values().collect()
+}
 impl Default for CardCache {
     fn default() -> Self {
-        let mut in_storage = HashSet::with_hasher(FxBuildHasher);
-        let mut set_cn = BiHashMap::with_hashers(FxBuildHasher, FxBuildHasher);
-        if let Some(folder_name) = folder() {
-            if let Ok(dir) = std::fs::read_dir(&folder_name) {
-                for set_path in dir.filter_map(Result::ok) {
-                    if let Some(set) = set_path.file_name().to_str()
-                        && let Ok(set_folder) = std::fs::read_dir(set_path.path())
-                    {
-                        for entry in set_folder.filter_map(Result::ok) {
-                            if let Some(set_uuid) = entry.file_name().to_str()
-                                && let Some((cn, uuid_str)) = set_uuid.rsplit_once('_')
-                                && let Ok(uuid) = uuid_str.parse()
-                            {
-                                in_storage.insert(uuid);
-                                set_cn.insert(format!("{set}/{cn}").into_boxed_str(), uuid);
-                            }
-                        }
-                    }
-                }
-            } else {
-                let _ = std::fs::create_dir_all(folder_name);
-            }
-        }
-        set_cn.reserve(512);
-        Self {
-            cards: HashMap::with_capacity_and_hasher(512, FxBuildHasher),
-            in_storage,
-            in_progress: HashSet::with_capacity_and_hasher(512, FxBuildHasher),
-            in_progress_set_cn: HashSet::with_capacity_and_hasher(512, FxBuildHasher),
-            set_cn,
-        }
+        Self::new(STORAGE.clone())
     }
 }
 #[derive(Debug)]
@@ -105,13 +143,69 @@ pub enum CacheResult<'a> {
     None(Identifier<'a>),
 }
 impl CardCache {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        let mut set_cn = BiHashMap::with_hashers(FxBuildHasher, FxBuildHasher);
+        set_cn.reserve(512);
+        let mut cache = Self {
+            cards: HashMap::with_capacity_and_hasher(512, FxBuildHasher),
+            in_storage: HashMap::with_hasher(FxBuildHasher),
+            in_progress: HashSet::with_capacity_and_hasher(512, FxBuildHasher),
+            in_progress_set_cn: HashSet::with_capacity_and_hasher(512, FxBuildHasher),
+            set_cn,
+            names: NameIndex::default(),
+            bytes: 0,
+            limit: DEFAULT_LIMIT,
+            storage,
+            clock: 0,
+            changes: 0,
+        };
+        let index = cache
+            .storage
+            .read(INDEX)
+            .and_then(|bytes| decode::<Vec<IndexEntry>>(&unseal(&bytes).ok()?).ok());
+        if let Some(index) = index {
+            index.into_iter().for_each(|entry| cache.add_entry(entry));
+        } else {
+            scan(cache.storage.keys())
+                .into_iter()
+                .for_each(|entry| cache.add_entry(entry));
+            cache.changes = SAVE_INTERVAL;
+        }
+        cache.load_names();
+        cache
+    }
+    fn load_names(&mut self) {
+        let names = self
+            .storage
+            .read(NAMES)
+            .and_then(|bytes| decode::<Vec<NameEntry>>(&unseal(&bytes).ok()?).ok());
+        if let Some(names) = names {
+            for entry in names {
+                if self.in_storage.contains_key(&entry.uuid) {
+                    self.names.insert_name(&entry.name, entry.uuid);
+                }
+            }
+            return;
+        }
+        for (set_cn, &uuid) in &self.set_cn {
+            let data = self
+                .storage
+                .read(&format!("{}/{DATA}", folder_path(set_cn, uuid)))
+                .and_then(|bytes| decode::<CardData>(&unseal(&bytes).ok()?).ok());
+            if let Some(data) = data {
+                self.names.insert(&data);
+            }
+        }
+        self.changes = SAVE_INTERVAL;
+    }
     pub fn clean(&mut self) {
         self.cards.retain(|_, card| !Arc::is_unique(&card.strong));
     }
     pub fn get<'b>(&mut self, uuid: Uuid) -> CacheResult<'b> {
+        self.touch(uuid);
         if let Some(val) = self.cards.get(&uuid) {
             CacheResult::Some(val.clone())
-        } else if self.in_storage.contains(&uuid) {
+        } else if self.in_storage.contains_key(&uuid) {
             self.in_progress.insert(uuid);
             let set_cn = self.set_cn.get_by_right(&uuid).unwrap();
             CacheResult::Cached(set_cn.clone(), uuid)
@@ -137,30 +231,263 @@ impl CardCache {
         let set_cn = card.strong.set_cn.clone();
         self.in_progress_set_cn.remove(&set_cn);
         self.set_cn.insert(set_cn, uuid);
+        self.names.insert(&card.strong);
         self.cards.insert(uuid, card);
         self.in_progress.remove(&uuid);
     }
+    fn add_entry(&mut self, entry: IndexEntry) {
+        self.clock = self.clock.max(entry.last_used);
+        self.bytes += entry.bytes;
+        self.set_cn.insert(entry.set_cn, entry.uuid);
+        self.in_storage.insert(
+            entry.uuid,
+            StorageEntry {
+                bytes: entry.bytes,
+                last_used: entry.last_used,
+            },
+        );
+    }
+    fn touch(&mut self, uuid: Uuid) {
+        if let Some(entry) = self.in_storage.get_mut(&uuid) {
+            self.clock += 1;
+            entry.last_used = self.clock;
+            self.changes += 1;
+        }
+    }
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            count: self.in_storage.len(),
+            bytes: self.bytes,
+            limit: self.limit,
+        }
+    }
+    pub fn stored(&mut self, uuid: Uuid, set_cn: &str, written: Written) {
+        self.clock += 1;
+        self.changes += 1;
+        let entry = self.in_storage.entry(uuid).or_default();
+        let bytes = (entry.bytes + written.bytes).saturating_sub(written.replaced);
+        self.bytes = self.bytes - entry.bytes + bytes;
+        entry.bytes = bytes;
+        entry.last_used = self.clock;
+        if !self.set_cn.contains_right(&uuid) {
+            self.set_cn.insert(set_cn.into(), uuid);
+        }
+    }
+    pub fn evict(&mut self, limit: u64) -> Vec<String> {
+        let mut folders = Vec::new();
+        if self.bytes <= limit {
+            return folders;
+        }
+        let target = limit / 10 * 9;
+        let mut lru: Vec<(u64, Uuid)> = self
+            .in_storage
+            .iter()
+            .filter(|(uuid, _)| {
+                !self.cards.contains_key(uuid)
+                    && !self.in_progress.contains(uuid)
+                    && !self.custom_entry(**uuid)
+            })
+            .map(|(uuid, entry)| (entry.last_used, *uuid))
+            .collect();
+        lru.sort_unstable();
+        for (_, uuid) in lru {
+            if self.bytes <= target {
+                break;
+            }
+            folders.extend(self.remove_storage(uuid));
+        }
+        folders
+    }
+    fn remove_storage(&mut self, uuid: Uuid) -> Option<String> {
+        let entry = self.in_storage.remove(&uuid)?;
+        self.bytes -= entry.bytes;
+        self.changes += 1;
+        let set_cn = if self.cards.contains_key(&uuid) {
+            self.set_cn.get_by_right(&uuid)?.clone()
+        } else {
+            self.set_cn.remove_by_right(&uuid)?.0
+        };
+        Some(folder_path(&set_cn, uuid))
+    }
+    fn custom_entry(&self, uuid: Uuid) -> bool {
+        self.set_cn
+            .get_by_right(&uuid)
+            .is_some_and(|set_cn| is_custom(set_cn))
+    }
+    pub fn clear(&mut self, keys: Vec<(Box<str>, u64)>) -> Vec<String> {
+        let cards = &self.cards;
+        self.set_cn
+            .retain(|set_cn, uuid| cards.contains_key(uuid) || is_custom(set_cn));
+        let set_cn = &self.set_cn;
+        self.in_storage.retain(|uuid, _| {
+            set_cn
+                .get_by_right(uuid)
+                .is_some_and(|set_cn| is_custom(set_cn))
+        });
+        self.bytes = self.in_storage.values().map(|entry| entry.bytes).sum();
+        self.changes += 1;
+        scan(keys)
+            .into_iter()
+            .filter(|entry| !is_custom(&entry.set_cn))
+            .map(|entry| folder_path(&entry.set_cn, entry.uuid))
+            .collect()
+    }
+    pub fn prune(&mut self, keys: Vec<(Box<str>, u64)>) -> Vec<String> {
+        let on_disk = scan(keys);
+        let found: HashSet<Uuid, FxBuildHasher> = on_disk.iter().map(|e| e.uuid).collect();
+        let missing: Vec<Uuid> = self
+            .in_storage
+            .keys()
+            .filter(|uuid| !found.contains(uuid))
+            .copied()
+            .collect();
+        for uuid in missing {
+            self.remove_storage(uuid);
+        }
+        for entry in on_disk {
+            if let Some(stored) = self.in_storage.get_mut(&entry.uuid) {
+                self.bytes = self.bytes - stored.bytes + entry.bytes;
+                stored.bytes = entry.bytes;
+            } else {
+                self.add_entry(entry);
+            }
+        }
+        self.changes += 1;
+        self.evict(self.limit)
+    }
+    pub fn index_files(&mut self, force: bool) -> Vec<(&'static str, Vec<u8>)> {
+        if self.changes == 0 || (!force && self.changes < SAVE_INTERVAL) {
+            return Vec::new();
+        }
+        self.changes = 0;
+        let index: Vec<IndexEntry> = self
+            .in_storage
+            .iter()
+            .filter_map(|(&uuid, entry)| {
+                Some(IndexEntry {
+                    set_cn: self.set_cn.get_by_right(&uuid)?.clone(),
+                    uuid,
+                    bytes: entry.bytes,
+                    last_used: entry.last_used,
+                })
+            })
+            .collect();
+        let names: Vec<NameEntry> = self
+            .names
+            .exact
+            .iter()
+            .filter(|(_, uuid)| self.in_storage.contains_key(uuid))
+            .map(|(name, &uuid)| NameEntry {
+                name: name.clone(),
+                uuid,
+            })
+            .collect();
+        vec![
+            (INDEX, seal(&encode(&index))),
+            (NAMES, seal(&encode(&names))),
+        ]
+    }
+    pub fn save_index(&mut self, force: bool) {
+        for (key, bytes) in self.index_files(force) {
+            self.storage.write(key, &bytes);
+        }
+    }
 }
-impl CardInCache {
-    pub async fn write_files(&self) {
-        if let Some(folder_name) = folder().map(|f| f.join(self.strong.folder_path())) {
-            let _ = fs::create_dir_all(&folder_name).await;
-            let data = encode::<CardData>(&self.strong);
-            let _ = fs::write(folder_name.join(DATA), data).await;
+async fn apply(
+    storage: &Arc<dyn Storage>,
+    folders: Vec<String>,
+    files: Vec<(&'static str, Vec<u8>)>,
+) {
+    if folders.is_empty() && files.is_empty() {
+        return;
+    }
+    let storage = storage.clone();
+    blocking(move || {
+        for folder in folders {
+            storage.remove(&folder);
+        }
+        for (key, bytes) in files {
+            storage.write(key, &bytes);
         }
+    })
+    .await;
+}
+pub async fn record_storage(config: &Config, uuid: Uuid, set_cn: &str, written: Option<Written>) {
+    let Some(written) = written else {
+        return;
+    };
+    let (folders, files) = {
+        let mut cache = config.cache.lock().await;
+        cache.stored(uuid, set_cn, written);
+        let limit = cache.limit;
+        (cache.evict(limit), cache.index_files(false))
+    };
+    apply(&config.storage, folders, files).await;
+}
+pub async fn prune_cache(config: Config) -> CacheStats {
+    let storage = config.storage.clone();
+    let mut cache = config.cache.lock().await;
+    let keys = blocking(move || storage.keys()).await.unwrap_or_default();
+    let folders = cache.prune(keys);
+    let files = cache.index_files(true);
+    apply(&config.storage, folders, files).await;
+    cache.stats()
+}
+pub async fn clear_cache(config: Config) -> CacheStats {
+    let storage = config.storage.clone();
+    let mut cache = config.cache.lock().await;
+    let keys = blocking(move || storage.keys()).await.unwrap_or_default();
+    let folders = cache.clear(keys);
+    let files = cache.index_files(true);
+    apply(&config.storage, folders, files).await;
+    cache.stats()
+}
+async fn read(storage: &Arc<dyn Storage>, key: String) -> Option<Vec<u8>> {
+    let storage = storage.clone();
+    blocking(move || storage.read(&key)).await?
+}
+async fn write(
+    storage: &Arc<dyn Storage>,
+    key: String,
+    bytes: impl AsRef<[u8]> + Send + 'static,
+) -> Option<Written> {
+    let storage = storage.clone();
+    let len = bytes.as_ref().len() as u64;
+    let replaced = blocking(move || storage.write(&key, bytes.as_ref())).await??;
+    Some(Written {
+        replaced,
+        bytes: len,
+    })
+}
+impl CardInCache {
+    pub async fn write_files(&self, storage: &Arc<dyn Storage>) -> Option<Written> {
+        let folder = self.strong.folder_path();
+        let data = seal(&encode::<CardData>(&self.strong));
+        write(storage, format!("{folder}/{DATA}"), data).await
     }
 }
 impl CacheRead {
-    pub async fn read_files(set_cn: &str, uuid: Uuid) -> Option<Self> {
-        let folder_name = folder()?.join(folder_path(set_cn, uuid));
-        let card_data = fs::read(folder_name.join(DATA)).await.ok()?;
-        let data = decode::<CardData>(&card_data).ok()?;
+    pub async fn read_files(
+        storage: &Arc<dyn Storage>,
+        set_cn: &str,
+        uuid: Uuid,
+        quality: Quality,
+    ) -> Option<Self> {
+        let folder = folder_path(set_cn, uuid);
+        let card_data = read(storage, format!("{folder}/{DATA}")).await?;
+        let payload = unseal(&card_data)
+            .inspect_err(|e| warn!("{set_cn} {uuid}: {e}, refetching"))
+            .ok()?;
+        let data = decode::<CardData>(&payload).ok()?;
         let (front_image, back_image) = get_images(
+            storage,
             set_cn,
             uuid,
             data.back.as_ref().is_some_and(|c| c.has_unique_face),
+            quality,
         )
-        .await?;
+        .await;
         let card = Self {
             strong: Arc::new(data),
             front_image,
@@ -170,33 +497,87 @@ impl CacheRead {
     }
 }
 pub async fn get_images(
+    storage: &Arc<dyn Storage>,
     set_cn: &str,
     uuid: Uuid,
     has_unique_face: bool,
-) -> Option<(CacheReadImage, CacheReadImage)> {
-    let folder_name = folder()?.join(folder_path(set_cn, uuid));
+    quality: Quality,
+) -> (CacheReadImage, CacheReadImage) {
+    let folder = folder_path(set_cn, uuid);
+    let stored_quality = if is_custom(set_cn) {
+        CUSTOM_QUALITY
+    } else {
+        quality
+    };
     let mut front_image = CacheReadImage::Missing;
     let mut back_image = CacheReadImage::None;
-    if let Ok(data) = fs::read(folder_name.join(FRONT)).await {
+    let front_file = image_file(Side::Front, stored_quality);
+    if let Some(data) = read(storage, format!("{folder}/{front_file}")).await {
         front_image = CacheReadImage::Some(data.into_boxed_slice());
     }
-    if let Ok(data) = fs::read(folder_name.join(BACK)).await {
+    let back_file = image_file(Side::Back, stored_quality);
+    if let Some(data) = read(storage, format!("{folder}/{back_file}")).await {
         back_image = CacheReadImage::Some(data.into_boxed_slice());
     } else if has_unique_face {
         back_image = CacheReadImage::Missing;
     }
-    Some((front_image, back_image))
-}
-pub async fn write_image(bytes: &[u8], set_cn: &str, uuid: Uuid, side: Side) {
-    if let Some(folder_name) = folder().map(|f| f.join(folder_path(set_cn, uuid))) {
-        let _ = fs::create_dir_all(&folder_name).await;
-        let _ = fs::write(
-            folder_name.join(match side {
-                Side::Front => FRONT,
-                Side::Back => BACK,
-            }),
-            bytes,
-        )
-        .await;
+    (front_image, back_image)
+}
+#[cfg(not(target_family = "wasm"))]
+const fn stores(_: Quality) -> bool {
+    true
+}
+#[cfg(target_family = "wasm")]
+const fn stores(quality: Quality) -> bool {
+    matches!(quality, Quality::Small)
+}
+pub async fn write_image(
+    storage: &Arc<dyn Storage>,
+    bytes: impl AsRef<[u8]> + Send + 'static,
+    set_cn: &str,
+    uuid: Uuid,
+    side: Side,
+    quality: Quality,
+) -> Option<Written> {
+    if !stores(quality) {
+        return None;
+    }
+    let folder = folder_path(set_cn, uuid);
+    let file = image_file(side, quality);
+    write(storage, format!("{folder}/{file}"), bytes).await
+}
+pub async fn read_compressed(
+    storage: &Arc<dyn Storage>,
+    set_cn: &str,
+    uuid: Uuid,
+    side: Side,
+    quality: Quality,
+) -> Option<Image> {
+    let folder = folder_path(set_cn, uuid);
+    let bytes = read(
+        storage,
+        format!("{folder}/{}", compressed_file(side, quality)),
+    )
+    .await?;
+    from_compressed_bytes(&bytes)
+}
+pub async fn write_compressed(
+    storage: &Arc<dyn Storage>,
+    image: &Image,
+    set_cn: &str,
+    uuid: Uuid,
+    side: Side,
+    quality: Quality,
+) -> Option<Written> {
+    if !stores(quality) {
+        return None;
     }
+    let folder = folder_path(set_cn, uuid);
+    let file = compressed_file(side, quality);
+    write(
+        storage,
+        format!("{folder}/{file}"),
+        to_compressed_bytes(image)?,
+    )
+    .await
 }
@@ -0,0 +1,106 @@
+use crate::card::{CardData, CardInfo};
+use crate::card_cache::{CardCache, DATA, INDEX, Written, image_file};
+use crate::format::seal;
+use crate::scryfall::{Quality, Side};
+use crate::storage::{MemoryStorage, Storage as _};
+use bitcode::encode;
+use std::sync::Arc;
+use uuid::Uuid;
+fn key(n: u128, file: &str) -> String {
+    format!("mck/{n}_{}/{file}", Uuid::from_u128(n))
+}
+fn storage() -> Arc<MemoryStorage> {
+    let storage = Arc::new(MemoryStorage::default());
+    for n in 0..4 {
+        storage.write(&key(n, DATA), &[0; 40]);
+        storage.write(&key(n, &image_file(Side::Front, Quality::Small)), &[0; 20]);
+        storage.write(&key(n, &image_file(Side::Front, Quality::Large)), &[0; 40]);
+    }
+    storage
+}
+fn remove(storage: &MemoryStorage, folders: Vec<String>) {
+    for folder in folders {
+        storage.remove(&folder);
+    }
+}
+#[test]
+fn test_evict() {
+    let storage = storage();
+    let mut cache = CardCache::new(storage.clone());
+    assert_eq!((cache.stats().count, cache.stats().bytes), (4, 400));
+    assert_eq!(
+        cache
+            .set_cn
+            .get_by_right(&Uuid::from_u128(2))
+            .map(AsRef::as_ref),
+        Some("mck/2")
+    );
+    let written = Written {
+        replaced: 40,
+        bytes: 40,
+    };
+    cache.stored(Uuid::from_u128(0), "mck/0", written);
+    remove(&storage, cache.evict(250));
+    assert_eq!((cache.stats().count, cache.stats().bytes), (2, 200));
+    assert!(storage.read(&key(0, DATA)).is_some());
+    assert!(storage.read(&key(1, DATA)).is_none());
+    assert!(storage.read(&key(2, "front_large.jpg")).is_none());
+    assert!(storage.read(&key(3, DATA)).is_some());
+    assert!(cache.set_cn.get_by_right(&Uuid::from_u128(1)).is_none());
+}
+#[test]
+fn test_index() {
+    let storage = storage();
+    let mut cache = CardCache::new(storage.clone());
+    remove(&storage, cache.evict(300));
+    cache.save_index(true);
+    assert!(storage.read(INDEX).is_some());
+    storage.write(&key(5, DATA), &[0; 10]);
+    let mut cache = CardCache::new(storage.clone());
+    assert_eq!((cache.stats().count, cache.stats().bytes), (2, 200));
+    remove(&storage, cache.prune(storage.keys()));
+    assert_eq!((cache.stats().count, cache.stats().bytes), (3, 210));
+    storage.remove(&format!("mck/5_{}", Uuid::from_u128(5)));
+    remove(&storage, cache.prune(storage.keys()));
+    assert_eq!((cache.stats().count, cache.stats().bytes), (2, 200));
+    remove(&storage, cache.clear(storage.keys()));
+    cache.save_index(true);
+    assert_eq!(cache.stats().count, 0);
+    assert_eq!(storage.keys().len(), 2);
+    assert_eq!(CardCache::new(storage).stats().count, 0);
+}
+#[test]
+fn test_custom() {
+    let storage = storage();
+    let custom = format!("custom/6_{}/{DATA}", Uuid::from_u128(6));
+    storage.write(&custom, &[0; 500]);
+    let mut cache = CardCache::new(storage.clone());
+    remove(&storage, cache.evict(300));
+    assert_eq!((cache.stats().count, cache.stats().bytes), (1, 500));
+    assert!(storage.read(&key(3, DATA)).is_none());
+    storage.write(&key(0, DATA), &[0; 40]);
+    remove(&storage, cache.clear(storage.keys()));
+    assert_eq!((cache.stats().count, cache.stats().bytes), (1, 500));
+    assert!(storage.read(&key(0, DATA)).is_none());
+    assert!(storage.read(&custom).is_some());
+}
+#[test]
+fn test_names() {
+    let storage = storage();
+    let data = CardData {
+        id: Uuid::from_u128(1),
+        set_cn: "mck/1".into(),
+        front: CardInfo {
+            name: "Llanowar Elves".into(),
+            ..CardInfo::default()
+        },
+        ..CardData::default()
+    };
+    storage.write(&key(1, DATA), &seal(&encode(&data)));
+    let mut cache = CardCache::new(storage.clone());
+    assert_eq!(cache.names.get("llanowar elves"), Some(Uuid::from_u128(1)));
+    cache.save_index(true);
+    storage.write(&key(1, DATA), &[0; 40]);
+    let cache = CardCache::new(storage);
+    assert_eq!(cache.names.get("Llanowar Elves"), Some(Uuid::from_u128(1)));
+}
@@ -0,0 +1,204 @@
+use crate::card::{CardData, CardInfo, Color, Cost, Layout, Mana, SubCard, Variable};
+use enumset::EnumSet;
+use std::cmp::Ordering;
+use std::sync::Arc;
+const MANA_SYMBOLS: &[&str] = &[
+    "{X}",
+    "{Y}",
+    "{Z}",
+    "{0}",
+    "{½}",
+    "{1}",
+    "{2}",
+    "{3}",
+    "{4}",
+    "{5}",
+    "{6}",
+    "{7}",
+    "{8}",
+    "{9}",
+    "{10}",
+    "{11}",
+    "{12}",
+    "{13}",
+    "{14}",
+    "{15}",
+    "{16}",
+    "{17}",
+    "{18}",
+    "{19}",
+    "{20}",
+    "{100}",
+    "{1000000}",
+    "{∞}",
+    "{W/U}",
+    "{W/B}",
+    "{B/R}",
+    "{B/G}",
+    "{U/B}",
+    "{U/R}",
+    "{R/G}",
+    "{R/W}",
+    "{G/W}",
+    "{G/U}",
+    "{B/G/P}",
+    "{B/R/P}",
+    "{G/U/P}",
+    "{G/W/P}",
+    "{R/G/P}",
+    "{R/W/P}",
+    "{U/B/P}",
+    "{U/R/P}",
+    "{W/B/P}",
+    "{W/U/P}",
+    "{C/W}",
+    "{C/U}",
+    "{C/B}",
+    "{C/R}",
+    "{C/G}",
+    "{2/W}",
+    "{2/U}",
+    "{2/B}",
+    "{2/R}",
+    "{2/G}",
+    "{H}",
+    "{W/P}",
+    "{U/P}",
+    "{B/P}",
+    "{R/P}",
+    "{G/P}",
+    "{C/P}",
+    "{HW}",
+    "{HR}",
+    "{W}",
+    "{U}",
+    "{B}",
+    "{R}",
+    "{G}",
+    "{C}",
+    "{S}",
+];
+const OTHER_SYMBOLS: &[&str] = &[
+    "{T}", "{Q}", "{E}", "{P}", "{PW}", "{CHAOS}", "{A}", "{TK}", "{L}", "{D}",
+];
+#[test]
+fn test_symbols() {
+    for symbol in MANA_SYMBOLS {
+        let cost = Cost::parse(symbol).unwrap();
+        assert_eq!(cost.symbols.len(), 1, "{symbol}");
+        assert_eq!(cost.to_string(), *symbol);
+    }
+    for symbol in OTHER_SYMBOLS {
+        assert_eq!(Cost::parse(symbol), None, "{symbol}");
+        assert!(Cost::from(*symbol).symbols.is_empty());
+    }
+    assert_eq!(Cost::parse("{W}{T}"), None);
+    assert_eq!(Cost::parse("W"), None);
+    assert_eq!(Cost::parse("{}"), None);
+    assert_eq!(Cost::parse("{W/U/B}"), None);
+    assert_eq!(Cost::parse(""), Some(Cost::default()));
+    assert_eq!(
+        Cost::from("{2}{T}{W/U}").symbols,
+        vec![Mana::Generic(2), Mana::Hybrid(Color::White, Color::Blue)]
+    );
+    assert!(Cost::from("{0}").has_cost);
+    assert!(!Cost::from("").has_cost);
+}
+#[test]
+fn test_mana_value() {
+    let value = |cost: &str| Cost::parse(cost).unwrap().mana_value().to_string();
+    assert_eq!(value("{2/W}"), "2");
+    assert_eq!(value("{HW}"), "0.5");
+    assert_eq!(value("{X}{X}{R}"), "1");
+    assert_eq!(value("{1000000}"), "1000000");
+    assert_eq!(value("{∞}"), "inf");
+    assert_eq!(value("{3}{W/P}{G/U/P}{C}{S}"), "7");
+    assert_eq!(value(""), "0");
+    assert_eq!(Mana::parse("X"), Some(Mana::Variable(Variable::X)));
+}
+#[test]
+fn test_colors() {
+    assert_eq!(
+        Cost::from("{2/W}{B/G/P}{C/U}{HR}").colors(),
+        Color::White | Color::Black | Color::Green | Color::Blue | Color::Red
+    );
+    assert_eq!(Cost::from("{3}{C}{S}{H}{X}").colors(), EnumSet::empty());
+}
+#[test]
+fn test_cmp() {
+    let cost = |s: &str| Cost::from(s);
+    assert!(cost("{1}{R}") < cost("{2}{R}{R}"));
+    assert!(cost("{R}") <= cost("{1}{R}"));
+    assert_eq!(
+        cost("{1}{1}{R}").partial_cmp(&cost("{2}{R}")),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(cost("{R}").partial_cmp(&cost("{G}")), None);
+    assert!(cost("{W/U}") < cost("{W/U}{W/U}"));
+}
+const LAYOUTS: &[&str] = &[
+    "normal",
+    "split",
+    "flip",
+    "transform",
+    "modal_dfc",
+    "meld",
+    "leveler",
+    "class",
+    "case",
+    "saga",
+    "adventure",
+    "mutate",
+    "prototype",
+    "battle",
+    "planar",
+    "scheme",
+    "vanguard",
+    "token",
+    "double_faced_token",
+    "emblem",
+    "augment",
+    "host",
+    "art_series",
+    "reversible_card",
+];
+fn sub_card(layout: Layout, back_text: &str, flipped: bool) -> SubCard {
+    SubCard {
+        data: Arc::new(CardData {
+            layout,
+            back: Some(Box::new(CardInfo {
+                oracle_text: back_text.into(),
+                ..CardInfo::default()
+            })),
+            ..CardData::default()
+        }),
+        flipped,
+        ..SubCard::default()
+    }
+}
+#[test]
+fn test_layouts() {
+    let layouts: Vec<Layout> = LAYOUTS
+        .iter()
+        .map(|name| Layout::from_name(name).unwrap())
+        .collect();
+    for (i, layout) in layouts.iter().enumerate() {
+        assert!(!layouts[i + 1..].contains(layout), "{layout:?}");
+    }
+    assert_eq!(Layout::from("sideways"), Layout::Normal);
+    assert!(Layout::DoubleFacedToken.is_token());
+    assert!(!Layout::ReversibleCard.is_token());
+    assert!(sub_card(Layout::Battle, "", false).is_sideways());
+    assert!(!sub_card(Layout::Battle, "", true).is_sideways());
+    assert!(sub_card(Layout::Split, "", false).is_sideways());
+    assert!(
+        !sub_card(
+            Layout::Split,
+            "Aftermath (Cast this spell only from your graveyard.)",
+            false
+        )
+        .is_sideways()
+    );
+    assert!(sub_card(Layout::Planar, "", false).is_sideways());
+    assert!(!sub_card(Layout::Transform, "", false).is_sideways());
+}
@@ -1,4 +1,4 @@
-use crate::card::{Color, MainType, SubType, SuperType};
+use crate::card::{Color, Format, MainType, SubType, SuperType};
 use bevy::math::Vec3;
 use bitcode::{Decode, Encode};
 use core::direct_const_arg;
@@ -30,9 +30,6 @@ macro_rules! coder {
         }
     };
 }
-coder!(EnumSet<SuperType>);
-coder!(EnumSet<MainType>);
-coder!(EnumSet<SubType>);
 coder!(EnumSet<Color>);
 coder!(Vec3);
 coder!(Uuid);
@@ -53,3 +50,49 @@ impl From<DataCoderBoxUuid> for Box<[Uuid]> {
         value.data.into_iter().map(Uuid::from_u128).collect()
     }
 }
+#[derive(Encode, Decode)]
+#[repr(transparent)]
+pub struct DataCoderOptionUuid {
+    pub data: Option<u128>,
+}
+impl From<&Option<Uuid>> for DataCoderOptionUuid {
+    fn from(value: &Option<Uuid>) -> Self {
+        Self {
+            data: value.map(Uuid::as_u128),
+        }
+    }
+}
+impl From<DataCoderOptionUuid> for Option<Uuid> {
+    fn from(value: DataCoderOptionUuid) -> Self {
+        value.data.map(Uuid::from_u128)
+    }
+}
+#[derive(Encode, Decode)]
+#[repr(transparent)]
+pub struct NameCoder {
+    pub names: Box<[Box<str>]>,
+}
+macro_rules! name_coder {
+    ($ty:ty) => {
+        impl From<&EnumSet<$ty>> for NameCoder {
+            fn from(value: &EnumSet<$ty>) -> Self {
+                Self {
+                    names: value.iter().map(|t| t.to_string().into()).collect(),
+                }
+            }
+        }
+        impl From<NameCoder> for EnumSet<$ty> {
+            fn from(value: NameCoder) -> Self {
+                value
+                    .names
+                    .iter()
+                    .filter_map(|name| <$ty>::try_from(name.as_ref()).ok())
+                    .collect()
+            }
+        }
+    };
+}
+name_coder!(SuperType);
+name_coder!(MainType);
+name_coder!(SubType);
+name_coder!(Format);
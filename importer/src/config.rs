@@ -0,0 +1,61 @@
+use crate::card_cache::CardCache;
+use crate::scryfall::CACHE;
+use crate::storage::{STORAGE, Storage};
+use reqwest::Client;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+#[cfg(target_family = "wasm")]
+use tokio_with_wasm as tokio;
+pub const API_URL: &str = "https://api.scryfall.com";
+pub const CARD_URL: &str = "https://cards.scryfall.io";
+#[derive(Clone)]
+pub struct Config {
+    pub client: Client,
+    pub api_url: Arc<str>,
+    pub card_url: Arc<str>,
+    pub compress: bool,
+    pub cache: Arc<Mutex<CardCache>>,
+    pub storage: Arc<dyn Storage>,
+}
+impl Debug for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("api_url", &self.api_url)
+            .field("card_url", &self.card_url)
+            .field("compress", &self.compress)
+            .finish_non_exhaustive()
+    }
+}
+impl Config {
+    #[must_use]
+    pub fn new(client: Client) -> Self {
+        Self::build(client, CACHE.clone(), STORAGE.clone())
+    }
+    #[must_use]
+    pub fn from_storage(client: Client, storage: Arc<dyn Storage>) -> Self {
+        let cache = Arc::new(Mutex::new(CardCache::new(storage.clone())));
+        Self::build(client, cache, storage)
+    }
+    fn build(client: Client, cache: Arc<Mutex<CardCache>>, storage: Arc<dyn Storage>) -> Self {
+        Self {
+            client,
+            api_url: API_URL.into(),
+            card_url: CARD_URL.into(),
+            compress: false,
+            cache,
+            storage,
+        }
+    }
+    #[must_use]
+    pub fn with_urls(mut self, api_url: &str, card_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').into();
+        self.card_url = card_url.trim_end_matches('/').into();
+        self
+    }
+    #[must_use]
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+}
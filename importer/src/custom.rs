@@ -0,0 +1,145 @@
+use crate::card::{CardData, CardInfo, Layout, MaybeHandles, SubCard, Types};
+use crate::card_cache::{CacheReadImage, CardInCache, get_images, record_storage, write_image};
+use crate::config::Config;
+use crate::format::checksum;
+use crate::scryfall::{IMAGES_TO_PROCESS, Quality, Side, decode_bytes};
+use std::sync::Arc;
+use uuid::{Builder, Uuid, Version};
+pub const CUSTOM_SET: &str = "custom";
+pub const CUSTOM_NAME: &str = "Custom Card";
+pub const CUSTOM_QUALITY: Quality = Quality::Png;
+pub const MAX_IMAGE_BYTES: usize = 16 << 20;
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomInfo {
+    pub name: String,
+    pub type_line: String,
+    pub oracle_text: String,
+}
+#[must_use]
+pub fn is_custom(set_cn: &str) -> bool {
+    set_cn
+        .split_once('/')
+        .is_some_and(|(set, _)| set == CUSTOM_SET)
+}
+fn custom_set_cn(id: Uuid) -> String {
+    format!("{CUSTOM_SET}/{}", &id.simple().to_string()[..8])
+}
+#[must_use]
+pub fn is_valid_custom(data: &CardData, image: &[u8]) -> bool {
+    let mut bytes = *data.id.as_bytes();
+    bytes[..8].copy_from_slice(&checksum(image).to_le_bytes());
+    data.id.get_version() == Some(Version::Custom)
+        && Builder::from_custom_bytes(bytes).into_uuid() == data.id
+        && *data.set_cn == custom_set_cn(data.id)
+}
+impl CustomInfo {
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        let mut fields = text.splitn(3, '|').map(str::trim);
+        Self {
+            name: fields.next().unwrap_or_default().to_owned(),
+            type_line: fields.next().unwrap_or_default().to_owned(),
+            oracle_text: fields.next().unwrap_or_default().replace("\\n", "\n"),
+        }
+    }
+    #[must_use]
+    pub fn uuid(&self, image: &[u8]) -> Uuid {
+        let info = format!("{}|{}|{}", self.name, self.type_line, self.oracle_text);
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&checksum(image).to_le_bytes());
+        bytes[8..].copy_from_slice(&checksum(info.as_bytes()).to_le_bytes());
+        Builder::from_custom_bytes(bytes).into_uuid()
+    }
+    #[must_use]
+    pub fn card_data(&self, image: &[u8]) -> CardData {
+        let id = self.uuid(image);
+        let name = if self.name.is_empty() {
+            CUSTOM_NAME
+        } else {
+            &self.name
+        };
+        CardData {
+            id,
+            set_cn: custom_set_cn(id).into(),
+            front: CardInfo {
+                name: name.into(),
+                type_line: Types::from(self.type_line.as_str()),
+                oracle_text: self.oracle_text.as_str().into(),
+                ..CardInfo::default()
+            },
+            layout: Layout::Normal,
+            ..CardData::default()
+        }
+    }
+}
+pub async fn add_custom(config: Config, data: CardData, image: Vec<u8>) -> Option<SubCard> {
+    if image.len() > MAX_IMAGE_BYTES || !is_valid_custom(&data, &image) {
+        return None;
+    }
+    let uuid = data.id;
+    let bytes = Arc::<[u8]>::from(image);
+    let decoded = decode_bytes(uuid, bytes.clone()).await?;
+    let strong = Arc::new(data);
+    let card = CardInCache {
+        strong: strong.clone(),
+        face_handles: MaybeHandles::Waiting,
+        back_handles: MaybeHandles::None,
+    };
+    let written = write_image(
+        &config.storage,
+        bytes,
+        &strong.set_cn,
+        uuid,
+        Side::Front,
+        CUSTOM_QUALITY,
+    )
+    .await;
+    if written.is_some() {
+        record_storage(&config, uuid, &strong.set_cn, written).await;
+        let bytes_stored = card.write_files(&config.storage).await;
+        record_storage(&config, uuid, &strong.set_cn, bytes_stored).await;
+    }
+    config.cache.lock().await.insert(card);
+    IMAGES_TO_PROCESS
+        .lock()
+        .await
+        .insert(uuid, (Some(decoded), None));
+    Some(SubCard {
+        data: strong,
+        face_handles: MaybeHandles::Waiting,
+        back_handles: MaybeHandles::None,
+        flipped: false,
+    })
+}
+pub async fn receive_custom(config: Config, data: CardData, image: Vec<u8>) -> Option<SubCard> {
+    if config.cache.lock().await.cards.contains_key(&data.id) {
+        return None;
+    }
+    add_custom(config, data, image).await
+}
+pub async fn custom_cards(config: Config) -> Vec<(CardData, Vec<u8>)> {
+    let custom: Vec<Arc<CardData>> = config
+        .cache
+        .lock()
+        .await
+        .cards
+        .values()
+        .filter(|card| is_custom(&card.strong.set_cn))
+        .map(|card| card.strong.clone())
+        .collect();
+    let mut cards = Vec::with_capacity(custom.len());
+    for data in custom {
+        let (front, _) = get_images(
+            &config.storage,
+            &data.set_cn,
+            data.id,
+            false,
+            CUSTOM_QUALITY,
+        )
+        .await;
+        if let CacheReadImage::Some(image) = front {
+            cards.push(((*data).clone(), image.into_vec()));
+        }
+    }
+    cards
+}
@@ -0,0 +1,59 @@
+use crate::card::{MainType, SubType};
+use crate::custom::{CUSTOM_NAME, CustomInfo, is_custom, is_valid_custom};
+use uuid::Uuid;
+use uuid::Version;
+#[test]
+fn test_parse() {
+    let info = CustomInfo::parse(" Grizzly Bears | Creature — Bear | Vanilla.\\nReally. ");
+    assert_eq!(info.name, "Grizzly Bears");
+    assert_eq!(info.type_line, "Creature — Bear");
+    assert_eq!(info.oracle_text, "Vanilla.\nReally.");
+    assert_eq!(
+        CustomInfo::parse("Just A Name"),
+        CustomInfo {
+            name: "Just A Name".to_owned(),
+            ..CustomInfo::default()
+        }
+    );
+    assert_eq!(CustomInfo::parse("a | b | c | d").oracle_text, "c | d");
+}
+#[test]
+fn test_card_data() {
+    let info = CustomInfo::parse("Grizzly Bears | Creature — Bear | ");
+    let image = b"not really a png".as_slice();
+    let data = info.card_data(image);
+    assert_eq!(data.id, info.uuid(image));
+    assert_eq!(data.id.get_version(), Some(Version::Custom));
+    assert_ne!(data.id, info.uuid(b"another image"));
+    assert_ne!(data.id, CustomInfo::default().uuid(image));
+    assert!(is_custom(&data.set_cn));
+    assert!(!is_custom("lea/161"));
+    assert!(!is_custom("customs/1"));
+    assert_eq!(&*data.front.name, "Grizzly Bears");
+    assert!(
+        data.front
+            .type_line
+            .main_type
+            .types
+            .contains(MainType::Creature)
+    );
+    assert!(data.front.type_line.sub_type.types.contains(SubType::Bear));
+    assert_eq!(
+        &*CustomInfo::default().card_data(image).front.name,
+        CUSTOM_NAME
+    );
+}
+#[test]
+fn test_valid_custom() {
+    let info = CustomInfo::parse("Grizzly Bears | Creature — Bear | ");
+    let image = b"not really a png".as_slice();
+    let data = info.card_data(image);
+    assert!(is_valid_custom(&data, image));
+    assert!(!is_valid_custom(&data, b"another image"));
+    let mut escape = data.clone();
+    escape.set_cn = "custom/../../../x".into();
+    assert!(!is_valid_custom(&escape, image));
+    let mut scryfall = data.clone();
+    scryfall.id = Uuid::from_u128(0x56eb_8c6d_9f4e_4b0c_8a2a_2e30_c4f1_0a7b);
+    assert!(!is_valid_custom(&scryfall, image));
+}
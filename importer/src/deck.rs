@@ -0,0 +1,427 @@
+use crate::card::{CardData, Format, MainType, SubType, SuperType};
+use crate::custom::is_custom;
+use enumset::EnumSet;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Commander,
+    Companion,
+    Main,
+    Sideboard,
+    Maybeboard,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeckEntry {
+    pub count: u32,
+    pub name: String,
+    pub set_cn: Option<String>,
+    pub section: Section,
+}
+#[derive(Debug, Clone)]
+pub struct DeckCard {
+    pub count: u32,
+    pub section: Section,
+    pub data: Arc<CardData>,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    NotLegal(Box<str>),
+    Banned(Box<str>),
+    Restricted(Box<str>),
+    TooManyCopies {
+        name: Box<str>,
+        count: u32,
+        limit: u32,
+    },
+    DeckSize {
+        count: u32,
+        min: u32,
+        max: Option<u32>,
+    },
+    SideboardSize(u32),
+    NoCommander,
+    InvalidCommander(Box<str>),
+    InvalidPartners(Box<str>, Box<str>),
+    TooManyCommanders(u32),
+    ColorIdentity(Box<str>),
+    NotCompanion(Box<str>),
+    Companion(Box<str>),
+}
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotLegal(name) => write!(f, "{name} is not legal"),
+            Self::Banned(name) => write!(f, "{name} is banned"),
+            Self::Restricted(name) => write!(f, "{name} is restricted to one copy"),
+            Self::TooManyCopies { name, count, limit } => {
+                write!(f, "{count} copies of {name}, at most {limit} allowed")
+            }
+            Self::DeckSize {
+                count,
+                min,
+                max: Some(max),
+            } if min == max => write!(f, "deck has {count} cards, needs exactly {min}"),
+            Self::DeckSize {
+                count,
+                min,
+                max: Some(max),
+            } => write!(f, "deck has {count} cards, needs {min} to {max}"),
+            Self::DeckSize {
+                count,
+                min,
+                max: None,
+            } => write!(f, "deck has {count} cards, needs at least {min}"),
+            Self::SideboardSize(count) => {
+                write!(f, "sideboard has {count} cards, at most 15 allowed")
+            }
+            Self::NoCommander => write!(f, "deck has no commander"),
+            Self::InvalidCommander(name) => write!(f, "{name} can not be your commander"),
+            Self::InvalidPartners(a, b) => write!(f, "{a} and {b} can not be commanders together"),
+            Self::TooManyCommanders(count) => write!(f, "deck has {count} commanders"),
+            Self::ColorIdentity(name) => {
+                write!(f, "{name} is outside the commander's color identity")
+            }
+            Self::NotCompanion(name) => write!(f, "{name} is not a companion"),
+            Self::Companion(name) => {
+                write!(f, "deck does not meet {name}'s companion condition")
+            }
+        }
+    }
+}
+impl Section {
+    fn from_header(line: &str) -> Option<Self> {
+        Some(
+            match line.trim_end_matches(':').trim().to_lowercase().as_str() {
+                "commander" | "commanders" => Self::Commander,
+                "companion" => Self::Companion,
+                "deck" | "main" | "mainboard" | "main deck" => Self::Main,
+                "sideboard" | "side" => Self::Sideboard,
+                "maybeboard" | "maybe" | "considering" => Self::Maybeboard,
+                _ => return None,
+            },
+        )
+    }
+}
+impl DeckEntry {
+    #[must_use]
+    pub fn parse(line: &str, current: Section) -> Option<Self> {
+        let (listed, rest) = match line.strip_prefix("SB:") {
+            Some(rest) => (Section::Sideboard, rest.trim_start()),
+            None => (current, line),
+        };
+        let (count, counted) = rest
+            .split_once(' ')
+            .and_then(|(count, name)| {
+                Some((
+                    count.trim_end_matches(['x', 'X']).parse().ok()?,
+                    name.trim(),
+                ))
+            })
+            .unwrap_or((1, rest));
+        let (section, full) = match counted.strip_suffix("*CMDR*") {
+            Some(name) => (Section::Commander, name.trim_end()),
+            None => (listed, counted),
+        };
+        let (name, set_cn) = full
+            .rsplit_once(" (")
+            .and_then(|(card, rest)| {
+                let (set, number) = rest.split_once(')')?;
+                set.chars().all(char::is_alphanumeric).then(|| {
+                    let cn = number.split_whitespace().next();
+                    (card, cn.map(|n| format!("{}/{n}", set.to_lowercase())))
+                })
+            })
+            .unwrap_or((full, None));
+        let trimmed = name.trim();
+        (count != 0 && !trimmed.is_empty()).then(|| Self {
+            count,
+            name: trimmed.to_owned(),
+            set_cn,
+            section,
+        })
+    }
+}
+#[must_use]
+pub fn parse_deck(text: &str) -> Vec<DeckEntry> {
+    let mut section = Section::Main;
+    let mut entries = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = Section::from_header(line) {
+            section = header;
+        } else if let Some(entry) = DeckEntry::parse(line, section) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+fn is_type(data: &CardData, main_type: MainType) -> bool {
+    data.front.type_line.main_type.types.contains(main_type)
+}
+fn is_sub_type(data: &CardData, sub_type: SubType) -> bool {
+    data.front.type_line.sub_type.types.contains(sub_type)
+}
+fn has_text(data: &CardData, text: &str) -> bool {
+    data.front.oracle_text.contains(text)
+}
+fn copy_limit(format: Format, data: &CardData) -> Option<u32> {
+    if data
+        .front
+        .type_line
+        .super_type
+        .types
+        .contains(SuperType::Basic)
+        || has_text(data, "A deck can have any number of cards named")
+    {
+        None
+    } else if has_text(data, "A deck can have up to seven cards named") {
+        Some(7)
+    } else if has_text(data, "A deck can have up to nine cards named") {
+        Some(9)
+    } else if format.is_singleton() {
+        Some(1)
+    } else {
+        Some(4)
+    }
+}
+fn can_command(format: Format, data: &CardData) -> bool {
+    let legendary = data
+        .front
+        .type_line
+        .super_type
+        .types
+        .contains(SuperType::Legendary);
+    match format {
+        Format::Oathbreaker => {
+            is_type(data, MainType::Planeswalker)
+                || is_type(data, MainType::Instant)
+                || is_type(data, MainType::Sorcery)
+        }
+        Format::PauperCommander => is_type(data, MainType::Creature),
+        Format::Brawl | Format::StandardBrawl => {
+            legendary
+                && (is_type(data, MainType::Creature) || is_type(data, MainType::Planeswalker))
+        }
+        _ => {
+            legendary
+                && (is_type(data, MainType::Creature) || is_sub_type(data, SubType::Background))
+                || has_text(data, "can be your commander")
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Partner<'a> {
+    Any,
+    With(&'a str),
+    Group(&'a str),
+}
+fn keywords(data: &CardData) -> impl Iterator<Item = &str> {
+    data.front.oracle_text.lines().map(|line| {
+        line.split_once(" (")
+            .map_or(line, |(keyword, _)| keyword)
+            .trim()
+    })
+}
+fn partner(data: &CardData) -> Option<Partner<'_>> {
+    keywords(data).find_map(|line| {
+        if let Some(name) = line.strip_prefix("Partner with ") {
+            Some(Partner::With(name))
+        } else if let Some(group) = line.strip_prefix("Partner—") {
+            Some(Partner::Group(group))
+        } else if line.eq_ignore_ascii_case("Friends forever") {
+            Some(Partner::Group("Friends forever"))
+        } else {
+            line.split(", ")
+                .any(|keyword| keyword.eq_ignore_ascii_case("Partner"))
+                .then_some(Partner::Any)
+        }
+    })
+}
+fn can_partner(format: Format, a: &CardData, b: &CardData) -> bool {
+    if format == Format::Oathbreaker {
+        return is_type(a, MainType::Planeswalker) != is_type(b, MainType::Planeswalker);
+    }
+    let background = |x: &CardData, y: &CardData| {
+        keywords(x).any(|line| line == "Choose a Background") && is_sub_type(y, SubType::Background)
+    };
+    let partners = match (partner(a), partner(b)) {
+        (Some(Partner::Any), Some(Partner::Any)) => true,
+        (Some(Partner::With(x)), Some(Partner::With(y))) => {
+            &*b.front.name == x && &*a.front.name == y
+        }
+        (Some(Partner::Group(x)), Some(Partner::Group(y))) => x == y,
+        _ => false,
+    };
+    partners || background(a, b) || background(b, a)
+}
+fn mana_value(data: &CardData) -> u32 {
+    data.front.mana_cost.mana_value() as u32
+}
+fn meets_companion(name: &str, deck: &[(u32, &CardData)], min_size: u32) -> bool {
+    let mut nonland = deck
+        .iter()
+        .filter(|(_, data)| !is_type(data, MainType::Land));
+    let mut permanents = deck
+        .iter()
+        .filter(|(_, data)| data.front.type_line.is_permanent());
+    match name {
+        "Gyruda, Doom of Depths" => nonland.all(|(_, data)| mana_value(data).is_multiple_of(2)),
+        "Jegantha, the Wellspring" => deck.iter().all(|(_, data)| {
+            let symbols = &data.front.mana_cost.symbols;
+            symbols
+                .iter()
+                .enumerate()
+                .all(|(i, symbol)| !symbols[i + 1..].contains(symbol))
+        }),
+        "Kaheera, the Orphanguard" => deck
+            .iter()
+            .filter(|(_, data)| is_type(data, MainType::Creature))
+            .all(|(_, data)| {
+                [
+                    SubType::Cat,
+                    SubType::Elemental,
+                    SubType::Nightmare,
+                    SubType::Dinosaur,
+                    SubType::Beast,
+                ]
+                .into_iter()
+                .any(|sub_type| is_sub_type(data, sub_type))
+            }),
+        "Keruga, the Macrosage" => nonland.all(|(_, data)| mana_value(data) >= 3),
+        "Lurrus of the Dream-Den" => permanents.all(|(_, data)| mana_value(data) <= 2),
+        "Lutri, the Spellchaser" => nonland.all(|(count, _)| *count == 1),
+        "Obosh, the Preypiercer" => nonland.all(|(_, data)| !mana_value(data).is_multiple_of(2)),
+        "Umori, the Collector" => {
+            let shared = nonland.fold(None, |shared, (_, data)| {
+                let types = data.front.type_line.main_type.types;
+                Some(shared.map_or(types, |common| common & types))
+            });
+            shared.is_none_or(|common: EnumSet<MainType>| !common.is_empty())
+        }
+        "Yorion, Sky Nomad" => deck.iter().map(|(count, _)| count).sum::<u32>() >= min_size + 20,
+        "Zirda, the Dawnwaker" => permanents.all(|(_, data)| {
+            is_type(data, MainType::Land)
+                || data
+                    .front
+                    .oracle_text
+                    .lines()
+                    .any(|line| line.contains(':'))
+        }),
+        _ => true,
+    }
+}
+#[must_use]
+pub fn validate(format: Format, deck: &[DeckCard]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let cards: Vec<&DeckCard> = deck
+        .iter()
+        .filter(|card| card.section != Section::Maybeboard)
+        .collect();
+    let mut totals: Vec<(&str, u32, &CardData)> = Vec::new();
+    let mut indices: HashMap<&str, usize> = HashMap::new();
+    for card in &cards {
+        let name = &*card.data.front.name;
+        if let Some(&i) = indices.get(name) {
+            totals[i].1 += card.count;
+        } else {
+            indices.insert(name, totals.len());
+            totals.push((name, card.count, &card.data));
+        }
+    }
+    for &(name, count, data) in &totals {
+        if is_custom(&data.set_cn) {
+            continue;
+        }
+        let legalities = &data.legalities;
+        if legalities.banned.contains(format) {
+            violations.push(Violation::Banned(name.into()));
+        } else if legalities.restricted.contains(format) && count > 1 {
+            violations.push(Violation::Restricted(name.into()));
+        } else if !legalities.is_legal(format) {
+            violations.push(Violation::NotLegal(name.into()));
+        }
+        if let Some(limit) = copy_limit(format, data)
+            && count > limit
+        {
+            violations.push(Violation::TooManyCopies {
+                name: name.into(),
+                count,
+                limit,
+            });
+        }
+    }
+    let count = |section: Section| -> u32 {
+        cards
+            .iter()
+            .filter(|card| card.section == section)
+            .map(|card| card.count)
+            .sum()
+    };
+    let (min, max) = format.deck_size();
+    let size = count(Section::Main) + count(Section::Commander);
+    if size < min || max.is_some_and(|limit| size > limit) {
+        violations.push(Violation::DeckSize {
+            count: size,
+            min,
+            max,
+        });
+    }
+    let sideboard = count(Section::Sideboard) + count(Section::Companion);
+    if !format.has_commander() && sideboard > 15 {
+        violations.push(Violation::SideboardSize(sideboard));
+    }
+    let starting: Vec<(u32, &CardData)> = cards
+        .iter()
+        .filter(|card| matches!(card.section, Section::Main | Section::Commander))
+        .map(|card| (card.count, &*card.data))
+        .collect();
+    if format.has_commander() {
+        let commanders: Vec<&CardData> = cards
+            .iter()
+            .filter(|card| card.section == Section::Commander)
+            .flat_map(|card| (0..card.count).map(|_| &*card.data))
+            .collect();
+        match commanders.as_slice() {
+            [] => violations.push(Violation::NoCommander),
+            [a, b] if !can_partner(format, a, b) => violations.push(Violation::InvalidPartners(
+                a.front.name.clone(),
+                b.front.name.clone(),
+            )),
+            [_] | [_, _] => {}
+            _ => violations.push(Violation::TooManyCommanders(commanders.len() as u32)),
+        }
+        for commander in &commanders {
+            if !can_command(format, commander) {
+                violations.push(Violation::InvalidCommander(commander.front.name.clone()));
+            }
+        }
+        if !commanders.is_empty() {
+            let identity = commanders
+                .iter()
+                .fold(EnumSet::empty(), |identity, commander| {
+                    identity | commander.front.color_identity.colors
+                });
+            for &(name, _, data) in &totals {
+                if !identity.is_superset(data.front.color_identity.colors) {
+                    violations.push(Violation::ColorIdentity(name.into()));
+                }
+            }
+        }
+    }
+    for card in cards
+        .iter()
+        .filter(|card| card.section == Section::Companion)
+    {
+        let name = &*card.data.front.name;
+        if !has_text(&card.data, "Companion —") {
+            violations.push(Violation::NotCompanion(name.into()));
+        } else if !meets_companion(name, &starting, min) {
+            violations.push(Violation::Companion(name.into()));
+        }
+    }
+    violations
+}
@@ -0,0 +1,301 @@
+use crate::card::{CardData, Format};
+use crate::deck::{DeckCard, DeckEntry, Section, Violation, parse_deck, validate};
+use crate::test_cards::card;
+use std::sync::Arc;
+fn entry(count: u32, data: &Arc<CardData>, section: Section) -> DeckCard {
+    DeckCard {
+        count,
+        section,
+        data: data.clone(),
+    }
+}
+fn filler(count: u32, identity: &str) -> Vec<DeckCard> {
+    (0..count)
+        .map(|i| {
+            let data =
+                Arc::new(card(&format!("Filler {i}"), "{2}", "Artifact", identity).with_legal());
+            entry(1, &data, Section::Main)
+        })
+        .collect()
+}
+#[test]
+fn test_parse_deck() {
+    let entries = parse_deck(
+        "// exported\nCommander\n1 Atraxa, Praetors' Voice (2X2) 190\n\nDeck\n4x Lightning Bolt\n\
+         1 B.F.M. (Big Furry Monster)\nSB: 2 Duress (M19) 94\nSideboard:\n3 Negate\n\
+         1 Sol Ring *CMDR*\nMaybeboard\n1 Opt\n",
+    );
+    let listed = [
+        (
+            1,
+            "Atraxa, Praetors' Voice",
+            Some("2x2/190"),
+            Section::Commander,
+        ),
+        (4, "Lightning Bolt", None, Section::Main),
+        (1, "B.F.M. (Big Furry Monster)", None, Section::Main),
+        (2, "Duress", Some("m19/94"), Section::Sideboard),
+        (3, "Negate", None, Section::Sideboard),
+        (1, "Sol Ring", None, Section::Commander),
+        (1, "Opt", None, Section::Maybeboard),
+    ];
+    let expected: Vec<DeckEntry> = listed
+        .into_iter()
+        .map(|(count, name, set_cn, section)| DeckEntry {
+            count,
+            name: name.to_owned(),
+            set_cn: set_cn.map(str::to_owned),
+            section,
+        })
+        .collect();
+    assert_eq!(entries, expected);
+    assert_eq!(DeckEntry::parse("0 Opt", Section::Main), None);
+    assert_eq!(
+        DeckEntry::parse("Opt", Section::Main).map(|e| e.count),
+        Some(1)
+    );
+}
+#[test]
+fn test_constructed() {
+    let bolt = Arc::new(card("Lightning Bolt", "{R}", "Instant", "r").with_legal());
+    let mut deck = filler(56, "");
+    deck.push(entry(4, &bolt, Section::Main));
+    assert_eq!(validate(Format::Modern, &deck), vec![]);
+    deck.push(entry(1, &bolt, Section::Sideboard));
+    assert_eq!(
+        validate(Format::Modern, &deck),
+        vec![Violation::TooManyCopies {
+            name: "Lightning Bolt".into(),
+            count: 5,
+            limit: 4
+        }]
+    );
+    deck.pop();
+    deck.pop();
+    assert_eq!(
+        validate(Format::Modern, &deck),
+        vec![Violation::DeckSize {
+            count: 56,
+            min: 60,
+            max: None
+        }]
+    );
+    let mut deck = filler(60, "");
+    let land = Arc::new(card("Island", "", "Basic Land — Island", "").with_legal());
+    let negate = Arc::new(card("Negate", "{1}{U}", "Instant", "u").with_legal());
+    deck.push(entry(20, &land, Section::Main));
+    deck.push(entry(16, &negate, Section::Sideboard));
+    let mut banned = (*negate).clone();
+    banned.legalities.banned.insert(Format::Modern);
+    deck.push(entry(1, &Arc::new(banned), Section::Maybeboard));
+    assert_eq!(
+        validate(Format::Modern, &deck),
+        vec![
+            Violation::TooManyCopies {
+                name: "Negate".into(),
+                count: 16,
+                limit: 4
+            },
+            Violation::SideboardSize(16)
+        ]
+    );
+}
+#[test]
+fn test_legality() {
+    let mut data = card("Sol Ring", "{1}", "Artifact", "");
+    data.legalities.restricted.insert(Format::Vintage);
+    data.legalities.banned.insert(Format::Legacy);
+    let ring = Arc::new(data);
+    let mut deck = filler(58, "");
+    deck.push(entry(2, &ring, Section::Main));
+    assert_eq!(
+        validate(Format::Vintage, &deck),
+        vec![Violation::Restricted("Sol Ring".into())]
+    );
+    assert_eq!(
+        validate(Format::Legacy, &deck),
+        vec![Violation::Banned("Sol Ring".into())]
+    );
+    assert_eq!(
+        validate(Format::Modern, &deck),
+        vec![Violation::NotLegal("Sol Ring".into())]
+    );
+    let data = card("Homebrew", "{1}", "Artifact", "").with_set_cn("custom/0a1b2c3d");
+    deck.pop();
+    deck.push(entry(2, &Arc::new(data), Section::Main));
+    assert_eq!(validate(Format::Modern, &deck), vec![]);
+}
+#[test]
+fn test_commander() {
+    let atraxa = Arc::new(
+        card(
+            "Atraxa, Praetors' Voice",
+            "{G}{W}{U}{B}",
+            "Legendary Creature — Phyrexian Angel Horror",
+            "wubg",
+        )
+        .with_text("Flying, vigilance, deathtouch, lifelink")
+        .with_legal(),
+    );
+    let mut deck = filler(99, "wubg");
+    deck.push(entry(1, &atraxa, Section::Commander));
+    assert_eq!(validate(Format::Commander, &deck), vec![]);
+    let bolt = Arc::new(card("Lightning Bolt", "{R}", "Instant", "r").with_legal());
+    deck[0] = entry(1, &bolt, Section::Main);
+    let commander = deck.pop().unwrap();
+    deck.push(entry(1, &bolt, Section::Main));
+    assert_eq!(
+        validate(Format::Commander, &deck),
+        vec![
+            Violation::TooManyCopies {
+                name: "Lightning Bolt".into(),
+                count: 2,
+                limit: 1
+            },
+            Violation::NoCommander
+        ]
+    );
+    deck.pop();
+    deck.push(commander);
+    assert_eq!(
+        validate(Format::Commander, &deck),
+        vec![Violation::ColorIdentity("Lightning Bolt".into())]
+    );
+    let ring = Arc::new(card("Sol Ring", "{1}", "Artifact", "").with_legal());
+    let mut deck = filler(98, "");
+    deck.push(entry(2, &ring, Section::Commander));
+    assert_eq!(
+        validate(Format::Commander, &deck),
+        vec![
+            Violation::TooManyCopies {
+                name: "Sol Ring".into(),
+                count: 2,
+                limit: 1
+            },
+            Violation::InvalidPartners("Sol Ring".into(), "Sol Ring".into()),
+            Violation::InvalidCommander("Sol Ring".into()),
+            Violation::InvalidCommander("Sol Ring".into()),
+        ]
+    );
+}
+#[test]
+fn test_partners() {
+    let partner = |name: &str, identity: &str| {
+        Arc::new(
+            card(name, "{3}", "Legendary Creature — Human", identity)
+                .with_text("Partner (You can have two commanders if both have partner.)")
+                .with_legal(),
+        )
+    };
+    let background = Arc::new(
+        card(
+            "Raised by Giants",
+            "{5}{G}",
+            "Legendary Enchantment — Background",
+            "g",
+        )
+        .with_text("Commander creatures you own have base power and toughness 10/10.")
+        .with_legal(),
+    );
+    let chooser = Arc::new(
+        card(
+            "Wilson, Refined Grizzly",
+            "{1}{G}",
+            "Legendary Creature — Bear Warrior",
+            "g",
+        )
+        .with_text("Choose a Background")
+        .with_legal(),
+    );
+    let mut deck = filler(98, "wg");
+    deck.push(entry(1, &partner("Thrasios", "ug"), Section::Commander));
+    deck.push(entry(1, &partner("Tymna", "wb"), Section::Commander));
+    assert_eq!(validate(Format::Commander, &deck), vec![]);
+    let mut deck = filler(98, "g");
+    deck.push(entry(1, &chooser, Section::Commander));
+    deck.push(entry(1, &background, Section::Commander));
+    assert_eq!(validate(Format::Commander, &deck), vec![]);
+    deck.pop();
+    deck.push(entry(1, &partner("Thrasios", "ug"), Section::Commander));
+    assert_eq!(
+        validate(Format::Commander, &deck),
+        vec![Violation::InvalidPartners(
+            "Wilson, Refined Grizzly".into(),
+            "Thrasios".into()
+        )]
+    );
+    let pair = |name: &str, other: &str| {
+        Arc::new(card(name, "{3}", "Legendary Creature — Human", "r").with_text(&format!(
+                "Partner with {other} (When this creature enters, target player may put {other} into their hand from their library, then shuffle.)"
+            )).with_legal())
+    };
+    let mut deck = filler(98, "r");
+    deck.push(entry(
+        1,
+        &pair("Pir, Imaginative Rascal", "Toothy, Imaginary Friend"),
+        Section::Commander,
+    ));
+    deck.push(entry(
+        1,
+        &pair("Toothy, Imaginary Friend", "Pir, Imaginative Rascal"),
+        Section::Commander,
+    ));
+    assert_eq!(validate(Format::Commander, &deck), vec![]);
+    deck.pop();
+    deck.push(entry(1, &partner("Thrasios", "ug"), Section::Commander));
+    assert_eq!(
+        validate(Format::Commander, &deck),
+        vec![Violation::InvalidPartners(
+            "Pir, Imaginative Rascal".into(),
+            "Thrasios".into()
+        )]
+    );
+    let mut deck = filler(98, "ug");
+    let mentions = Arc::new(
+        card("Mentions Partner", "{3}", "Legendary Creature — Human", "u")
+            .with_text("Partner creatures you control get +1/+1.")
+            .with_legal(),
+    );
+    deck.push(entry(1, &mentions, Section::Commander));
+    deck.push(entry(1, &partner("Thrasios", "ug"), Section::Commander));
+    assert_eq!(
+        validate(Format::Commander, &deck),
+        vec![Violation::InvalidPartners(
+            "Mentions Partner".into(),
+            "Thrasios".into()
+        )]
+    );
+}
+#[test]
+fn test_companion() {
+    let lurrus = Arc::new(
+        card(
+            "Lurrus of the Dream-Den",
+            "{1}{W/B}{W/B}",
+            "Legendary Creature — Cat Nightmare",
+            "wb",
+        )
+        .with_text(
+            "Companion — Each permanent card in your starting deck has mana value 2 or less.",
+        )
+        .with_legal(),
+    );
+    let mut deck = filler(60, "");
+    deck.push(entry(1, &lurrus, Section::Companion));
+    assert_eq!(validate(Format::Legacy, &deck), vec![]);
+    let titan = Arc::new(card("Grave Titan", "{4}{B}{B}", "Creature — Giant", "b").with_legal());
+    deck.push(entry(1, &titan, Section::Main));
+    assert_eq!(
+        validate(Format::Legacy, &deck),
+        vec![Violation::Companion("Lurrus of the Dream-Den".into())]
+    );
+    let bolt = Arc::new(card("Lightning Bolt", "{R}", "Instant", "r").with_legal());
+    deck.push(entry(1, &bolt, Section::Companion));
+    assert_eq!(
+        validate(Format::Legacy, &deck),
+        vec![
+            Violation::Companion("Lurrus of the Dream-Den".into()),
+            Violation::NotCompanion("Lightning Bolt".into())
+        ]
+    );
+}
@@ -0,0 +1,130 @@
+use futures::channel::oneshot;
+use rustc_hash::FxBuildHasher;
+use std::collections::{HashSet, VecDeque};
+use std::num::NonZero;
+use std::sync::{Arc, Condvar, LazyLock, Mutex, RwLock};
+use uuid::Uuid;
+pub static DECODE_POOL: LazyLock<DecodePool> = LazyLock::new(|| DecodePool::new(default_threads()));
+type Job = Box<dyn FnOnce() + Send>;
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<(Uuid, Job)>,
+    closed: bool,
+}
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+    visible: RwLock<HashSet<Uuid, FxBuildHasher>>,
+}
+pub struct DecodePool {
+    shared: Arc<Shared>,
+    threads: usize,
+}
+pub fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, NonZero::get)
+}
+impl Shared {
+    fn push(&self, uuid: Uuid, job: Job) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.jobs.push_back((uuid, job));
+        }
+        self.ready.notify_one();
+    }
+    fn next(&self, queue: &mut Queue) -> Option<Job> {
+        let index = self
+            .visible
+            .read()
+            .ok()
+            .and_then(|visible| {
+                queue
+                    .jobs
+                    .iter()
+                    .position(|(uuid, _)| visible.contains(uuid))
+            })
+            .unwrap_or(0);
+        queue.jobs.remove(index).map(|(_, job)| job)
+    }
+    #[cfg(target_family = "wasm")]
+    fn try_pop(&self) -> Option<Job> {
+        let mut queue = self.queue.lock().ok()?;
+        if queue.closed {
+            return None;
+        }
+        self.next(&mut queue)
+    }
+    #[cfg(not(target_family = "wasm"))]
+    fn pop(&self) -> Option<Job> {
+        let mut queue = self.queue.lock().ok()?;
+        loop {
+            if queue.closed {
+                return None;
+            }
+            if let Some(job) = self.next(&mut queue) {
+                return Some(job);
+            }
+            queue = self.ready.wait(queue).ok()?;
+        }
+    }
+    #[cfg(not(target_family = "wasm"))]
+    fn work(&self) {
+        while let Some(job) = self.pop() {
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+        }
+    }
+}
+impl DecodePool {
+    pub fn new(threads: usize) -> Self {
+        let shared = Arc::new(Shared::default());
+        let workers = threads.max(1);
+        #[cfg(not(target_family = "wasm"))]
+        for i in 0..workers {
+            let worker = shared.clone();
+            let _ = std::thread::Builder::new()
+                .name(format!("decode-{i}"))
+                .spawn(move || worker.work());
+        }
+        Self {
+            shared,
+            threads: workers,
+        }
+    }
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+    pub fn queued(&self) -> usize {
+        self.shared.queue.lock().map_or(0, |queue| queue.jobs.len())
+    }
+    pub fn set_visible(&self, visible: impl IntoIterator<Item = Uuid>) {
+        if let Ok(mut set) = self.shared.visible.write() {
+            set.clear();
+            set.extend(visible);
+        }
+    }
+    pub async fn run<T: Send + 'static>(
+        &self,
+        uuid: Uuid,
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> Option<T> {
+        let (sender, receiver) = oneshot::channel();
+        self.shared.push(
+            uuid,
+            Box::new(move || {
+                let _ = sender.send(job());
+            }),
+        );
+        #[cfg(target_family = "wasm")]
+        if let Some(next) = self.shared.try_pop() {
+            next();
+        }
+        receiver.await.ok()
+    }
+}
+impl Drop for DecodePool {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = self.shared.queue.lock() {
+            queue.closed = true;
+        }
+        self.shared.ready.notify_all();
+    }
+}
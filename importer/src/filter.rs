@@ -0,0 +1,500 @@
+use crate::card::{
+    CardData, CardInfo, Colors, Cost, Layout, MainTypes, Rarity, SubTypes, SuperTypes, Types,
+};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::CharIndices;
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+    Term(SearchKey, Order, SearchValue),
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    Greater,
+    Less,
+    Equal,
+    GreaterEqual,
+    LessEqual,
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchKey {
+    Name,
+    Cmc,
+    Type,
+    SuperType,
+    MainType,
+    SubType,
+    Text,
+    Color,
+    Identity,
+    Power,
+    Toughness,
+    Loyalty,
+    Mana,
+    Rarity,
+    Set,
+    CollectorNumber,
+    Layout,
+    Is,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchValue {
+    Text(Box<str>),
+    Number(u8),
+    Count(usize),
+    Types(Types),
+    SuperTypes(SuperTypes),
+    MainTypes(MainTypes),
+    SubTypes(SubTypes),
+    Colors(Colors),
+    Cost(Cost),
+    Rarity(Rarity),
+    Layout(Layout),
+    Is(Is),
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Is {
+    Dfc,
+    Token,
+    Permanent,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterError {
+    UnknownKey(Box<str>),
+    InvalidValue(SearchKey, Box<str>),
+    MissingValue(SearchKey),
+    UnclosedQuote,
+    UnclosedParen,
+    UnexpectedParen,
+    DanglingOperator(&'static str),
+}
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open,
+    Close,
+    Not,
+    Or,
+    And,
+    Word(&'a str),
+}
+impl Display for FilterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownKey(key) => write!(f, "unknown key \"{key}\""),
+            Self::InvalidValue(key, value) => write!(f, "invalid value \"{value}\" for {key:?}"),
+            Self::MissingValue(key) => write!(f, "missing value for {key:?}"),
+            Self::UnclosedQuote => write!(f, "unclosed quote"),
+            Self::UnclosedParen => write!(f, "unclosed parenthesis"),
+            Self::UnexpectedParen => write!(f, "unexpected closing parenthesis"),
+            Self::DanglingOperator(op) => write!(f, "\"{op}\" is missing an operand"),
+        }
+    }
+}
+impl PartialEq<Order> for Ordering {
+    fn eq(&self, other: &Order) -> bool {
+        match other {
+            Order::Greater => matches!(self, Ordering::Greater),
+            Order::Less => matches!(self, Ordering::Less),
+            Order::Equal => matches!(self, Ordering::Equal),
+            Order::GreaterEqual => matches!(self, Ordering::Greater | Ordering::Equal),
+            Order::LessEqual => matches!(self, Ordering::Less | Ordering::Equal),
+        }
+    }
+}
+impl Order {
+    #[must_use]
+    pub fn matches(self, ordering: Option<Ordering>) -> bool {
+        ordering.is_some_and(|o| o == self)
+    }
+}
+impl SearchKey {
+    #[must_use]
+    pub fn from_name(key: &str) -> Option<Self> {
+        Some(match key {
+            "name" | "n" => SearchKey::Name,
+            "cmc" | "cost" | "mv" => SearchKey::Cmc,
+            "type" | "t" => SearchKey::Type,
+            "super_type" | "ut" => SearchKey::SuperType,
+            "main_type" | "mt" => SearchKey::MainType,
+            "sub_type" | "st" => SearchKey::SubType,
+            "text" | "o" => SearchKey::Text,
+            "color" | "c" => SearchKey::Color,
+            "identity" | "id" | "ci" => SearchKey::Identity,
+            "power" | "p" => SearchKey::Power,
+            "loyalty" | "l" => SearchKey::Loyalty,
+            "toughness" | "h" => SearchKey::Toughness,
+            "mana" | "m" => SearchKey::Mana,
+            "rarity" | "r" => SearchKey::Rarity,
+            "set" | "s" | "e" => SearchKey::Set,
+            "number" | "cn" => SearchKey::CollectorNumber,
+            "layout" => SearchKey::Layout,
+            "is" => SearchKey::Is,
+            _ => return None,
+        })
+    }
+    #[must_use]
+    pub fn colon_order(self) -> Order {
+        match self {
+            SearchKey::Color
+            | SearchKey::Mana
+            | SearchKey::Type
+            | SearchKey::SuperType
+            | SearchKey::MainType
+            | SearchKey::SubType => Order::GreaterEqual,
+            SearchKey::Identity => Order::LessEqual,
+            _ => Order::Equal,
+        }
+    }
+    pub fn parse_value(self, value: &str) -> Result<SearchValue, FilterError> {
+        let invalid = || FilterError::InvalidValue(self, value.into());
+        if value.is_empty() {
+            return Err(FilterError::MissingValue(self));
+        }
+        Ok(match self {
+            SearchKey::Name | SearchKey::Text | SearchKey::Set | SearchKey::CollectorNumber => {
+                SearchValue::Text(value.into())
+            }
+            SearchKey::Cmc | SearchKey::Power | SearchKey::Toughness | SearchKey::Loyalty => {
+                SearchValue::Number(value.parse().map_err(|_| invalid())?)
+            }
+            SearchKey::Type
+            | SearchKey::SuperType
+            | SearchKey::MainType
+            | SearchKey::SubType
+            | SearchKey::Color
+            | SearchKey::Identity
+                if let Ok(count) = value.parse() =>
+            {
+                SearchValue::Count(count)
+            }
+            SearchKey::Type => SearchValue::Types(Types::parse_exact(value).ok_or_else(invalid)?),
+            SearchKey::SuperType => {
+                SearchValue::SuperTypes(SuperTypes::parse_exact(value).ok_or_else(invalid)?)
+            }
+            SearchKey::MainType => {
+                SearchValue::MainTypes(MainTypes::parse_exact(value).ok_or_else(invalid)?)
+            }
+            SearchKey::SubType => {
+                SearchValue::SubTypes(SubTypes::parse_exact(value).ok_or_else(invalid)?)
+            }
+            SearchKey::Color | SearchKey::Identity => {
+                SearchValue::Colors(Colors::try_from(value).map_err(|()| invalid())?)
+            }
+            SearchKey::Mana => SearchValue::Cost(parse_mana(value).ok_or_else(invalid)?),
+            SearchKey::Rarity => SearchValue::Rarity(Rarity::from_name(value).ok_or_else(invalid)?),
+            SearchKey::Layout => SearchValue::Layout(Layout::from_name(value).ok_or_else(invalid)?),
+            SearchKey::Is => SearchValue::Is(match value {
+                "dfc" | "mdfc" | "transform" => Is::Dfc,
+                "token" => Is::Token,
+                "permanent" => Is::Permanent,
+                _ => return Err(invalid()),
+            }),
+        })
+    }
+}
+fn parse_mana(value: &str) -> Option<Cost> {
+    let upper = value.to_ascii_uppercase();
+    let braced = if upper.starts_with('{') {
+        upper
+    } else {
+        let mut braced = String::with_capacity(upper.len() * 3);
+        let mut digits = false;
+        for c in upper.chars() {
+            if c.is_ascii_digit() {
+                if !digits {
+                    braced.push('{');
+                    digits = true;
+                }
+                braced.push(c);
+            } else {
+                if digits {
+                    braced.push('}');
+                    digits = false;
+                }
+                braced.push('{');
+                braced.push(c);
+                braced.push('}');
+            }
+        }
+        if digits {
+            braced.push('}');
+        }
+        braced
+    };
+    Cost::parse(&braced).filter(|cost| cost.has_cost)
+}
+impl TryFrom<&str> for Filter {
+    type Error = FilterError;
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        let lower = text.to_ascii_lowercase();
+        let tokens = tokenize(lower.trim())?;
+        let mut iter = tokens.into_iter().peekable();
+        let filter = parse_or(&mut iter)?;
+        if iter.next().is_some() {
+            return Err(FilterError::UnexpectedParen);
+        }
+        Ok(filter)
+    }
+}
+impl Filter {
+    #[must_use]
+    pub fn matches(&self, data: &CardData, face: &CardInfo) -> bool {
+        match self {
+            Filter::And(v) => v.iter().all(|f| f.matches(data, face)),
+            Filter::Or(v) => v.iter().any(|f| f.matches(data, face)),
+            Filter::Not(f) => !f.matches(data, face),
+            Filter::Term(key, order, value) => term_matches(data, face, *key, *order, value),
+        }
+    }
+}
+fn term_matches(
+    data: &CardData,
+    face: &CardInfo,
+    key: SearchKey,
+    order: Order,
+    value: &SearchValue,
+) -> bool {
+    match (key, value) {
+        (SearchKey::Name, SearchValue::Text(v)) => face.name.to_ascii_lowercase().contains(&**v),
+        (SearchKey::Text, SearchValue::Text(v)) => {
+            face.oracle_text.to_ascii_lowercase().contains(&**v)
+        }
+        (SearchKey::Set, SearchValue::Text(v)) => data.set().eq_ignore_ascii_case(v),
+        (SearchKey::CollectorNumber, SearchValue::Text(v)) => {
+            let cn = data.collector_number();
+            if matches!(order, Order::Equal) {
+                cn.eq_ignore_ascii_case(v)
+            } else if let Some(a) = leading_number(cn)
+                && let Some(b) = leading_number(v)
+            {
+                order.matches(a.partial_cmp(&b))
+            } else {
+                false
+            }
+        }
+        (SearchKey::Cmc, &SearchValue::Number(v)) => {
+            order.matches(face.mana_cost.mana_value().partial_cmp(&f32::from(v)))
+        }
+        (SearchKey::Power, &SearchValue::Number(v)) => {
+            order.matches(face.power.and_then(|p| p.partial_cmp(&v)))
+        }
+        (SearchKey::Toughness, &SearchValue::Number(v)) => {
+            order.matches(face.toughness.and_then(|p| p.partial_cmp(&v)))
+        }
+        (SearchKey::Loyalty, &SearchValue::Number(v)) => {
+            order.matches(face.loyalty.and_then(|p| p.partial_cmp(&v)))
+        }
+        (SearchKey::Type, &SearchValue::Count(v)) => {
+            order.matches(face.type_line.len().partial_cmp(&v))
+        }
+        (SearchKey::SuperType, &SearchValue::Count(v)) => {
+            order.matches(face.type_line.super_type.types.len().partial_cmp(&v))
+        }
+        (SearchKey::MainType, &SearchValue::Count(v)) => {
+            order.matches(face.type_line.main_type.types.len().partial_cmp(&v))
+        }
+        (SearchKey::SubType, &SearchValue::Count(v)) => {
+            order.matches(face.type_line.sub_type.types.len().partial_cmp(&v))
+        }
+        (SearchKey::Color, &SearchValue::Count(v)) => {
+            order.matches(face.colors.len().partial_cmp(&v))
+        }
+        (SearchKey::Identity, &SearchValue::Count(v)) => {
+            order.matches(face.color_identity.len().partial_cmp(&v))
+        }
+        (SearchKey::Type, SearchValue::Types(v)) => order.matches(face.type_line.partial_cmp(v)),
+        (SearchKey::SuperType, SearchValue::SuperTypes(v)) => {
+            order.matches(face.type_line.super_type.partial_cmp(v))
+        }
+        (SearchKey::MainType, SearchValue::MainTypes(v)) => {
+            order.matches(face.type_line.main_type.partial_cmp(v))
+        }
+        (SearchKey::SubType, SearchValue::SubTypes(v)) => {
+            order.matches(face.type_line.sub_type.partial_cmp(v))
+        }
+        (SearchKey::Color, SearchValue::Colors(v)) => order.matches(face.colors.partial_cmp(v)),
+        (SearchKey::Identity, SearchValue::Colors(v)) => {
+            order.matches(face.color_identity.partial_cmp(v))
+        }
+        (SearchKey::Mana, SearchValue::Cost(v)) => order.matches(face.mana_cost.partial_cmp(v)),
+        (SearchKey::Rarity, SearchValue::Rarity(v)) => order.matches(data.rarity.partial_cmp(v)),
+        (SearchKey::Layout, SearchValue::Layout(v)) => data.layout == *v,
+        (SearchKey::Is, SearchValue::Is(Is::Dfc)) => {
+            data.back.as_ref().is_some_and(|b| b.has_unique_face)
+        }
+        (SearchKey::Is, SearchValue::Is(Is::Token)) => data.layout.is_token(),
+        (SearchKey::Is, SearchValue::Is(Is::Permanent)) => face.type_line.is_permanent(),
+        _ => false,
+    }
+}
+fn leading_number(s: &str) -> Option<u32> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s[..end].parse().ok()
+}
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '-' | '!' => {
+                chars.next();
+                tokens.push(Token::Not);
+            }
+            _ => {
+                let end = word_end(&mut chars)?.unwrap_or(text.len());
+                tokens.push(match &text[i..end] {
+                    "or" => Token::Or,
+                    "and" => Token::And,
+                    word => Token::Word(word),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+fn word_end(chars: &mut Peekable<CharIndices<'_>>) -> Result<Option<usize>, FilterError> {
+    let mut quoted = false;
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' | ')' if !quoted => return Ok(Some(i)),
+            c if c.is_whitespace() && !quoted => return Ok(Some(i)),
+            _ => {}
+        }
+        chars.next();
+    }
+    if quoted {
+        Err(FilterError::UnclosedQuote)
+    } else {
+        Ok(None)
+    }
+}
+fn parse_or<'a>(
+    tokens: &mut Peekable<impl Iterator<Item = Token<'a>>>,
+) -> Result<Filter, FilterError> {
+    let mut or = vec![parse_and(tokens)?];
+    while tokens.next_if_eq(&Token::Or).is_some() {
+        if matches!(tokens.peek(), None | Some(Token::Close | Token::Or)) {
+            return Err(FilterError::DanglingOperator("or"));
+        }
+        or.push(parse_and(tokens)?);
+    }
+    Ok(if or.len() == 1 {
+        or.pop().unwrap()
+    } else {
+        Filter::Or(or)
+    })
+}
+fn parse_and<'a>(
+    tokens: &mut Peekable<impl Iterator<Item = Token<'a>>>,
+) -> Result<Filter, FilterError> {
+    let mut and = Vec::new();
+    loop {
+        match tokens.peek() {
+            Some(Token::Or) if and.is_empty() => {
+                return Err(FilterError::DanglingOperator("or"));
+            }
+            None | Some(Token::Close | Token::Or) => break,
+            Some(Token::And) => {
+                tokens.next();
+                if and.is_empty()
+                    || matches!(
+                        tokens.peek(),
+                        None | Some(Token::Close | Token::Or | Token::And)
+                    )
+                {
+                    return Err(FilterError::DanglingOperator("and"));
+                }
+            }
+            Some(_) => and.push(parse_unary(tokens)?),
+        }
+    }
+    Ok(if and.len() == 1 {
+        and.pop().unwrap()
+    } else {
+        Filter::And(and)
+    })
+}
+fn parse_unary<'a>(
+    tokens: &mut Peekable<impl Iterator<Item = Token<'a>>>,
+) -> Result<Filter, FilterError> {
+    match tokens.next() {
+        Some(Token::Not) => {
+            if matches!(
+                tokens.peek(),
+                None | Some(Token::Close | Token::Or | Token::And)
+            ) {
+                return Err(FilterError::DanglingOperator("-"));
+            }
+            Ok(Filter::Not(Box::new(parse_unary(tokens)?)))
+        }
+        Some(Token::Open) => {
+            let inner = parse_or(tokens)?;
+            if tokens.next_if_eq(&Token::Close).is_none() {
+                return Err(FilterError::UnclosedParen);
+            }
+            Ok(inner)
+        }
+        Some(Token::Word(word)) => parse_term(word),
+        Some(Token::Close) => Err(FilterError::UnexpectedParen),
+        Some(Token::Or | Token::And) | None => unreachable!(),
+    }
+}
+fn parse_term(word: &str) -> Result<Filter, FilterError> {
+    let op = word
+        .char_indices()
+        .scan(false, |quoted, (i, c)| {
+            if c == '"' {
+                *quoted = !*quoted;
+            }
+            Some((i, c, *quoted))
+        })
+        .find(|&(_, c, quoted)| !quoted && matches!(c, ':' | '=' | '<' | '>'));
+    let Some((i, c, _)) = op else {
+        return Ok(Filter::Term(
+            SearchKey::Name,
+            Order::Equal,
+            SearchValue::Text(unquote(word).into()),
+        ));
+    };
+    let key_str = &word[..i];
+    let Some(key) = SearchKey::from_name(key_str) else {
+        return Err(FilterError::UnknownKey(key_str.into()));
+    };
+    let rest = &word[i + 1..];
+    let (order, value) = match c {
+        ':' => (key.colon_order(), rest),
+        '=' => (Order::Equal, rest),
+        '<' => match rest.strip_prefix('=') {
+            Some(value) => (Order::LessEqual, value),
+            None => (Order::Less, rest),
+        },
+        '>' => match rest.strip_prefix('=') {
+            Some(value) => (Order::GreaterEqual, value),
+            None => (Order::Greater, rest),
+        },
+        _ => false,
+    };
+    Ok(Filter::Term(key, order, key.parse_value(unquote(value))?))
+}
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
@@ -0,0 +1,117 @@
+use crate::card::{CardData, Layout};
+use crate::filter::{Filter, FilterError, SearchKey};
+use crate::test_cards::card;
+fn matches(data: &CardData, text: &str) -> bool {
+    data.filter(&Filter::try_from(text).unwrap())
+}
+#[test]
+fn test_terms() {
+    let bolt = card("Lightning Bolt", "{R}", "Instant", "r")
+        .with_set_cn("m10/146")
+        .with_text("Lightning Bolt deals 3 damage to any target.");
+    assert!(matches(&bolt, "bolt"));
+    assert!(matches(&bolt, "\"lightning bolt\""));
+    assert!(!matches(&bolt, "\"bolt lightning\""));
+    assert!(matches(&bolt, "o:\"3 damage\""));
+    assert!(matches(&bolt, "cmc=1 t:instant"));
+    assert!(matches(&bolt, "cmc<=1"));
+    assert!(!matches(&bolt, "cmc>1"));
+    assert!(matches(&bolt, "r:common s:m10 cn:146 cn>=100"));
+    assert!(matches(&bolt, "r<rare"));
+    assert!(matches(&bolt, "m:{R}"));
+    assert!(!matches(&bolt, "m:{R}{R}"));
+    assert!(matches(
+        &bolt,
+        "layout:normal -is:dfc -is:token -is:permanent"
+    ));
+}
+#[test]
+fn test_boolean() {
+    let bolt = card("Lightning Bolt", "{R}", "Instant", "r");
+    assert!(matches(&bolt, "goblin or bolt"));
+    assert!(!matches(&bolt, "goblin or (bolt t:creature)"));
+    assert!(matches(&bolt, "-(goblin or t:creature)"));
+    assert!(matches(&bolt, "!goblin and bolt"));
+    assert!(matches(&bolt, ""));
+}
+#[test]
+fn test_identity() {
+    let charm = card("Bant Charm", "{G}{W}{U}", "Instant", "gwu");
+    assert!(matches(&charm, "id<=bant"));
+    assert!(matches(&charm, "id:bant"));
+    assert!(!matches(&charm, "id<bant"));
+    assert!(!matches(&charm, "id<=azorius"));
+    assert!(matches(&charm, "id>=azorius"));
+    assert!(matches(&charm, "c:wu c=3"));
+    assert!(matches(&charm, "m:gwu m={G}{W}{U}"));
+    let goblin = card("Goblin Guide", "{R}", "Creature — Goblin Scout", "r").with_stats(2, 2);
+    assert!(matches(&goblin, "t:goblin t:\"creature goblin\" p>=2"));
+    assert!(!matches(&goblin, "t=creature"));
+    assert!(!matches(&goblin, "id<=bant"));
+}
+#[test]
+fn test_errors() {
+    assert_eq!(
+        Filter::try_from("foo:bar"),
+        Err(FilterError::UnknownKey("foo".into()))
+    );
+    assert_eq!(
+        Filter::try_from("cmc>x"),
+        Err(FilterError::InvalidValue(SearchKey::Cmc, "x".into()))
+    );
+    assert_eq!(
+        Filter::try_from("t:"),
+        Err(FilterError::MissingValue(SearchKey::Type))
+    );
+    assert_eq!(
+        Filter::try_from("t:notatype"),
+        Err(FilterError::InvalidValue(
+            SearchKey::Type,
+            "notatype".into()
+        ))
+    );
+    assert_eq!(
+        Filter::try_from("o:\"draw"),
+        Err(FilterError::UnclosedQuote)
+    );
+    assert_eq!(Filter::try_from("(a or b"), Err(FilterError::UnclosedParen));
+    assert_eq!(Filter::try_from("a)"), Err(FilterError::UnexpectedParen));
+    assert_eq!(
+        Filter::try_from("a or"),
+        Err(FilterError::DanglingOperator("or"))
+    );
+    assert_eq!(
+        Filter::try_from("a and and b"),
+        Err(FilterError::DanglingOperator("and"))
+    );
+    assert_eq!(
+        Filter::try_from("or b"),
+        Err(FilterError::DanglingOperator("or"))
+    );
+    assert_eq!(
+        Filter::try_from("a or or b"),
+        Err(FilterError::DanglingOperator("or"))
+    );
+    assert_eq!(
+        Filter::try_from("a -"),
+        Err(FilterError::DanglingOperator("-"))
+    );
+    assert!(Filter::try_from("layout:sideways").is_err());
+    assert!(Filter::try_from("m:{Q}").is_err());
+    assert!(Filter::try_from("is:foil").is_err());
+}
+#[test]
+fn test_layouts() {
+    let mut token = card("Goblin", "", "Token Creature — Goblin", "r");
+    token.layout = Layout::Token;
+    assert!(matches(&token, "is:token layout:token"));
+    assert!(matches(&token, "is:permanent"));
+    token.layout = Layout::DoubleFacedToken;
+    assert!(matches(
+        &token,
+        "is:token layout:double_faced_token -layout:token"
+    ));
+    let mut battle = card("Invasion of Zendikar", "{3}{G}", "Battle — Siege", "g");
+    battle.layout = Layout::Battle;
+    assert!(matches(&battle, "layout:battle -layout:transform"));
+}
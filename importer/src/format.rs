@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+use std::fmt;
+pub const MAGIC: [u8; 4] = *b"rmtg";
+pub const FORMAT_VERSION: u32 = 4;
+const HEADER_LEN: usize = 16;
+type Migration = fn(&[u8]) -> Option<Vec<u8>>;
+const MIGRATIONS: &[Migration] = &[refetch, refetch, refetch];
+const _: () = assert!(MIGRATIONS.len() + 1 == FORMAT_VERSION as usize);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    Truncated,
+    Magic,
+    Version(u32),
+    Checksum,
+}
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "truncated cache entry"),
+            Self::Magic => write!(f, "unversioned cache entry"),
+            Self::Version(version) => write!(
+                f,
+                "cache entry version {version}, expected {FORMAT_VERSION}"
+            ),
+            Self::Checksum => write!(f, "cache entry checksum mismatch"),
+        }
+    }
+}
+pub fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+pub fn seal(payload: &[u8]) -> Vec<u8> {
+    seal_version(payload, FORMAT_VERSION)
+}
+pub fn seal_version(payload: &[u8], version: u32) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&version.to_le_bytes());
+    bytes.extend_from_slice(&checksum(payload).to_le_bytes());
+    bytes.extend_from_slice(payload);
+    bytes
+}
+pub fn unseal(bytes: &[u8]) -> Result<Cow<'_, [u8]>, FormatError> {
+    let (header, payload) = bytes
+        .split_first_chunk::<HEADER_LEN>()
+        .ok_or(FormatError::Truncated)?;
+    if header[..4] != MAGIC {
+        return Err(FormatError::Magic);
+    }
+    let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    let sum = u64::from_le_bytes(header[8..].try_into().unwrap_or_default());
+    if sum != checksum(payload) {
+        return Err(FormatError::Checksum);
+    }
+    migrate(version, payload)
+}
+// v2 stores mana costs per symbol, v3 every layout and v4 legalities; older entries are refetched
+fn refetch(_: &[u8]) -> Option<Vec<u8>> {
+    None
+}
+fn migrate(version: u32, payload: &[u8]) -> Result<Cow<'_, [u8]>, FormatError> {
+    if version == FORMAT_VERSION {
+        return Ok(Cow::Borrowed(payload));
+    }
+    let migrations = version
+        .checked_sub(1)
+        .and_then(|i| MIGRATIONS.get(i as usize..))
+        .filter(|_| version < FORMAT_VERSION)
+        .ok_or(FormatError::Version(version))?;
+    let mut data = payload.to_vec();
+    for migration in migrations {
+        data = migration(&data).ok_or(FormatError::Version(version))?;
+    }
+    Ok(Cow::Owned(data))
+}
@@ -0,0 +1,72 @@
+use crate::card::{Format, MainType, SubType, SuperType};
+use crate::coder::NameCoder;
+use crate::format::{FORMAT_VERSION, FormatError, checksum, seal, seal_version, unseal};
+use enumset::EnumSet;
+#[test]
+fn test_checksum() {
+    assert_eq!(checksum(b""), 0xcbf29ce484222325);
+    assert_eq!(checksum(b"a"), 0xaf63dc4c8601ec8c);
+    assert_ne!(checksum(b"ab"), checksum(b"ba"));
+}
+#[test]
+fn test_seal() {
+    let payload = b"card data".as_slice();
+    let sealed = seal(payload);
+    assert_eq!(unseal(&sealed).as_deref(), Ok(payload));
+    assert_eq!(unseal(&seal(&[])).as_deref(), Ok([].as_slice()));
+    let mut corrupt = sealed.clone();
+    *corrupt.last_mut().unwrap() ^= 1;
+    assert_eq!(unseal(&corrupt), Err(FormatError::Checksum));
+    assert_eq!(unseal(&sealed[..8]), Err(FormatError::Truncated));
+    assert_eq!(unseal(payload), Err(FormatError::Truncated));
+    assert_eq!(unseal(&[0; 32]), Err(FormatError::Magic));
+    let newer = seal_version(payload, FORMAT_VERSION + 1);
+    assert_eq!(
+        unseal(&newer),
+        Err(FormatError::Version(FORMAT_VERSION + 1))
+    );
+    assert_eq!(
+        unseal(&seal_version(payload, 0)),
+        Err(FormatError::Version(0))
+    );
+    assert_eq!(
+        unseal(&seal_version(payload, 1)),
+        Err(FormatError::Version(1))
+    );
+    assert_eq!(
+        unseal(&seal_version(payload, 2)),
+        Err(FormatError::Version(2))
+    );
+    assert_eq!(
+        unseal(&seal_version(payload, 3)),
+        Err(FormatError::Version(3))
+    );
+}
+#[test]
+fn test_name_coder() {
+    let sub_types = SubType::Elf | SubType::Druid;
+    let coder = NameCoder::from(&sub_types);
+    let mut names = coder.names.to_vec();
+    names.sort_unstable();
+    assert_eq!(names, vec![Box::from("Druid"), Box::from("Elf")]);
+    assert_eq!(EnumSet::<SubType>::from(coder), sub_types);
+    let main_types = MainType::Artifact | MainType::Creature;
+    assert_eq!(
+        EnumSet::<MainType>::from(NameCoder::from(&main_types)),
+        main_types
+    );
+    let super_types = EnumSet::only(SuperType::Legendary);
+    assert_eq!(
+        EnumSet::<SuperType>::from(NameCoder::from(&super_types)),
+        super_types
+    );
+    let formats = Format::Commander | Format::StandardBrawl;
+    assert_eq!(EnumSet::<Format>::from(NameCoder::from(&formats)), formats);
+    let unknown = NameCoder {
+        names: vec![Box::from("Elf"), Box::from("Not A Type")].into(),
+    };
+    assert_eq!(
+        EnumSet::<SubType>::from(unknown),
+        EnumSet::only(SubType::Elf)
+    );
+}
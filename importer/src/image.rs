@@ -1,10 +1,12 @@
 use crate::CARD_CORNER_RADIUS;
 use crate::circle::{Circumference, Octant};
+use crate::format::{seal, unseal};
 use bevy::asset::RenderAssetUsages;
 use bevy::image::Image;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 use fast_image_resize::{ResizeAlg, ResizeOptions, Resizer};
-use image::{ImageBuffer, ImageReader, Rgba, RgbaImage};
+use image::codecs::png::PngEncoder;
+use image::{ExtendedColorType, ImageBuffer, ImageEncoder as _, ImageReader, Rgba, RgbaImage};
 use std::io::Cursor;
 #[must_use]
 pub fn parse_bytes(bytes: &[u8]) -> Option<Image> {
@@ -13,6 +15,19 @@ pub fn parse_bytes(bytes: &[u8]) -> Option<Image> {
     let (data, mips) = generate_mips_texture(image);
     Some(make_img(data, width, height, mips))
 }
+#[must_use]
+pub fn encode_png(image: &Image) -> Option<Vec<u8>> {
+    let (width, height) = (image.width(), image.height());
+    let data = image
+        .data
+        .as_ref()
+        .filter(|data| data.len() == width as usize * height as usize * 4)?;
+    let mut bytes = Vec::new();
+    PngEncoder::new(&mut bytes)
+        .write_image(data, width, height, ExtendedColorType::Rgba8)
+        .ok()?;
+    Some(bytes)
+}
 fn parse_no_mips(bytes: &[u8]) -> Option<RgbaImage> {
     let image = ImageReader::new(Cursor::new(bytes))
         .with_guessed_format()
@@ -107,3 +122,180 @@ fn generate_mips(mut dyn_image: RgbaImage, mip_count: u32) -> Vec<u8> {
 fn calculate_mip_count(width: u32, height: u32) -> u32 {
     ((width.min(height) as f32).log2().floor() as u32 + 1).min(12)
 }
+pub const BLOCK_BYTES: usize = 16;
+pub const WEIGHTS: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+#[must_use]
+pub fn compress(image: &Image) -> Option<Image> {
+    let data = image.data.as_ref()?;
+    let Extent3d { width, height, .. } = image.texture_descriptor.size;
+    let (crop_width, crop_height) = (width / 4 * 4, height / 4 * 4);
+    if image.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb
+        || crop_width == 0
+        || crop_height == 0
+    {
+        return None;
+    }
+    let mips = image.texture_descriptor.mip_level_count;
+    let mut compressed = Vec::with_capacity(data.len() / 4);
+    let mut offset = 0;
+    for level in 0..mips {
+        let (w, h) = ((width >> level).max(1), (height >> level).max(1));
+        let len = (w * h * 4) as usize;
+        compressed.extend(compress_level(
+            data.get(offset..offset + len)?,
+            w,
+            (crop_width >> level).max(1),
+            (crop_height >> level).max(1),
+        ));
+        offset += len;
+    }
+    Some(make_compressed(compressed, crop_width, crop_height, mips))
+}
+fn make_compressed(data: Vec<u8>, width: u32, height: u32, mips: u32) -> Image {
+    let mut image = make_img(data, width, height, mips);
+    image.texture_descriptor.format = TextureFormat::Bc7RgbaUnormSrgb;
+    image
+}
+fn compress_level(rgba: &[u8], stride: u32, width: u32, height: u32) -> Vec<u8> {
+    let mut blocks =
+        Vec::with_capacity((width.div_ceil(4) * height.div_ceil(4)) as usize * BLOCK_BYTES);
+    for by in (0..height).step_by(4) {
+        for bx in (0..width).step_by(4) {
+            let mut block = [[0; 4]; 16];
+            for (i, pixel) in block.iter_mut().enumerate() {
+                let x = (bx + i as u32 % 4).min(width - 1);
+                let y = (by + i as u32 / 4).min(height - 1);
+                let start = ((y * stride + x) * 4) as usize;
+                pixel.copy_from_slice(&rgba[start..start + 4]);
+            }
+            blocks.extend(encode_block(&block));
+        }
+    }
+    blocks
+}
+#[must_use]
+pub fn palette(color0: [u8; 4], color1: [u8; 4]) -> [[u8; 4]; 16] {
+    WEIGHTS.map(|w| {
+        std::array::from_fn(|c| {
+            (((64 - w) * u32::from(color0[c]) + w * u32::from(color1[c]) + 32) >> 6) as u8
+        })
+    })
+}
+fn distance(a: [u8; 4], b: [u8; 4]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(&x, y)| u32::from(x.abs_diff(y)).pow(2))
+        .sum()
+}
+fn endpoints(block: &[[u8; 4]; 16]) -> ([f32; 4], [f32; 4]) {
+    let pixels = block.map(|p| p.map(f32::from));
+    let mut mean = [0.0; 4];
+    let mut min = [255.0f32; 4];
+    let mut max = [0.0f32; 4];
+    for pixel in &pixels {
+        for c in 0..4 {
+            mean[c] += pixel[c] / 16.0;
+            min[c] = min[c].min(pixel[c]);
+            max[c] = max[c].max(pixel[c]);
+        }
+    }
+    let mut covariance = [[0.0f32; 4]; 4];
+    for pixel in &pixels {
+        for i in 0..4 {
+            for j in 0..4 {
+                covariance[i][j] += (pixel[i] - mean[i]) * (pixel[j] - mean[j]);
+            }
+        }
+    }
+    let mut axis: [f32; 4] = std::array::from_fn(|c| max[c] - min[c]);
+    for _ in 0..8 {
+        let next: [f32; 4] =
+            std::array::from_fn(|i| (0..4).map(|j| covariance[i][j] * axis[j]).sum());
+        let len = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if len < f32::EPSILON {
+            return (mean, mean);
+        }
+        axis = next.map(|v| v / len);
+    }
+    let project = |pixel: &[f32; 4]| (0..4).map(|c| (pixel[c] - mean[c]) * axis[c]).sum::<f32>();
+    let (lo, hi) = pixels
+        .iter()
+        .map(project)
+        .fold((0.0f32, 0.0f32), |(lo, hi), t| (lo.min(t), hi.max(t)));
+    let point = |t: f32| std::array::from_fn(|c| (mean[c] + axis[c] * t).clamp(0.0, 255.0));
+    (point(lo), point(hi))
+}
+fn quantize(color: [f32; 4]) -> ([u8; 4], u8) {
+    let quantized = |p: u8| {
+        let bits = color.map(|v| ((v - f32::from(p)) / 2.0).round().clamp(0.0, 127.0) as u8);
+        let error: f32 = (0..4)
+            .map(|c| {
+                let weight = if c == 3 { 4.0 } else { 1.0 };
+                weight * (f32::from((bits[c] << 1) | p) - color[c]).powi(2)
+            })
+            .sum();
+        (bits, p, error)
+    };
+    let (even, odd) = (quantized(0), quantized(1));
+    let (bits, p, _) = if odd.2 < even.2 { odd } else { even };
+    (bits, p)
+}
+#[must_use]
+pub fn encode_block(block: &[[u8; 4]; 16]) -> [u8; BLOCK_BYTES] {
+    let (lo, hi) = endpoints(block);
+    let (mut bits0, mut p0) = quantize(lo);
+    let (mut bits1, mut p1) = quantize(hi);
+    let expand = |bits: [u8; 4], p: u8| bits.map(|c| (c << 1) | p);
+    let colors = palette(expand(bits0, p0), expand(bits1, p1));
+    let mut indices = block.map(|pixel| {
+        (0..16)
+            .min_by_key(|&i| distance(pixel, colors[i]))
+            .unwrap_or_default() as u128
+    });
+    if indices[0] >= 8 {
+        (bits0, bits1) = (bits1, bits0);
+        (p0, p1) = (p1, p0);
+        indices = indices.map(|i| 15 - i);
+    }
+    let mut packed = 1u128 << 6;
+    let mut offset = 7;
+    let mut push = |value: u128, len: u32| {
+        packed |= value << offset;
+        offset += len;
+    };
+    for c in 0..4 {
+        push(u128::from(bits0[c]), 7);
+        push(u128::from(bits1[c]), 7);
+    }
+    push(u128::from(p0), 1);
+    push(u128::from(p1), 1);
+    for (i, index) in indices.into_iter().enumerate() {
+        push(index, if i == 0 { 3 } else { 4 });
+    }
+    packed.to_le_bytes()
+}
+#[must_use]
+pub fn to_compressed_bytes(image: &Image) -> Option<Vec<u8>> {
+    let data = image.data.as_ref()?;
+    let Extent3d { width, height, .. } = image.texture_descriptor.size;
+    let mut bytes = Vec::with_capacity(12 + data.len());
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.extend_from_slice(&image.texture_descriptor.mip_level_count.to_le_bytes());
+    bytes.extend_from_slice(data);
+    Some(seal(&bytes))
+}
+#[must_use]
+pub fn from_compressed_bytes(bytes: &[u8]) -> Option<Image> {
+    let payload = unseal(bytes).ok()?;
+    let (header, data) = payload.split_first_chunk::<12>()?;
+    let [width, height, mips] = [0, 4, 8]
+        .map(|i| u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]));
+    let expected: u32 = (0..mips)
+        .map(|level| {
+            let (w, h) = ((width >> level).max(1), (height >> level).max(1));
+            w.div_ceil(4) * h.div_ceil(4) * BLOCK_BYTES as u32
+        })
+        .sum();
+    (data.len() == expected as usize).then(|| make_compressed(data.to_vec(), width, height, mips))
+}
@@ -1,9 +1,30 @@
 extern crate test;
+use crate::decode::{DecodePool, default_threads};
 use crate::image::parse_bytes;
+use futures::executor::block_on;
+use futures::future::join_all;
 use std::hint::black_box;
 use test::Bencher;
+use uuid::Uuid;
+const DECK_SIZE: usize = 60;
+const BACK: &[u8] = include_bytes!("../../assets/back.png");
 #[bench]
 fn bench_back(bencher: &mut Bencher) {
-    let bytes = include_bytes!("../../assets/back.png");
-    bencher.iter(|| black_box(parse_bytes(bytes).unwrap()))
+    bencher.iter(|| black_box(parse_bytes(BACK).unwrap()))
+}
+fn load_deck(pool: &DecodePool) {
+    let images = (0..DECK_SIZE).map(|i| pool.run(Uuid::from_u128(i as u128), || parse_bytes(BACK)));
+    for image in block_on(join_all(images)) {
+        black_box(image.flatten().unwrap());
+    }
+}
+#[bench]
+fn bench_deck_serial(bencher: &mut Bencher) {
+    let pool = DecodePool::new(1);
+    bencher.iter(|| load_deck(&pool))
+}
+#[bench]
+fn bench_deck_parallel(bencher: &mut Bencher) {
+    let pool = DecodePool::new(default_threads());
+    bencher.iter(|| load_deck(&pool))
 }
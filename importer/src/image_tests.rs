@@ -0,0 +1,104 @@
+use crate::image::{
+    BLOCK_BYTES, compress, encode_block, encode_png, from_compressed_bytes, palette, parse_bytes,
+    to_compressed_bytes,
+};
+use bevy::asset::RenderAssetUsages;
+use bevy::image::Image;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+fn decode_block(bytes: [u8; BLOCK_BYTES]) -> [[u8; 4]; 16] {
+    let bits = u128::from_le_bytes(bytes);
+    assert_eq!(bits & 0x7f, 1 << 6);
+    let field = |offset: u32, len: u32| ((bits >> offset) & ((1 << len) - 1)) as u8;
+    let p = [field(63, 1), field(64, 1)];
+    let endpoint = |e: u32| {
+        std::array::from_fn(|c| (field(7 + 14 * c as u32 + 7 * e, 7) << 1) | p[e as usize])
+    };
+    let colors = palette(endpoint(0), endpoint(1));
+    std::array::from_fn(|i| {
+        let index = if i == 0 {
+            field(65, 3)
+        } else {
+            field(64 + 4 * i as u32, 4)
+        };
+        colors[index as usize]
+    })
+}
+#[test]
+fn test_encode_block() {
+    let solid = [[200, 100, 50, 255]; 16];
+    for pixel in decode_block(encode_block(&solid)) {
+        assert!(
+            pixel
+                .iter()
+                .zip([200, 100, 50, 255])
+                .all(|(&a, b)| a.abs_diff(b) <= 4)
+        );
+    }
+    let mut gradient = [[0; 4]; 16];
+    for (i, pixel) in gradient.iter_mut().enumerate() {
+        let v = (i * 16) as u8;
+        *pixel = [v, v, v, 255];
+    }
+    let decoded = decode_block(encode_block(&gradient));
+    assert!(
+        decoded
+            .iter()
+            .zip(gradient)
+            .all(|(a, b)| a[0].abs_diff(b[0]) <= 4)
+    );
+    assert!(decoded.iter().all(|p| p[3] == 255));
+    let mut corner = [[30, 60, 90, 255]; 16];
+    corner[0] = [0; 4];
+    corner[1] = [0; 4];
+    let decoded = decode_block(encode_block(&corner));
+    assert_eq!(decoded[0], [0; 4]);
+    assert_eq!(decoded[1], [0; 4]);
+    assert!(decoded[2..].iter().all(|p| p[3] == 255));
+}
+#[test]
+fn test_compress() {
+    let image = parse_bytes(include_bytes!("../../assets/back.png")).unwrap();
+    let compressed = compress(&image).unwrap();
+    assert_eq!(
+        compressed.texture_descriptor.format,
+        TextureFormat::Bc7RgbaUnormSrgb
+    );
+    let size = compressed.texture_descriptor.size;
+    assert_eq!((size.width, size.height), (672, 936));
+    assert_eq!(
+        compressed.texture_descriptor.mip_level_count,
+        image.texture_descriptor.mip_level_count
+    );
+    assert!(compressed.data.as_ref().unwrap().len() * 3 < image.data.as_ref().unwrap().len());
+    let bytes = to_compressed_bytes(&compressed).unwrap();
+    let loaded = from_compressed_bytes(&bytes).unwrap();
+    assert_eq!(loaded.data, compressed.data);
+    assert_eq!(loaded.texture_descriptor, compressed.texture_descriptor);
+    assert!(from_compressed_bytes(&bytes[..bytes.len() - 1]).is_none());
+}
+#[test]
+fn test_encode_png() {
+    let size = Extent3d {
+        width: 40,
+        height: 56,
+        depth_or_array_layers: 1,
+    };
+    let image = Image::new(
+        size,
+        TextureDimension::D2,
+        vec![200; 40 * 56 * 4],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    let png = encode_png(&image).unwrap();
+    let parsed = parse_bytes(&png).unwrap();
+    assert_eq!(parsed.texture_descriptor.size, size);
+    assert!(parsed.texture_descriptor.mip_level_count > 1);
+    let data = parsed.data.unwrap();
+    assert_eq!(data[..4], [0; 4]);
+    let center = (28 * 40 + 20) * 4;
+    assert_eq!(data[center..center + 4], [200, 200, 200, 255]);
+    let mut empty = image;
+    empty.data = None;
+    assert!(encode_png(&empty).is_none());
+}
@@ -6,19 +6,57 @@
 #![cfg_attr(test, feature(test))]
 extern crate core;
 pub mod card;
+#[cfg(test)]
+mod card_tests;
 pub mod image;
+#[cfg(test)]
+mod image_tests;
 pub use bitcode;
 pub use reqwest;
 pub use uuid;
 pub mod card_cache;
+#[cfg(test)]
+mod card_cache_tests;
 pub mod circle;
 pub mod coder;
+pub mod config;
+pub mod custom;
+#[cfg(test)]
+mod custom_tests;
+pub mod deck;
+#[cfg(test)]
+mod deck_tests;
+pub mod decode;
+pub mod filter;
+#[cfg(test)]
+mod filter_tests;
+pub mod format;
+#[cfg(test)]
+mod format_tests;
 #[cfg(test)]
 mod image_bench;
+#[cfg(test)]
+mod mock_scryfall;
+pub mod names;
+#[cfg(test)]
+mod names_tests;
 pub mod oracle_card;
+#[cfg(test)]
+mod oracle_card_tests;
 pub mod scryfall;
 #[cfg(test)]
 mod scryfall_tests;
+pub mod sort;
+#[cfg(test)]
+mod sort_tests;
+pub mod stats;
+#[cfg(test)]
+mod stats_tests;
+pub mod storage;
+#[cfg(test)]
+mod storage_tests;
+#[cfg(test)]
+mod test_cards;
 pub const CARD_CORNER_RADIUS: f32 = 1.0 / 20.0;
 #[eii(app_name)]
 pub fn app_name() -> &'static str {
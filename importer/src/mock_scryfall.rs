@@ -0,0 +1,282 @@
+use crate::config::Config;
+use crate::names::normalize;
+use crate::storage::MemoryStorage;
+use jzon::JsonValue;
+use reqwest::Client;
+use std::io::{BufRead as _, BufReader, Write as _};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use uuid::Uuid;
+const IMAGE: &[u8] = include_bytes!("../../assets/back.png");
+#[derive(Clone)]
+pub struct MockCard {
+    pub id: Uuid,
+    pub oracle_id: Uuid,
+    pub name: &'static str,
+    pub back: Option<&'static str>,
+    pub set: &'static str,
+    pub cn: &'static str,
+}
+pub struct MockScryfall {
+    pub cards: Vec<MockCard>,
+    pub failures: AtomicUsize,
+}
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+impl Response {
+    fn json(json: &JsonValue) -> Self {
+        Self {
+            status: "200 OK",
+            content_type: "application/json",
+            body: json.dump().into_bytes(),
+        }
+    }
+    fn error(code: &'static str, kind: Option<&'static str>) -> Self {
+        let mut json = JsonValue::new_object();
+        json["object"] = "error".into();
+        json["code"] = code.into();
+        json["status"] = 404.into();
+        if let Some(kind) = kind {
+            json["type"] = kind.into();
+        }
+        Self {
+            status: "404 Not Found",
+            ..Self::json(&json)
+        }
+    }
+    fn not_found() -> Self {
+        Self::error("not_found", None)
+    }
+}
+fn face(name: &str) -> JsonValue {
+    let mut json = JsonValue::new_object();
+    json["name"] = name.into();
+    json["mana_cost"] = "{1}{G}".into();
+    json["type_line"] = "Creature — Elf Druid".into();
+    json["oracle_text"] = "{T}: Add {G}.".into();
+    json["colors"] = JsonValue::Array(vec!["G".into()]);
+    json["power"] = "1".into();
+    json["toughness"] = "1".into();
+    json
+}
+fn list(object: &str, data: Vec<JsonValue>) -> JsonValue {
+    let mut json = JsonValue::new_object();
+    json["object"] = object.into();
+    json["has_more"] = false.into();
+    json["data"] = JsonValue::Array(data);
+    json
+}
+fn decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut iter = text.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let digits = [iter.next().unwrap_or(b'0'), iter.next().unwrap_or(b'0')];
+                let hex = str::from_utf8(&digits).unwrap_or("00");
+                bytes.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+            }
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+impl MockCard {
+    fn full_name(&self) -> String {
+        self.back.map_or_else(
+            || self.name.to_owned(),
+            |back| format!("{} // {back}", self.name),
+        )
+    }
+    fn json(&self) -> JsonValue {
+        let mut json = if let Some(back) = self.back {
+            let mut json = JsonValue::new_object();
+            json["layout"] = "transform".into();
+            let faces = [self.name, back].map(|name| {
+                let mut face = face(name);
+                face["image_uris"] = JsonValue::new_object();
+                face
+            });
+            json["card_faces"] = JsonValue::Array(faces.into());
+            json["color_identity"] = JsonValue::Array(vec!["G".into()]);
+            json
+        } else {
+            let mut json = face(self.name);
+            json["layout"] = "normal".into();
+            json["color_identity"] = JsonValue::Array(vec!["G".into()]);
+            json
+        };
+        json["object"] = "card".into();
+        json["name"] = self.full_name().into();
+        json["id"] = self.id.to_string().into();
+        json["oracle_id"] = self.oracle_id.to_string().into();
+        json["set"] = self.set.into();
+        json["collector_number"] = self.cn.into();
+        json["rarity"] = "common".into();
+        json
+    }
+}
+impl MockScryfall {
+    #[must_use]
+    pub fn new(cards: Vec<MockCard>) -> Self {
+        Self {
+            cards,
+            failures: AtomicUsize::new(0),
+        }
+    }
+    #[must_use]
+    pub fn with_failures(self, failures: usize) -> Self {
+        self.failures.store(failures, Ordering::Relaxed);
+        self
+    }
+    #[must_use]
+    pub fn spawn(self) -> Config {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let mock = Arc::new(self);
+        thread::spawn(move || {
+            for stream in listener.incoming().filter_map(Result::ok) {
+                let mock = mock.clone();
+                thread::spawn(move || mock.respond(stream));
+            }
+        });
+        Config::from_storage(Client::new(), Arc::new(MemoryStorage::default()))
+            .with_urls(&url, &url)
+    }
+    fn respond(&self, mut stream: TcpStream) {
+        let mut line = String::new();
+        {
+            let mut reader = BufReader::new(&stream);
+            if reader.read_line(&mut line).is_err() {
+                return;
+            }
+            let mut header = String::new();
+            while reader.read_line(&mut header).is_ok_and(|n| n > 2) {
+                header.clear();
+            }
+        }
+        let target = line.split(' ').nth(1).unwrap_or_default();
+        if self
+            .failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            let _ = write!(
+                stream,
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            );
+            return;
+        }
+        let response = self.route(target);
+        let _ = write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            response.status,
+            response.content_type,
+            response.body.len()
+        );
+        let _ = stream.write_all(&response.body);
+    }
+    fn route(&self, target: &str) -> Response {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let param = |key: &str| {
+            query
+                .split('&')
+                .find_map(|kv| kv.strip_prefix(key)?.strip_prefix('='))
+                .map(decode)
+                .unwrap_or_default()
+        };
+        let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        match segments.as_slice() {
+            ["cards", "search"] => {
+                let q = param("q");
+                let Some(oracle) = q
+                    .strip_prefix("oracleid=")
+                    .and_then(|s| s.split(' ').next())
+                    .and_then(|s| Uuid::parse_str(s).ok())
+                else {
+                    return Response::not_found();
+                };
+                let data: Vec<JsonValue> = self
+                    .cards
+                    .iter()
+                    .filter(|c| c.oracle_id == oracle)
+                    .map(MockCard::json)
+                    .collect();
+                if data.is_empty() {
+                    Response::not_found()
+                } else {
+                    Response::json(&list("list", data))
+                }
+            }
+            ["cards", "named"] => self.named(&param("fuzzy")),
+            ["cards", "autocomplete"] => {
+                let q = normalize(&param("q"));
+                let mut names: Vec<String> = self
+                    .cards
+                    .iter()
+                    .map(MockCard::full_name)
+                    .filter(|n| normalize(n).contains(&q))
+                    .collect();
+                names.sort_unstable();
+                names.dedup();
+                let data = names.into_iter().map(JsonValue::from).collect();
+                Response::json(&list("catalog", data))
+            }
+            ["cards", id] => self.find(|c| Uuid::parse_str(id).is_ok_and(|uuid| c.id == uuid)),
+            ["cards", set, cn] => self.find(|c| c.set == *set && c.cn == *cn),
+            [_, _, _, _, file] => {
+                let id = file.split('.').next().unwrap_or_default();
+                if self
+                    .cards
+                    .iter()
+                    .any(|c| Uuid::parse_str(id).is_ok_and(|uuid| c.id == uuid))
+                {
+                    Response {
+                        status: "200 OK",
+                        content_type: "image/png",
+                        body: IMAGE.to_vec(),
+                    }
+                } else {
+                    Response::not_found()
+                }
+            }
+            _ => Response::not_found(),
+        }
+    }
+    fn find(&self, f: impl Fn(&MockCard) -> bool) -> Response {
+        self.cards
+            .iter()
+            .find(|c| f(c))
+            .map_or_else(Response::not_found, |c| Response::json(&c.json()))
+    }
+    fn named(&self, fuzzy: &str) -> Response {
+        let query = normalize(fuzzy);
+        let exact = self.cards.iter().find(|c| {
+            normalize(&c.full_name()) == query
+                || normalize(c.name) == query
+                || c.back.is_some_and(|b| normalize(b) == query)
+        });
+        if let Some(card) = exact {
+            return Response::json(&card.json());
+        }
+        let mut matches: Vec<&MockCard> = self
+            .cards
+            .iter()
+            .filter(|c| normalize(&c.full_name()).contains(&query))
+            .collect();
+        matches.sort_by_key(|c| c.oracle_id);
+        matches.dedup_by_key(|c| c.oracle_id);
+        match matches.as_slice() {
+            [card] => Response::json(&card.json()),
+            [] => Response::not_found(),
+            _ => Response::error("not_found", Some("ambiguous")),
+        }
+    }
+}
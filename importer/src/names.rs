@@ -0,0 +1,133 @@
+use crate::card::CardData;
+use rustc_hash::FxBuildHasher;
+use std::collections::HashMap;
+use uuid::Uuid;
+pub const MAX_CHOICES: usize = 10;
+#[derive(Default)]
+pub struct NameIndex {
+    pub exact: HashMap<Box<str>, Uuid, FxBuildHasher>,
+    pub normalized: HashMap<Box<str>, (Box<str>, Uuid), FxBuildHasher>,
+}
+#[derive(Debug, PartialEq)]
+pub enum NameMatch {
+    Found(Uuid),
+    Ambiguous(Vec<Box<str>>),
+    None,
+}
+impl NameIndex {
+    pub fn insert(&mut self, data: &CardData) {
+        self.insert_name(&data.front.name, data.id);
+        if let Some(back) = &data.back {
+            self.insert_name(&back.name, data.id);
+            self.insert_name(&format!("{} // {}", data.front.name, back.name), data.id);
+        }
+    }
+    pub fn insert_name(&mut self, name: &str, uuid: Uuid) {
+        self.exact.entry(name.into()).or_insert(uuid);
+        self.normalized
+            .entry(normalize(name).into_boxed_str())
+            .or_insert_with(|| (name.into(), uuid));
+    }
+    #[must_use]
+    pub fn get(&self, query: &str) -> Option<Uuid> {
+        if let Some(&uuid) = self.exact.get(query.trim()) {
+            return Some(uuid);
+        }
+        self.normalized
+            .get(normalize(query).as_str())
+            .map(|&(_, uuid)| uuid)
+    }
+    #[must_use]
+    pub fn resolve(&self, query: &str) -> NameMatch {
+        if let Some(uuid) = self.get(query) {
+            return NameMatch::Found(uuid);
+        }
+        let norm = normalize(query);
+        if norm.is_empty() {
+            return NameMatch::None;
+        }
+        let contains = self
+            .normalized
+            .iter()
+            .filter(|(k, _)| k.contains(norm.as_str()))
+            .map(|(_, v)| v);
+        let found = collect(contains);
+        if !matches!(found, NameMatch::None) {
+            return found;
+        }
+        let max = (norm.chars().count() / 4).max(1);
+        let mut best = max;
+        let mut closest = Vec::new();
+        for (k, v) in &self.normalized {
+            let dist = edit_distance(k, &norm);
+            if dist < best {
+                best = dist;
+                closest.clear();
+            }
+            if dist == best {
+                closest.push(v);
+            }
+        }
+        collect(closest)
+    }
+}
+fn collect<'a>(found: impl IntoIterator<Item = &'a (Box<str>, Uuid)>) -> NameMatch {
+    let mut by_uuid: HashMap<Uuid, &Box<str>, FxBuildHasher> = HashMap::default();
+    for (name, uuid) in found {
+        let entry = by_uuid.entry(*uuid).or_insert(name);
+        if name.len() > entry.len() {
+            *entry = name;
+        }
+    }
+    match by_uuid.len() {
+        0 => NameMatch::None,
+        1 => NameMatch::Found(*by_uuid.keys().next().unwrap()),
+        _ => {
+            let mut names: Vec<Box<str>> = by_uuid.into_values().cloned().collect();
+            names.sort_unstable();
+            names.dedup();
+            names.truncate(MAX_CHOICES);
+            NameMatch::Ambiguous(names)
+        }
+    }
+}
+#[must_use]
+pub fn normalize(name: &str) -> String {
+    let mut ret = String::with_capacity(name.len());
+    for c in name.chars().flat_map(char::to_lowercase) {
+        match c {
+            'æ' => ret.push_str("ae"),
+            c if c.is_alphanumeric() => ret.push(c),
+            c if (c.is_whitespace() || c == '/' || c == '-')
+                && !ret.is_empty()
+                && !ret.ends_with(' ') =>
+            {
+                ret.push(' ');
+            }
+            _ => {}
+        }
+    }
+    if ret.ends_with(' ') {
+        ret.pop();
+    }
+    ret
+}
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let chars: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=chars.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in chars.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diag
+            } else {
+                1 + diag.min(above).min(row[j])
+            };
+            diag = above;
+        }
+    }
+    row[chars.len()]
+}
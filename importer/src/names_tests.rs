@@ -0,0 +1,58 @@
+use crate::names::{NameIndex, NameMatch, edit_distance, normalize};
+use crate::test_cards::card;
+use uuid::Uuid;
+fn index() -> NameIndex {
+    let mut index = NameIndex::default();
+    for data in [
+        card("Lightning Bolt", "", "", "").with_id(1),
+        card("Lightning Helix", "", "", "").with_id(2),
+        card("Fire", "", "", "").with_id(3).with_back("Ice"),
+        card("Jace, the Mind Sculptor", "", "", "").with_id(4),
+        card("Æther Vial", "", "", "").with_id(5),
+    ] {
+        index.insert(&data);
+    }
+    index
+}
+#[test]
+fn test_normalize() {
+    assert_eq!(
+        normalize("Jace, the Mind Sculptor"),
+        "jace the mind sculptor"
+    );
+    assert_eq!(normalize("Fire // Ice"), "fire ice");
+    assert_eq!(normalize("  Æther   Vial "), "aether vial");
+    assert_eq!(
+        normalize("Borrowing 100,000 Arrows"),
+        "borrowing 100000 arrows"
+    );
+}
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("bolt", "bolt"), 0);
+    assert_eq!(edit_distance("bolt", "blot"), 2);
+    assert_eq!(edit_distance("lightning", "lightening"), 1);
+    assert_eq!(edit_distance("", "ice"), 3);
+    assert_eq!(edit_distance("fire", ""), 4);
+}
+#[test]
+fn test_resolve() {
+    let index = index();
+    let found = |n| NameMatch::Found(Uuid::from_u128(n));
+    assert_eq!(index.resolve("Lightning Bolt"), found(1));
+    assert_eq!(index.resolve("lightning bolt"), found(1));
+    assert_eq!(index.resolve("jace the mind sculptor"), found(4));
+    assert_eq!(index.resolve("aether vial"), found(5));
+    assert_eq!(index.resolve("Fire // Ice"), found(3));
+    assert_eq!(index.resolve("fire/ice"), found(3));
+    assert_eq!(index.resolve("ice"), found(3));
+    assert_eq!(index.resolve("helix"), found(2));
+    assert_eq!(index.resolve("lightnig bolt"), found(1));
+    assert_eq!(index.resolve("jace teh mind sculptor"), found(4));
+    assert_eq!(
+        index.resolve("lightning"),
+        NameMatch::Ambiguous(vec!["Lightning Bolt".into(), "Lightning Helix".into()])
+    );
+    assert_eq!(index.resolve("goblin guide"), NameMatch::None);
+    assert_eq!(index.resolve("//"), NameMatch::None);
+}
@@ -1,9 +1,59 @@
 use crate::card::CardInfo;
-use bevy::ecs::children;
-use bevy::prelude::Bundle;
+pub const TITLE_WIDTH: usize = 24;
+pub const TEXT_WIDTH: usize = 36;
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OracleFace {
+    pub name: String,
+    pub cost: String,
+    pub type_line: String,
+    pub text: String,
+    pub stats: Option<String>,
+}
 impl CardInfo {
     #[must_use]
-    pub fn get_oracle(&self) -> impl Bundle + use<> {
-        children![]
+    pub fn get_oracle(&self) -> OracleFace {
+        let stats = match (self.power, self.toughness, self.loyalty) {
+            (Some(power), Some(toughness), _) => Some(format!("{power}/{toughness}")),
+            (_, _, Some(loyalty)) => Some(loyalty.to_string()),
+            _ => None,
+        };
+        OracleFace {
+            name: wrap(&self.name, TITLE_WIDTH),
+            cost: self.mana_cost.to_string(),
+            type_line: wrap(&self.type_line.to_string(), TEXT_WIDTH),
+            text: wrap(&self.oracle_text, TEXT_WIDTH),
+            stats,
+        }
+    }
+}
+#[must_use]
+pub fn wrap(text: &str, width: usize) -> String {
+    let mut wrapped = String::with_capacity(text.len());
+    for (i, paragraph) in text.lines().enumerate() {
+        if i != 0 {
+            wrapped.push('\n');
+        }
+        let mut line = 0;
+        for word in paragraph.split_whitespace() {
+            let mut rest = word;
+            while !rest.is_empty() {
+                let len = rest.chars().count();
+                if line != 0 && line + 1 + len > width {
+                    wrapped.push('\n');
+                    line = 0;
+                } else if line != 0 {
+                    wrapped.push(' ');
+                    line += 1;
+                }
+                let take = rest
+                    .char_indices()
+                    .nth(width - line)
+                    .map_or(rest.len(), |(i, _)| i);
+                wrapped.push_str(&rest[..take]);
+                line += rest[..take].chars().count();
+                rest = &rest[take..];
+            }
+        }
     }
+    wrapped
 }
@@ -0,0 +1,57 @@
+use crate::card::{CardInfo, Cost, MainType, SubType, SuperType, Types};
+use crate::oracle_card::wrap;
+#[test]
+fn test_wrap() {
+    assert_eq!(wrap("", 10), "");
+    assert_eq!(wrap("one two three", 7), "one two\nthree");
+    assert_eq!(wrap("one  two", 20), "one two");
+    assert_eq!(wrap("Flying\nVigilance", 20), "Flying\nVigilance");
+    assert_eq!(wrap("abcdefghij", 4), "abcd\nefgh\nij");
+    assert_eq!(wrap("ab abcdefgh", 5), "ab\nabcde\nfgh");
+    for line in wrap(
+        "When this enters, draw a card for each creature you control.",
+        12,
+    )
+    .lines()
+    {
+        assert!(line.chars().count() <= 12, "{line}");
+    }
+}
+#[test]
+fn test_get_oracle() {
+    let mut info = CardInfo {
+        name: "Llanowar Elves".into(),
+        mana_cost: Cost::from("{G}"),
+        type_line: Types::default(),
+        oracle_text: "{T}: Add {G}.".into(),
+        power: Some(1),
+        toughness: Some(1),
+        ..CardInfo::default()
+    };
+    info.type_line.main_type.types.insert(MainType::Creature);
+    info.type_line.sub_type.types.insert(SubType::Elf);
+    info.type_line.sub_type.types.insert(SubType::Druid);
+    let oracle = info.get_oracle();
+    assert_eq!(oracle.name, "Llanowar Elves");
+    assert_eq!(oracle.cost, "{G}");
+    assert!(oracle.type_line.starts_with("Creature \u{2014} "));
+    assert!(oracle.type_line.contains("Elf") && oracle.type_line.contains("Druid"));
+    assert_eq!(oracle.text, "{T}: Add {G}.");
+    assert_eq!(oracle.stats.as_deref(), Some("1/1"));
+    info.power = None;
+    info.toughness = None;
+    info.loyalty = Some(3);
+    info.type_line = Types::default();
+    info.type_line.super_type.types.insert(SuperType::Legendary);
+    info.type_line
+        .main_type
+        .types
+        .insert(MainType::Planeswalker);
+    info.mana_cost = Cost::from("{X}{2}{U}{U}");
+    let oracle = info.get_oracle();
+    assert_eq!(oracle.type_line, "Legendary Planeswalker");
+    assert_eq!(oracle.cost, "{X}{2}{U}{U}");
+    assert_eq!(oracle.stats.as_deref(), Some("3"));
+    assert_eq!(Cost::from("{0}").to_string(), "{0}");
+    assert_eq!(Cost::default().to_string(), "");
+}
@@ -1,25 +1,30 @@
-use crate::card::{CardData, CardInfo, Layout, MaybeHandles};
+use crate::card::{CardData, CardInfo, Format, Layout, Legalities, MaybeHandles, Rarity};
 use crate::card::{Colors, Cost, SubCard, Types};
 use crate::card_cache::{
     CacheRead, CacheReadImage, CacheResult, CardCache, CardInCache, Identifier, get_images,
-    write_image,
+    read_compressed, record_storage, write_compressed, write_image,
 };
-use crate::image::parse_bytes;
+use crate::config::Config;
+use crate::decode::DECODE_POOL;
+use crate::image::{compress, parse_bytes};
+use crate::names::{MAX_CHOICES, NameMatch};
 use bevy::image::Image;
 use bevy::log::warn;
+use bevy::reflect::Reflect;
 use futures::future::join_all;
 use jzon::{JsonValue, parse};
 use ratelimit::Ratelimiter;
-use reqwest::Client;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
 use rustc_hash::FxBuildHasher;
 use std::collections::{HashMap, HashSet};
-use std::fmt::{Debug, Display, Formatter};
+use std::fmt::{Display, Formatter};
 use std::mem;
 use std::str::FromStr as _;
 use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 use tokio::join;
-use tokio::sync::{Mutex, Semaphore};
+use tokio::sync::Mutex;
 #[cfg(not(target_family = "wasm"))]
 use tokio::time::sleep;
 #[cfg(target_family = "wasm")]
@@ -27,29 +32,93 @@ use tokio_with_wasm as tokio;
 use uuid::Uuid;
 #[cfg(target_family = "wasm")]
 use wasmtimer::tokio::sleep;
-pub static CACHE: LazyLock<Mutex<CardCache>> = LazyLock::new(|| Mutex::new(CardCache::default()));
-const URL: &str = "api.scryfall.com";
-const CARD_URL: &str = "cards.scryfall.io";
-#[derive(Clone, Copy)]
+pub static CACHE: LazyLock<Arc<Mutex<CardCache>>> =
+    LazyLock::new(|| Arc::new(Mutex::new(CardCache::default())));
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
 pub enum Quality {
     Small,
     Normal,
+    #[default]
     Large,
     Png,
 }
+#[derive(Clone, Copy)]
 pub enum Side {
     Front,
     Back,
 }
-fn warn_if<T, E: Debug>(val: Result<T, E>) -> Option<T> {
-    match val {
-        Ok(v) => Some(v),
-        Err(e) => {
-            warn!("{e:?}");
-            None
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchError {
+    Network(Box<str>),
+    Status(u16),
+    Parse(Box<str>),
+    NotFound,
+    Failed,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardError<T> {
+    pub id: T,
+    pub error: FetchError,
+}
+#[derive(Debug, Clone, PartialEq)]
+pub enum NameError {
+    Ambiguous(Vec<Box<str>>),
+    Fetch(CardError<Box<str>>),
+}
+impl FetchError {
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Failed => true,
+            &Self::Status(code) => StatusCode::from_u16(code)
+                .is_ok_and(|s| s == StatusCode::TOO_MANY_REQUESTS || s.is_server_error()),
+            Self::Parse(_) | Self::NotFound => false,
+        }
+    }
+    fn parse(msg: &str) -> Self {
+        Self::Parse(msg.into())
+    }
+}
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "network error: {e}"),
+            Self::Status(code) => write!(f, "http status {code}"),
+            Self::Parse(e) => write!(f, "parse error: {e}"),
+            Self::NotFound => write!(f, "not found"),
+            Self::Failed => write!(f, "concurrent fetch failed"),
+        }
+    }
+}
+impl<T: Display> Display for CardError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.id, self.error)
+    }
+}
+impl Display for NameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ambiguous(names) => write!(f, "ambiguous name: {}", names.join(", ")),
+            Self::Fetch(e) => write!(f, "{e}"),
+        }
+    }
+}
+impl<T> CardError<T> {
+    fn new(id: impl Into<T>, error: FetchError) -> Self {
+        Self {
+            id: id.into(),
+            error,
         }
     }
 }
+impl NameError {
+    fn new(name: &str, error: FetchError) -> Self {
+        Self::Fetch(CardError::new(name, error))
+    }
+}
+fn network(e: &reqwest::Error) -> FetchError {
+    FetchError::Network(e.to_string().into_boxed_str())
+}
 impl Display for Side {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -80,99 +149,157 @@ impl Quality {
         }
     }
 }
-pub async fn throttled_parse_bytes(bytes: &[u8]) -> Option<Image> {
-    static THROTTLE: LazyLock<Semaphore> = LazyLock::new(|| {
-        let cpus = 1;
-        Semaphore::new(cpus)
-    });
-    let lock = THROTTLE.acquire().await.unwrap();
-    let res = parse_bytes(bytes);
-    drop(lock);
-    res
+pub async fn decode_bytes(uuid: Uuid, bytes: impl AsRef<[u8]> + Send + 'static) -> Option<Image> {
+    DECODE_POOL
+        .run(uuid, move || parse_bytes(bytes.as_ref()))
+        .await
+        .flatten()
 }
-#[cfg(not(target_family = "wasm"))]
-async fn get_image(
-    client: &Client,
+async fn compress_image(
+    config: &Config,
+    image: Image,
     set_cn: &str,
     uuid: Uuid,
     quality: Quality,
     side: Side,
 ) -> Option<Image> {
-    let byte = uuid.as_bytes()[0];
-    let request = warn_if(
-        client
-            .get(format!(
-                "https://{CARD_URL}/{}/{side}/{:x}/{:x}/{uuid}.{}",
-                quality.name(),
-                byte / 16,
-                byte % 16,
-                quality.extension(),
-            ))
-            .send()
-            .await,
-    )?;
-    let bytes = warn_if(request.bytes().await)?;
-    write_image(&bytes, set_cn, uuid, side).await;
-    throttled_parse_bytes(&bytes).await
+    let compressed = match DECODE_POOL
+        .run(uuid, move || compress(&image).ok_or(image))
+        .await?
+    {
+        Ok(compressed) => compressed,
+        Err(image) => return Some(image),
+    };
+    let bytes_stored =
+        write_compressed(&config.storage, &compressed, set_cn, uuid, side, quality).await;
+    record_storage(config, uuid, set_cn, bytes_stored).await;
+    Some(compressed)
 }
-#[cfg(target_family = "wasm")]
-async fn get_image(_: &Client, _: &str, _: Uuid, _: Quality, _: Side) -> Option<Image> {
-    None
+async fn get_image(
+    config: &Config,
+    set_cn: &str,
+    uuid: Uuid,
+    quality: Quality,
+    side: Side,
+    (remote, remote_side): (Uuid, Side),
+) -> Option<Image> {
+    let byte = remote.as_bytes()[0];
+    let url = format!(
+        "{}/{}/{remote_side}/{:x}/{:x}/{remote}.{}",
+        config.card_url,
+        quality.name(),
+        byte / 16,
+        byte % 16,
+        quality.extension(),
+    );
+    let bytes = match fetch(None, || config.client.get(&url))
+        .await
+        .and_then(check_status)
+    {
+        Ok(response) => response.bytes().await.map_err(|e| network(&e)),
+        Err(e) => Err(e),
+    };
+    let bytes = bytes.inspect_err(|e| warn!("{url}: {e}")).ok()?;
+    let image = decode_bytes(uuid, bytes.clone()).await?;
+    let bytes_stored = write_image(&config.storage, bytes, set_cn, uuid, side, quality).await;
+    record_storage(config, uuid, set_cn, bytes_stored).await;
+    Some(image)
 }
 impl CacheReadImage {
     pub async fn get_image(
         self,
-        client: &Client,
+        config: &Config,
         set_cn: &str,
         uuid: Uuid,
         quality: Quality,
         side: Side,
+        remote: (Uuid, Side),
     ) -> Option<Image> {
-        match self {
+        if config.compress
+            && !matches!(self, CacheReadImage::None)
+            && let Some(image) = read_compressed(&config.storage, set_cn, uuid, side, quality).await
+        {
+            return Some(image);
+        }
+        let image = match self {
             CacheReadImage::Some(bytes) => {
-                if let Some(image) = throttled_parse_bytes(&bytes).await {
+                if let Some(image) = decode_bytes(uuid, bytes).await {
                     Some(image)
                 } else {
-                    get_image(client, set_cn, uuid, quality, side).await
+                    get_image(config, set_cn, uuid, quality, side, remote).await
                 }
             }
-            CacheReadImage::Missing => get_image(client, set_cn, uuid, quality, side).await,
+            CacheReadImage::Missing => get_image(config, set_cn, uuid, quality, side, remote).await,
             CacheReadImage::None => None,
+        }?;
+        if config.compress {
+            compress_image(config, image, set_cn, uuid, quality, side).await
+        } else {
+            Some(image)
         }
     }
 }
+fn back_source(uuid: Uuid, meld_result: Option<Uuid>) -> (Uuid, Side) {
+    meld_result.map_or((uuid, Side::Back), |meld| (meld, Side::Front))
+}
 async fn read_cards(
-    client: Client,
+    config: Config,
     set_cn: Box<str>,
     uuid: Uuid,
+    meld_result: Option<Uuid>,
     quality: Quality,
     front_image: CacheReadImage,
     back_image: CacheReadImage,
 ) {
+    let front = (uuid, Side::Front);
+    let back = back_source(uuid, meld_result);
     if IMAGES_IN_PROGRESS.lock().await.insert(uuid) {
+        if quality != Quality::Small && matches!(front_image, CacheReadImage::Missing) {
+            let has_back = !matches!(back_image, CacheReadImage::None);
+            let (front_preview, back_preview) =
+                get_images(&config.storage, &set_cn, uuid, has_back, Quality::Small).await;
+            let (front, back) = join!(
+                front_preview.get_image(&config, &set_cn, uuid, Quality::Small, Side::Front, front),
+                back_preview.get_image(&config, &set_cn, uuid, Quality::Small, Side::Back, back)
+            );
+            if front.is_some() {
+                IMAGES_TO_PROCESS.lock().await.insert(uuid, (front, back));
+            }
+        }
         let (front, back) = join!(
-            front_image.get_image(&client, &set_cn, uuid, quality, Side::Front),
-            back_image.get_image(&client, &set_cn, uuid, quality, Side::Back)
+            front_image.get_image(&config, &set_cn, uuid, quality, Side::Front, front),
+            back_image.get_image(&config, &set_cn, uuid, quality, Side::Back, back)
         );
         IMAGES_TO_PROCESS.lock().await.insert(uuid, (front, back));
         IMAGES_IN_PROGRESS.lock().await.remove(&uuid);
     }
 }
 async fn read_cards_check(
-    client: Client,
+    config: Config,
     set_cn: Box<str>,
     uuid: Uuid,
+    meld_result: Option<Uuid>,
     quality: Quality,
-    mut front_image: CacheReadImage,
-    mut back_image: CacheReadImage,
+    back_image: CacheReadImage,
 ) {
-    if let Some((front, back)) =
-        get_images(&set_cn, uuid, matches!(back_image, CacheReadImage::Missing)).await
-    {
-        front_image = front;
-        back_image = back;
-    }
-    read_cards(client, set_cn, uuid, quality, front_image, back_image).await;
+    let (front_image, stored_back) = get_images(
+        &config.storage,
+        &set_cn,
+        uuid,
+        matches!(back_image, CacheReadImage::Missing),
+        quality,
+    )
+    .await;
+    read_cards(
+        config,
+        set_cn,
+        uuid,
+        meld_result,
+        quality,
+        front_image,
+        stored_back,
+    )
+    .await;
 }
 impl From<&MaybeHandles> for CacheReadImage {
     fn from(value: &MaybeHandles) -> Self {
@@ -207,19 +334,113 @@ static CARDS_THROTTLE: LazyLock<Ratelimiter<Clock>> =
 static SEARCH_THROTTLE: LazyLock<Ratelimiter<Clock>> =
     LazyLock::new(|| Ratelimiter::with_clock(1, Clock::default()));
 pub const SLEEP_TIME: Duration = Duration::new(0, 1_048_576);
-async fn get_uuid(client: Client, uuid: Uuid, quality: Quality) -> Option<SubCard> {
-    while CARDS_THROTTLE.try_wait().is_err() {
-        sleep(SLEEP_TIME).await;
+pub const MAX_RETRIES: u32 = 4;
+pub const BACKOFF: Duration = Duration::from_millis(250);
+fn retry_after(response: &Response) -> Option<Duration> {
+    let secs = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    secs.trim().parse().ok().map(Duration::from_secs)
+}
+async fn fetch(
+    throttle: Option<&Ratelimiter<Clock>>,
+    request: impl Fn() -> RequestBuilder,
+) -> Result<Response, FetchError> {
+    let mut attempt = 0;
+    loop {
+        if let Some(throttle) = throttle {
+            while throttle.try_wait().is_err() {
+                sleep(SLEEP_TIME).await;
+            }
+        }
+        let backoff = BACKOFF * 2u32.pow(attempt);
+        let (error, wait) = match request().send().await {
+            Ok(response)
+                if response.status() == StatusCode::TOO_MANY_REQUESTS
+                    || response.status().is_server_error() =>
+            {
+                let wait = retry_after(&response).unwrap_or(backoff);
+                (FetchError::Status(response.status().as_u16()), wait)
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_builder() => return Err(network(&e)),
+            Err(e) => (network(&e), backoff),
+        };
+        if attempt == MAX_RETRIES {
+            return Err(error);
+        }
+        warn!("{error}, retrying in {wait:?}");
+        sleep(wait).await;
+        attempt += 1;
+    }
+}
+async fn read_json(response: Response) -> Result<JsonValue, FetchError> {
+    let json_raw = response.text().await.map_err(|e| network(&e))?;
+    parse(&json_raw).map_err(|e| FetchError::Parse(e.to_string().into_boxed_str()))
+}
+async fn fetch_json(
+    throttle: &Ratelimiter<Clock>,
+    request: impl Fn() -> RequestBuilder,
+) -> Result<JsonValue, FetchError> {
+    let response = check_status(fetch(Some(throttle), request).await?)?;
+    read_json(response).await
+}
+fn check_status(response: Response) -> Result<Response, FetchError> {
+    match response.status() {
+        StatusCode::NOT_FOUND => Err(FetchError::NotFound),
+        status if !status.is_success() => Err(FetchError::Status(status.as_u16())),
+        _ => Ok(response),
+    }
+}
+async fn get_uuid(config: Config, uuid: Uuid, quality: Quality) -> Result<SubCard, FetchError> {
+    let json = fetch_json(&CARDS_THROTTLE, || {
+        config
+            .client
+            .get(format!("{}/cards/{uuid}", config.api_url))
+    })
+    .await?;
+    SubCard::from_scryfall(config, json, uuid, quality).await
+}
+async fn get_autocomplete(config: &Config, name: &str) -> Result<Vec<Box<str>>, FetchError> {
+    let json = fetch_json(&SEARCH_THROTTLE, || {
+        config
+            .client
+            .get(format!("{}/cards/autocomplete", config.api_url))
+            .query(&(("q", name),))
+    })
+    .await?;
+    Ok(json["data"]
+        .as_array()
+        .ok_or_else(|| FetchError::parse("missing data"))?
+        .iter()
+        .filter_map(JsonValue::as_str)
+        .take(MAX_CHOICES)
+        .map(Box::from)
+        .collect())
+}
+async fn get_fuzzy(config: Config, name: &str, quality: Quality) -> Result<SubCard, NameError> {
+    let response = fetch(Some(&SEARCH_THROTTLE), || {
+        config
+            .client
+            .get(format!("{}/cards/named", config.api_url))
+            .query(&(("fuzzy", name),))
+    })
+    .await
+    .map_err(|e| NameError::new(name, e))?;
+    let status = response.status();
+    let json = read_json(response)
+        .await
+        .map_err(|e| NameError::new(name, e))?;
+    match (json["object"].as_str(), json["type"].as_str()) {
+        (Some("card"), _) => SubCard::get_json(config, json, quality)
+            .await
+            .map_err(|e| NameError::new(name, e.error)),
+        (Some("error"), Some("ambiguous")) => match get_autocomplete(&config, name).await {
+            Ok(names) if !names.is_empty() => Err(NameError::Ambiguous(names)),
+            Ok(_) => Err(NameError::new(name, FetchError::NotFound)),
+            Err(e) => Err(NameError::new(name, e)),
+        },
+        _ if status == StatusCode::NOT_FOUND => Err(NameError::new(name, FetchError::NotFound)),
+        _ => Err(NameError::new(name, FetchError::Status(status.as_u16()))),
     }
-    let request = warn_if(
-        client
-            .get(format!("https://{URL}/cards/{uuid}"))
-            .send()
-            .await,
-    )?;
-    let json_raw = warn_if(request.text().await)?;
-    let json = warn_if(parse(&json_raw))?;
-    SubCard::from_scryfall(client, json, uuid, quality).await
 }
 pub static IMAGES_TO_PROCESS: LazyLock<
     Mutex<HashMap<Uuid, (Option<Image>, Option<Image>), FxBuildHasher>>,
@@ -229,121 +450,143 @@ pub static IMAGES_IN_PROGRESS: LazyLock<Mutex<HashSet<Uuid, FxBuildHasher>>> =
 impl SubCard {
     #[must_use]
     pub async fn get_list(
-        client: Client,
+        config: Config,
         iter: &[Uuid],
         quality: Quality,
-    ) -> Vec<Result<Self, Uuid>> {
+    ) -> Vec<Result<Self, CardError<Uuid>>> {
         join_all(
             iter.iter()
                 .copied()
-                .map(|uuid| Self::get(client.clone(), uuid, quality)),
+                .map(|uuid| Self::get(config.clone(), uuid, quality)),
         )
         .await
     }
     #[must_use]
     pub async fn get_list_set_cn(
-        client: Client,
+        config: Config,
         iter: &[&str],
         quality: Quality,
-    ) -> Vec<Result<Self, Box<str>>> {
+    ) -> Vec<Result<Self, CardError<Box<str>>>> {
         join_all(
             iter.iter()
-                .map(|set_cn| Self::get_set_cn(client.clone(), set_cn, quality)),
+                .map(|set_cn| Self::get_set_cn(config.clone(), set_cn, quality)),
         )
         .await
     }
     pub async fn get_prints_id(
-        client: Client,
+        config: Config,
         id: Uuid,
         quality: Quality,
-    ) -> Result<Vec<Result<Self, Uuid>>, Uuid> {
-        let card = Self::get(client.clone(), id, quality).await?;
-        Self::get_prints(client, card.data.front.oracle_id, quality)
+    ) -> Result<Vec<Result<Self, CardError<Uuid>>>, CardError<Uuid>> {
+        let card = Self::get(config.clone(), id, quality).await?;
+        Self::get_prints(config, card.data.front.oracle_id, quality)
             .await
-            .map_err(|_| id)
+            .map_err(|e| CardError::new(id, e.error))
     }
     pub async fn get_prints_set_cn(
-        client: Client,
+        config: Config,
         set_cn: &str,
         quality: Quality,
-    ) -> Result<Vec<Result<Self, Uuid>>, Box<str>> {
-        let card = Self::get_set_cn(client.clone(), set_cn, quality).await?;
-        Self::get_prints(client, card.data.front.oracle_id, quality)
+    ) -> Result<Vec<Result<Self, CardError<Uuid>>>, CardError<Box<str>>> {
+        let card = Self::get_set_cn(config.clone(), set_cn, quality).await?;
+        Self::get_prints(config, card.data.front.oracle_id, quality)
+            .await
+            .map_err(|e| CardError::new(set_cn, e.error))
+    }
+    pub async fn get_prints_named(
+        config: Config,
+        name: &str,
+        quality: Quality,
+    ) -> Result<Vec<Result<Self, CardError<Uuid>>>, NameError> {
+        let card = Self::get_named(config.clone(), name, quality).await?;
+        Self::get_prints(config, card.data.front.oracle_id, quality)
             .await
-            .map_err(|_| set_cn.into())
+            .map_err(|e| NameError::new(name, e.error))
     }
     pub async fn get_prints(
-        client: Client,
+        config: Config,
         oracle: Uuid,
         quality: Quality,
-    ) -> Result<Vec<Result<Self, Uuid>>, Uuid> {
+    ) -> Result<Vec<Result<Self, CardError<Uuid>>>, CardError<Uuid>> {
         async fn inner(
-            client: Client,
+            config: Config,
             oracle: Uuid,
             quality: Quality,
-        ) -> Option<Vec<Result<SubCard, Uuid>>> {
+        ) -> Result<Vec<Result<SubCard, CardError<Uuid>>>, FetchError> {
             let mut set = Vec::new();
             for i in 1.. {
-                while SEARCH_THROTTLE.try_wait().is_err() {
-                    sleep(SLEEP_TIME).await;
-                }
-                let request = warn_if(client
-                    .get(format!(
-                        "https://{URL}/cards/search?q=oracleid%3D{oracle}+game%3Dpaper+unique%3Aprints"
-                    ))
-                    .query(&(("page", i),))
-                    .send()
-                    .await
-                )?;
-                let json_raw = warn_if(request.text().await)?;
-                let mut json = warn_if(parse(&json_raw))?;
-                let mut list = join_all(json["data"].as_array_mut()?.iter_mut().map(|card_json| {
+                let mut json = fetch_json(&SEARCH_THROTTLE, || {
+                    config
+                        .client
+                        .get(format!(
+                            "{}/cards/search?q=oracleid%3D{oracle}+game%3Dpaper+unique%3Aprints",
+                            config.api_url
+                        ))
+                        .query(&(("page", i),))
+                })
+                .await?;
+                let data = json["data"]
+                    .as_array_mut()
+                    .ok_or_else(|| FetchError::parse("missing data"))?;
+                let mut list = join_all(data.iter_mut().map(|card_json| {
                     SubCard::get_json(
-                        client.clone(),
+                        config.clone(),
                         mem::replace(card_json, JsonValue::Null),
                         quality,
                     )
                 }))
                 .await;
                 set.append(&mut list);
-                if !json["has_more"].as_bool()? {
+                if !json["has_more"]
+                    .as_bool()
+                    .ok_or_else(|| FetchError::parse("missing has_more"))?
+                {
                     break;
                 }
             }
-            Some(set)
+            Ok(set)
         }
-        inner(client, oracle, quality).await.ok_or(oracle)
+        inner(config, oracle, quality)
+            .await
+            .map_err(|e| CardError::new(oracle, e))
     }
-    pub async fn get_json(client: Client, json: JsonValue, quality: Quality) -> Result<Self, Uuid> {
+    pub async fn get_json(
+        config: Config,
+        json: JsonValue,
+        quality: Quality,
+    ) -> Result<Self, CardError<Uuid>> {
         let uuid = Uuid::parse_str(json["id"].as_str().unwrap_or_default()).unwrap_or_default();
         let res = {
-            let mut cache = CACHE.lock().await;
+            let mut cache = config.cache.lock().await;
             cache.get(uuid)
         };
-        Self::get_cache_result(client, res, quality, async |client, quality| {
-            Self::from_scryfall(client, json, uuid, quality).await
+        Self::get_cache_result(config, res, quality, async |config, quality| {
+            Self::from_scryfall(config, json, uuid, quality).await
         })
         .await
-        .ok_or(uuid)
+        .map_err(|e| CardError::new(uuid, e))
     }
     pub async fn get_cache_result<F>(
-        client: Client,
+        config: Config,
         cache_result: CacheResult<'_>,
         quality: Quality,
-        on_none: impl FnOnce(Client, Quality) -> F,
-    ) -> Option<Self>
+        on_none: impl FnOnce(Config, Quality) -> F,
+    ) -> Result<Self, FetchError>
     where
-        F: Future<Output = Option<Self>>,
+        F: Future<Output = Result<Self, FetchError>>,
     {
+        let shared = config.cache.clone();
         match cache_result {
-            CacheResult::Some(card) => Some(Self {
+            CacheResult::Some(card) => Ok(Self {
                 data: card.strong,
                 face_handles: card.face_handles,
                 back_handles: card.back_handles,
                 flipped: false,
             }),
             CacheResult::Cached(set_cn, uuid) => {
-                if let Some(read) = CacheRead::read_files(&set_cn, uuid).await {
+                if let Some(read) =
+                    CacheRead::read_files(&config.storage, &set_cn, uuid, quality).await
+                {
                     let data = read.strong.clone();
                     let back_handles = if matches!(read.back_image, CacheReadImage::None) {
                         MaybeHandles::None
@@ -351,9 +594,10 @@ impl SubCard {
                         MaybeHandles::Waiting
                     };
                     tokio::spawn(read_cards(
-                        client,
+                        config,
                         data.set_cn.clone(),
                         uuid,
+                        data.meld_result,
                         quality,
                         read.front_image,
                         read.back_image,
@@ -364,29 +608,30 @@ impl SubCard {
                         back_handles: back_handles.clone(),
                         flipped: false,
                     };
-                    CACHE.lock().await.insert(CardInCache {
+                    shared.lock().await.insert(CardInCache {
                         strong: data,
                         face_handles: MaybeHandles::Waiting,
                         back_handles,
                     });
-                    Some(card)
-                } else if let Some(card) = on_none(client, quality).await {
-                    Some(card)
+                    Ok(card)
                 } else {
-                    CACHE.lock().await.in_progress.remove(&uuid);
-                    None
+                    let res = on_none(config, quality).await;
+                    if res.is_err() {
+                        shared.lock().await.in_progress.remove(&uuid);
+                    }
+                    res
                 }
             }
             CacheResult::Wait(Identifier::Uuid(uuid)) => loop {
                 sleep(SLEEP_TIME).await;
                 let card = {
-                    let cache = CACHE.lock().await;
+                    let cache = shared.lock().await;
                     if cache.in_progress.contains(&uuid) {
                         continue;
                     }
-                    cache.cards.get(&uuid)?.clone()
+                    cache.cards.get(&uuid).ok_or(FetchError::Failed)?.clone()
                 };
-                return Some(Self {
+                return Ok(Self {
                     data: card.strong,
                     face_handles: card.face_handles,
                     back_handles: card.back_handles,
@@ -396,76 +641,107 @@ impl SubCard {
             CacheResult::Wait(Identifier::SetCn(str)) => loop {
                 sleep(SLEEP_TIME).await;
                 let card = {
-                    let cache = CACHE.lock().await;
+                    let cache = shared.lock().await;
                     if cache.in_progress_set_cn.contains(str) {
                         continue;
                     }
-                    let &uuid = cache.set_cn.get_by_left(str)?;
-                    cache.cards.get(&uuid)?.clone()
+                    let &uuid = cache.set_cn.get_by_left(str).ok_or(FetchError::Failed)?;
+                    cache.cards.get(&uuid).ok_or(FetchError::Failed)?.clone()
                 };
-                return Some(Self {
+                return Ok(Self {
                     data: card.strong,
                     face_handles: card.face_handles,
                     back_handles: card.back_handles,
                     flipped: false,
                 });
             },
-            CacheResult::None(_) if let Some(card) = on_none(client, quality).await => Some(card),
-            CacheResult::None(Identifier::Uuid(uuid)) => {
-                CACHE.lock().await.in_progress.remove(&uuid);
-                None
-            }
-            CacheResult::None(Identifier::SetCn(str)) => {
-                CACHE.lock().await.in_progress_set_cn.remove(str);
-                None
+            CacheResult::None(identifier) => {
+                let res = on_none(config, quality).await;
+                if res.is_err() {
+                    let mut cache = shared.lock().await;
+                    match identifier {
+                        Identifier::Uuid(uuid) => {
+                            cache.in_progress.remove(&uuid);
+                        }
+                        Identifier::SetCn(str) => {
+                            cache.in_progress_set_cn.remove(str);
+                        }
+                    }
+                }
+                res
             }
         }
     }
-    pub async fn get(client: Client, uuid: Uuid, quality: Quality) -> Result<Self, Uuid> {
+    pub async fn get(
+        config: Config,
+        uuid: Uuid,
+        quality: Quality,
+    ) -> Result<Self, CardError<Uuid>> {
         let res = {
-            let mut cache = CACHE.lock().await;
+            let mut cache = config.cache.lock().await;
             cache.get(uuid)
         };
-        Self::get_cache_result(client, res, quality, async |client, quality| {
-            get_uuid(client, uuid, quality).await
+        Self::get_cache_result(config, res, quality, async |config, quality| {
+            get_uuid(config, uuid, quality).await
         })
         .await
-        .ok_or(uuid)
+        .map_err(|e| CardError::new(uuid, e))
     }
     pub async fn get_set_cn(
-        client: Client,
+        config: Config,
         set_cn: &str,
         quality: Quality,
-    ) -> Result<Self, Box<str>> {
+    ) -> Result<Self, CardError<Box<str>>> {
         let res = {
-            let mut cache = CACHE.lock().await;
+            let mut cache = config.cache.lock().await;
             cache.get_set_cn(set_cn)
         };
-        Self::get_cache_result(client, res, quality, async |client, quality| {
-            while CARDS_THROTTLE.try_wait().is_err() {
-                sleep(SLEEP_TIME).await;
-            }
-            let request = warn_if(
-                client
-                    .get(format!("https://{URL}/cards/{set_cn}"))
-                    .send()
-                    .await,
-            )?;
-            let json_raw = warn_if(request.text().await)?;
-            let json = warn_if(parse(&json_raw))?;
-            let uuid = warn_if(Uuid::parse_str(json["id"].as_str()?))?;
-            Self::from_scryfall(client, json, uuid, quality).await
+        Self::get_cache_result(config, res, quality, async |config, quality| {
+            let json = fetch_json(&CARDS_THROTTLE, || {
+                config
+                    .client
+                    .get(format!("{}/cards/{set_cn}", config.api_url))
+            })
+            .await?;
+            let uuid = json["id"]
+                .as_str()
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .ok_or_else(|| FetchError::parse("missing id"))?;
+            Self::from_scryfall(config, json, uuid, quality).await
         })
         .await
-        .ok_or_else(|| set_cn.into())
+        .map_err(|e| CardError::new(set_cn, e))
+    }
+    pub async fn get_named(
+        config: Config,
+        name: &str,
+        quality: Quality,
+    ) -> Result<Self, NameError> {
+        let exact = config.cache.lock().await.names.get(name);
+        if let Some(uuid) = exact {
+            return Self::get(config, uuid, quality)
+                .await
+                .map_err(|e| NameError::new(name, e.error));
+        }
+        let error = match get_fuzzy(config.clone(), name, quality).await {
+            Err(NameError::Fetch(e)) if e.error.is_retryable() => e,
+            res => return res,
+        };
+        let res = config.cache.lock().await.names.resolve(name);
+        match res {
+            NameMatch::Found(uuid) => Self::get(config, uuid, quality)
+                .await
+                .map_err(|e| NameError::new(name, e.error)),
+            NameMatch::Ambiguous(names) => Err(NameError::Ambiguous(names)),
+            NameMatch::None => Err(NameError::Fetch(error)),
+        }
     }
-    #[must_use]
     pub async fn from_scryfall(
-        client: Client,
+        config: Config,
         json: JsonValue,
         uuid: Uuid,
         quality: Quality,
-    ) -> Option<Self> {
+    ) -> Result<Self, FetchError> {
         fn get_face(json: &JsonValue, face: &JsonValue) -> Option<CardInfo> {
             fn get<'a>(face: &'a JsonValue, json: &'a JsonValue, s: &str) -> &'a JsonValue {
                 if face[s].is_null() {
@@ -474,7 +750,7 @@ impl SubCard {
                     &face[s]
                 }
             }
-            let oracle_id = warn_if(Uuid::parse_str(get(face, json, "oracle_id").as_str()?))?;
+            let oracle_id = Uuid::parse_str(get(face, json, "oracle_id").as_str()?).ok()?;
             let [name_raw, mana_cost_raw, type_line_raw, oracle_text_raw] =
                 ["name", "mana_cost", "type_line", "oracle_text"]
                     .try_map(|s| get(face, json, s).as_str())?;
@@ -494,7 +770,7 @@ impl SubCard {
             let oracle_text = oracle_text_raw.to_owned();
             let mana_cost = Cost::from(mana_cost_raw);
             let type_line = Types::from(type_line_raw);
-            let has_unique_face = face["image_uris"].is_array();
+            let has_unique_face = face["image_uris"].is_object();
             Some(CardInfo {
                 oracle_id,
                 name: name.into_boxed_str(),
@@ -509,50 +785,97 @@ impl SubCard {
                 has_unique_face,
             })
         }
-        let layout_str = json["layout"].as_str()?;
+        let malformed =
+            |field: &str| FetchError::Parse(format!("{uuid}: bad {field}").into_boxed_str());
+        let layout_str = json["layout"].as_str().ok_or_else(|| malformed("layout"))?;
         let layout = Layout::from(layout_str);
         let face_handles = MaybeHandles::Waiting;
         let mut back_handles = MaybeHandles::None;
-        let (front, back) = if json["card_faces"].is_null() {
-            let front = get_face(&json, &JsonValue::Null)?;
+        let part = |component: &'static str| {
+            json["all_parts"]
+                .members()
+                .filter(move |p| p["component"].as_str() == Some(component))
+                .filter_map(|p| Uuid::from_str(p["id"].as_str()?).ok())
+        };
+        let meld_result = part("meld_result").find(|id| *id != uuid);
+        let (front, mut back) = if json["card_faces"].is_null() {
+            let front = get_face(&json, &JsonValue::Null).ok_or_else(|| malformed("card"))?;
             (front, None)
         } else {
-            let faces = json["card_faces"].as_array()?;
-            let front = get_face(&json, &faces[0])?;
-            let back = get_face(&json, &faces[1])?;
-            if back.has_unique_face {
-                back_handles = MaybeHandles::Waiting;
-            }
-            (front, Some(Box::new(back)))
+            let faces = json["card_faces"]
+                .as_array()
+                .ok_or_else(|| malformed("card_faces"))?;
+            let front = faces
+                .first()
+                .and_then(|face| get_face(&json, face))
+                .ok_or_else(|| malformed("front face"))?;
+            let back = faces
+                .get(1)
+                .map(|face| get_face(&json, face).ok_or_else(|| malformed("back face")))
+                .transpose()?;
+            (front, back.map(Box::new))
         };
-        let set = json["set"].as_str().unwrap();
-        let cn = json["collector_number"].as_str().unwrap();
+        if let Some(meld) = meld_result {
+            let meld_json = fetch_json(&CARDS_THROTTLE, || {
+                config
+                    .client
+                    .get(format!("{}/cards/{meld}", config.api_url))
+            })
+            .await
+            .inspect_err(|e| warn!("{uuid}: meld result {meld}: {e}"))
+            .ok();
+            back = meld_json
+                .and_then(|meld_card| get_face(&meld_card, &JsonValue::Null))
+                .map(|face| {
+                    Box::new(CardInfo {
+                        has_unique_face: true,
+                        ..face
+                    })
+                });
+        }
+        if back.as_ref().is_some_and(|b| b.has_unique_face) {
+            back_handles = MaybeHandles::Waiting;
+        }
+        let set = json["set"].as_str().ok_or_else(|| malformed("set"))?;
+        let cn = json["collector_number"]
+            .as_str()
+            .ok_or_else(|| malformed("collector_number"))?;
         let set_cn = format!("{set}/{cn}").into_boxed_str();
+        let rarity = json["rarity"]
+            .as_str()
+            .and_then(Rarity::from_name)
+            .unwrap_or_default();
+        let mut legalities = Legalities::default();
+        for (name, status) in json["legalities"].entries() {
+            let Ok(format) = Format::try_from(name) else {
+                continue;
+            };
+            match status.as_str() {
+                Some("legal") => legalities.legal.insert(format),
+                Some("restricted") => legalities.restricted.insert(format),
+                Some("banned") => legalities.banned.insert(format),
+                _ => false,
+            };
+        }
         tokio::spawn(read_cards_check(
-            client,
+            config.clone(),
             set_cn.clone(),
             uuid,
+            meld_result,
             quality,
-            (&face_handles).into(),
             (&back_handles).into(),
         ));
-        let tokens = json["all_parts"]
-            .as_array()
-            .map(|v| {
-                v.iter()
-                    .filter(|p| p["component"].as_str() == Some("token"))
-                    .filter_map(|p| p["id"].as_str())
-                    .filter_map(|s| Uuid::from_str(s).ok())
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
+        let tokens: Vec<Uuid> = part("token").collect();
         let data = CardData {
             id: uuid,
             set_cn,
             tokens: tokens.into(),
+            meld_result,
             front,
             back,
             layout,
+            rarity,
+            legalities,
         };
         let cache = CardInCache {
             strong: Arc::new(data),
@@ -565,8 +888,10 @@ impl SubCard {
             back_handles: cache.back_handles.clone(),
             flipped: false,
         };
-        cache.write_files().await;
-        CACHE.lock().await.insert(cache);
-        Some(card)
+        let bytes = cache.write_files(&config.storage).await;
+        let set_cn = cache.strong.set_cn.clone();
+        config.cache.lock().await.insert(cache);
+        record_storage(&config, uuid, &set_cn, bytes).await;
+        Ok(card)
     }
 }
@@ -1,37 +1,69 @@
-const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 use crate::card::SubCard;
-use crate::scryfall::{IMAGES_IN_PROGRESS, IMAGES_TO_PROCESS, Quality, SLEEP_TIME};
+use crate::mock_scryfall::{MockCard, MockScryfall};
+use crate::scryfall::{
+    CardError, FetchError, IMAGES_IN_PROGRESS, IMAGES_TO_PROCESS, NameError, Quality, SLEEP_TIME,
+};
 use fdlimit::raise_fd_limit;
-use reqwest::Client;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 #[cfg(target_family = "wasm")]
 use tokio_with_wasm as tokio;
-use uuid::uuid;
+use uuid::{Uuid, uuid};
+const ELF: Uuid = uuid!("00000000-0000-4000-8000-0000000e1f00");
+const FOREST: Uuid = uuid!("00000000-0000-4000-8000-0000000f0000");
+const BOLT: Uuid = uuid!("00000000-0000-4000-8000-0000000b0000");
+const HELIX: Uuid = uuid!("00000000-0000-4000-8000-0000000c0000");
+const DELVER: Uuid = uuid!("00000000-0000-4000-8000-0000000d0000");
+fn mock(id: u128, oracle_id: Uuid, name: &'static str, cn: &'static str) -> MockCard {
+    MockCard {
+        id: Uuid::from_u128(id),
+        oracle_id,
+        name,
+        back: None,
+        set: "mck",
+        cn,
+    }
+}
+fn mock_cards() -> Vec<MockCard> {
+    vec![
+        mock(0xe1f01, ELF, "Llanowar Elves", "1"),
+        mock(0xf0001, FOREST, "Forest", "2"),
+        mock(0xf0002, FOREST, "Forest", "3"),
+        mock(0xf0003, FOREST, "Forest", "4"),
+        mock(0xb0001, BOLT, "Lightning Bolt", "5"),
+        mock(0xc0001, HELIX, "Lightning Helix", "6"),
+        MockCard {
+            back: Some("Insectile Aberration"),
+            ..mock(0xd0001, DELVER, "Delver of Secrets", "7")
+        },
+    ]
+}
+async fn wait_for_images(uuids: &[Uuid]) -> bool {
+    let tmr = Instant::now();
+    while tmr.elapsed() < Duration::from_secs(30) {
+        {
+            let mut images = IMAGES_TO_PROCESS.lock().await;
+            if uuids.iter().all(|uuid| images.contains_key(uuid)) {
+                let loaded = uuids.iter().all(|uuid| {
+                    images
+                        .remove(uuid)
+                        .is_some_and(|(front, _)| front.is_some())
+                });
+                return loaded;
+            }
+        }
+        sleep(SLEEP_TIME).await;
+    }
+    false
+}
 #[tokio::test(flavor = "multi_thread")]
 async fn test_list() {
     raise_fd_limit().unwrap();
-    let client = Client::builder().user_agent(USER_AGENT).build().unwrap();
-    let kiki_uuid = uuid!("0e6fc996-17ba-4090-bf82-0c2eba93a81e");
-    let reaper_uuid = uuid!("502740bf-0bff-4358-8996-1a27e5f0343f");
-    let tamiyo_uuid = uuid!("222a736e-d819-452d-aeda-eb848c4b2302");
-    let charred_uuid = uuid!("a128e6d1-b90f-45a1-b587-f8c29bd0ec8c");
-    let erayo_uuid = uuid!("0b61d772-2d8b-4acf-9dd2-b2e8b03538c8");
-    let aclazotz_uuid = uuid!("627c392c-4d18-4eb2-a4e8-c668f61f5487");
-    let bruce_uuid = uuid!("e0dbbdcf-84e1-494f-8b8c-0a094f603fa9");
-    let gisela_uuid = uuid!("04506bad-3856-4184-8dda-941ded60f41a");
+    let config = MockScryfall::new(mock_cards()).spawn();
+    let unique = [0xb0001, 0xc0001, 0xd0001].map(Uuid::from_u128);
     let tmr = Instant::now();
-    let uuids = [[
-        kiki_uuid,
-        reaper_uuid,
-        tamiyo_uuid,
-        charred_uuid,
-        erayo_uuid,
-        aclazotz_uuid,
-        bruce_uuid,
-        gisela_uuid,
-    ]; 128];
-    let list = SubCard::get_list(client, uuids.as_flattened(), Quality::Normal).await;
+    let uuids = [unique; 128];
+    let list = SubCard::get_list(config, uuids.as_flattened(), Quality::Normal).await;
     let time = tmr.elapsed().as_millis();
     let mut i = 0;
     for res in &list {
@@ -41,25 +73,28 @@ async fn test_list() {
         }
     }
     println!("{} {i} {}", list.len(), time);
+    assert_eq!(i, 0);
+    let delver = list
+        .iter()
+        .flatten()
+        .find(|c| c.data.id == unique[2])
+        .unwrap();
+    let back = delver.data.back.as_ref().unwrap();
+    assert_eq!(back.name.as_ref(), "Insectile Aberration");
+    assert!(back.has_unique_face);
     let tmr = Instant::now();
-    while IMAGES_IN_PROGRESS.lock().await.len() > 0 {
-        sleep(SLEEP_TIME).await;
-    }
+    assert!(wait_for_images(&unique).await);
     println!(
         "{} {}",
         tmr.elapsed().as_millis(),
-        IMAGES_TO_PROCESS.lock().await.len()
+        IMAGES_IN_PROGRESS.lock().await.len()
     );
-    IMAGES_TO_PROCESS.lock().await.clear();
-    IMAGES_IN_PROGRESS.lock().await.clear();
 }
 #[tokio::test(flavor = "multi_thread")]
 async fn test_prints() {
-    raise_fd_limit().unwrap();
-    let client = Client::builder().user_agent(USER_AGENT).build().unwrap();
-    let forest_uuid = uuid!("b34bb2dc-c1af-4d77-b0b3-a0fb342a5fc6");
+    let config = MockScryfall::new(mock_cards()).spawn();
     let tmr = Instant::now();
-    let vec = SubCard::get_prints(client, forest_uuid, Quality::Normal)
+    let vec = SubCard::get_prints(config.clone(), FOREST, Quality::Normal)
         .await
         .unwrap();
     let time = tmr.elapsed().as_millis();
@@ -71,15 +106,79 @@ async fn test_prints() {
         }
     }
     println!("{} {i} {}", vec.len(), time);
-    let tmr = Instant::now();
-    while IMAGES_IN_PROGRESS.lock().await.len() > 0 {
-        sleep(SLEEP_TIME).await;
+    assert_eq!((vec.len(), i), (3, 0));
+    let dead = uuid!("00000000-0000-4000-8000-00000000dead");
+    assert_eq!(
+        SubCard::get_prints(config, dead, Quality::Normal)
+            .await
+            .map(|v| v.len()),
+        Err(CardError {
+            id: dead,
+            error: FetchError::NotFound
+        })
+    );
+    let uuids = [0xf0001, 0xf0002, 0xf0003].map(Uuid::from_u128);
+    assert!(wait_for_images(&uuids).await);
+}
+#[tokio::test(flavor = "multi_thread")]
+async fn test_named() {
+    let config = MockScryfall::new(mock_cards()).spawn();
+    let card = SubCard::get_set_cn(config.clone(), "mck/1", Quality::Normal)
+        .await
+        .unwrap();
+    assert_eq!(card.data.front.name.as_ref(), "Llanowar Elves");
+    let card = SubCard::get_named(config.clone(), "lightning bolt", Quality::Normal)
+        .await
+        .unwrap();
+    assert_eq!(card.data.id, Uuid::from_u128(0xb0001));
+    match SubCard::get_named(config.clone(), "lightning", Quality::Normal).await {
+        Err(NameError::Ambiguous(names)) => {
+            assert_eq!(
+                names,
+                vec![Box::from("Lightning Bolt"), Box::from("Lightning Helix")]
+            );
+        }
+        res => panic!("{:?}", res.map(|c| c.data.id)),
     }
-    println!(
-        "{} {}",
-        tmr.elapsed().as_millis(),
-        IMAGES_TO_PROCESS.lock().await.len()
+    assert_eq!(
+        SubCard::get_named(config, "goblin guide", Quality::Normal)
+            .await
+            .map(|c| c.data.id),
+        Err(NameError::Fetch(CardError {
+            id: "goblin guide".into(),
+            error: FetchError::NotFound
+        }))
     );
-    IMAGES_TO_PROCESS.lock().await.clear();
-    IMAGES_IN_PROGRESS.lock().await.clear();
+    assert!(wait_for_images(&[Uuid::from_u128(0xe1f01)]).await);
+}
+#[tokio::test(flavor = "multi_thread")]
+async fn test_retry() {
+    let config = MockScryfall::new(mock_cards()).with_failures(2).spawn();
+    let vec = SubCard::get_prints(config, ELF, Quality::Normal)
+        .await
+        .unwrap();
+    assert_eq!(vec.len(), 1);
+    let config = MockScryfall::new(mock_cards())
+        .with_failures(usize::MAX)
+        .spawn();
+    let missing = Uuid::from_u128(0xe1f02);
+    assert_eq!(
+        SubCard::get(config, missing, Quality::Normal)
+            .await
+            .map(|c| c.data.id),
+        Err(CardError {
+            id: missing,
+            error: FetchError::Status(429)
+        })
+    );
+}
+#[test]
+fn test_retryable() {
+    assert!(FetchError::Status(429).is_retryable());
+    assert!(FetchError::Status(503).is_retryable());
+    assert!(!FetchError::Status(400).is_retryable());
+    assert!(!FetchError::Status(403).is_retryable());
+    assert!(!FetchError::Status(404).is_retryable());
+    assert!(FetchError::Network("reset".into()).is_retryable());
+    assert!(!FetchError::NotFound.is_retryable());
 }
@@ -0,0 +1,109 @@
+use crate::card::{CardData, MainType};
+use bevy::reflect::Reflect;
+use std::cmp::Ordering;
+const TYPE_ORDER: [MainType; 9] = [
+    MainType::Creature,
+    MainType::Planeswalker,
+    MainType::Battle,
+    MainType::Instant,
+    MainType::Sorcery,
+    MainType::Kindred,
+    MainType::Artifact,
+    MainType::Enchantment,
+    MainType::Land,
+];
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SortKey {
+    Name,
+    ManaValue,
+    Color,
+    Type,
+    Power,
+    SetCn,
+}
+impl SortKey {
+    pub const ALL: [Self; 6] = [
+        Self::Name,
+        Self::ManaValue,
+        Self::Color,
+        Self::Type,
+        Self::Power,
+        Self::SetCn,
+    ];
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::ManaValue => "mana value",
+            Self::Color => "color",
+            Self::Type => "type",
+            Self::Power => "power",
+            Self::SetCn => "set",
+        }
+    }
+    #[must_use]
+    pub fn compare(self, a: &CardData, b: &CardData) -> Ordering {
+        match self {
+            Self::Name => a.front.name.cmp(&b.front.name),
+            Self::ManaValue => a
+                .front
+                .mana_cost
+                .mana_value()
+                .total_cmp(&b.front.mana_cost.mana_value()),
+            Self::Color => color_key(a).cmp(&color_key(b)),
+            Self::Type => type_key(a).cmp(&type_key(b)),
+            Self::Power => match (a.front.power, b.front.power) {
+                (Some(a_power), Some(b_power)) => a_power.cmp(&b_power),
+                (a_power, b_power) => b_power.is_some().cmp(&a_power.is_some()),
+            },
+            Self::SetCn => set_cn_key(a).cmp(&set_cn_key(b)),
+        }
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct SortBy {
+    pub key: SortKey,
+    pub descending: bool,
+}
+impl SortBy {
+    #[must_use]
+    pub fn new(key: SortKey, descending: bool) -> Self {
+        Self { key, descending }
+    }
+    #[must_use]
+    pub fn compare(self, a: &CardData, b: &CardData) -> Ordering {
+        let ordering = self.key.compare(a, b);
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+#[must_use]
+pub fn compare(keys: &[SortBy], a: &CardData, b: &CardData) -> Ordering {
+    keys.iter()
+        .map(|key| key.compare(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+fn color_key(card: &CardData) -> (u8, usize, u8) {
+    let colors = card.front.colors.colors;
+    match colors.len() {
+        0 => (2, 0, 0),
+        1 => (0, 1, colors.as_u8()),
+        len => (1, len, colors.as_u8()),
+    }
+}
+fn type_key(card: &CardData) -> usize {
+    let types = card.front.type_line.main_type.types;
+    TYPE_ORDER
+        .iter()
+        .position(|ty| types.contains(*ty))
+        .unwrap_or(TYPE_ORDER.len())
+}
+fn set_cn_key(card: &CardData) -> (&str, u32, &str) {
+    let (set, cn) = card.set_cn.split_once('/').unwrap_or((&card.set_cn, ""));
+    let digits = cn.find(|c: char| !c.is_ascii_digit()).unwrap_or(cn.len());
+    (set, cn[..digits].parse().unwrap_or(u32::MAX), cn)
+}
@@ -0,0 +1,56 @@
+use crate::card::CardData;
+use crate::sort::{SortBy, SortKey, compare};
+use crate::test_cards::card;
+fn sorted(cards: &[CardData], keys: &[SortBy]) -> Vec<String> {
+    let mut refs: Vec<&CardData> = cards.iter().collect();
+    refs.sort_by(|a, b| compare(keys, a, b));
+    refs.iter()
+        .map(|card| card.front.name.to_string())
+        .collect()
+}
+#[test]
+fn test_sort_keys() {
+    let cards = [
+        card("Island", "", "Basic Land — Island", "c").with_set_cn("neo/10"),
+        card("Ox", "{1}{W}", "Creature — Ox", "w")
+            .with_set_cn("neo/2")
+            .with_stats(2, 2),
+        card("Bolt", "{R}", "Instant", "r").with_set_cn("m10/146"),
+        card("Duo", "{U}{R}", "Sorcery", "ur").with_set_cn("neo/2a"),
+        card("Golem", "{4}", "Artifact Creature — Golem", "c")
+            .with_set_cn("m10/210")
+            .with_stats(5, 5),
+    ];
+    let by = |key: SortKey| sorted(&cards, &[SortBy::new(key, false)]);
+    assert_eq!(by(SortKey::Name), ["Bolt", "Duo", "Golem", "Island", "Ox"]);
+    assert_eq!(
+        by(SortKey::ManaValue),
+        ["Island", "Bolt", "Ox", "Duo", "Golem"]
+    );
+    assert_eq!(by(SortKey::Color), ["Ox", "Bolt", "Duo", "Island", "Golem"]);
+    assert_eq!(by(SortKey::Type), ["Ox", "Golem", "Bolt", "Duo", "Island"]);
+    assert_eq!(by(SortKey::Power), ["Ox", "Golem", "Island", "Bolt", "Duo"]);
+    assert_eq!(by(SortKey::SetCn), ["Bolt", "Golem", "Ox", "Duo", "Island"]);
+    assert_eq!(
+        sorted(&cards, &[SortBy::new(SortKey::Name, true)]),
+        ["Ox", "Island", "Golem", "Duo", "Bolt"]
+    );
+}
+#[test]
+fn test_sort_multi_key() {
+    let cards = [
+        card("Cc", "{2}", "Artifact", "c").with_set_cn("a/1"),
+        card("Aa", "{2}", "Artifact", "c").with_set_cn("a/2"),
+        card("Bb", "{1}", "Artifact", "c").with_set_cn("a/3"),
+        card("Aa", "{1}", "Artifact", "c").with_set_cn("a/4"),
+    ];
+    let keys = [
+        SortBy::new(SortKey::ManaValue, true),
+        SortBy::new(SortKey::Name, false),
+    ];
+    assert_eq!(sorted(&cards, &keys), ["Aa", "Cc", "Aa", "Bb"]);
+    let mut stable: Vec<&CardData> = cards.iter().collect();
+    stable.sort_by(|a, b| compare(&[SortBy::new(SortKey::Name, false)], a, b));
+    let set_cns: Vec<&str> = stable.iter().map(|card| &*card.set_cn).collect();
+    assert_eq!(set_cns, ["a/2", "a/4", "a/3", "a/1"]);
+}
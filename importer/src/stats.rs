@@ -0,0 +1,99 @@
+use crate::card::{CardInfo, Color, MainType};
+use enumset::EnumSet;
+use std::collections::BTreeMap;
+pub const CURVE_LEN: usize = 8;
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DeckStats {
+    pub cards: u32,
+    pub lands: u32,
+    pub curve: [u32; CURVE_LEN],
+    pub pips: Vec<(Color, u32)>,
+    pub types: Vec<(MainType, u32)>,
+    pub copies: Vec<(Box<str>, u32)>,
+    pub total_mana_value: f32,
+}
+impl DeckStats {
+    #[must_use]
+    pub fn new<'a>(cards: impl IntoIterator<Item = &'a CardInfo>) -> Self {
+        let mut stats = Self {
+            pips: EnumSet::<Color>::all().iter().map(|c| (c, 0)).collect(),
+            types: EnumSet::<MainType>::all().iter().map(|t| (t, 0)).collect(),
+            ..Self::default()
+        };
+        let mut copies: BTreeMap<&str, u32> = BTreeMap::new();
+        for card in cards {
+            stats.cards += 1;
+            *copies.entry(&*card.name).or_default() += 1;
+            let types = card.type_line.main_type.types;
+            for (ty, count) in &mut stats.types {
+                *count += u32::from(types.contains(*ty));
+            }
+            for symbol in &card.mana_cost.symbols {
+                let colors = symbol.colors();
+                for (color, count) in &mut stats.pips {
+                    *count += u32::from(colors.contains(*color));
+                }
+            }
+            if types.contains(MainType::Land) {
+                stats.lands += 1;
+            } else {
+                let mana_value = card.mana_cost.mana_value();
+                stats.total_mana_value += mana_value;
+                stats.curve[(mana_value as usize).min(CURVE_LEN - 1)] += 1;
+            }
+        }
+        stats.types.retain(|(_, count)| *count != 0);
+        stats.copies = copies
+            .into_iter()
+            .map(|(name, count)| (name.into(), count))
+            .collect();
+        stats
+    }
+    #[must_use]
+    pub fn average_mana_value(&self) -> f32 {
+        let spells = self.cards - self.lands;
+        if spells == 0 {
+            0.0
+        } else {
+            self.total_mana_value / spells as f32
+        }
+    }
+    #[must_use]
+    pub fn land_odds(&self, lands: u32, turn: u32, on_play: bool) -> f64 {
+        at_least(self.cards, self.lands, cards_seen(turn, on_play), lands)
+    }
+    #[must_use]
+    pub fn copies(&self, name: &str) -> u32 {
+        self.copies
+            .iter()
+            .find(|(card, _)| **card == *name)
+            .map_or(0, |(_, count)| *count)
+    }
+    #[must_use]
+    pub fn card_odds(&self, name: &str, wanted: u32, turn: u32, on_play: bool) -> f64 {
+        at_least(
+            self.cards,
+            self.copies(name),
+            cards_seen(turn, on_play),
+            wanted,
+        )
+    }
+}
+#[must_use]
+pub fn cards_seen(turn: u32, on_play: bool) -> u32 {
+    6 + turn + u32::from(!on_play)
+}
+fn choose(n: u32, k: u32) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k.min(n - k)).fold(1.0, |acc, i| acc * f64::from(n - i) / f64::from(i + 1))
+}
+#[must_use]
+pub fn at_least(population: u32, successes: u32, draws: u32, wanted: u32) -> f64 {
+    let seen = draws.min(population);
+    let total = choose(population, seen);
+    (wanted..=seen.min(successes)).fold(0.0, |acc, k| {
+        acc + choose(successes, k) * choose(population - successes, seen - k) / total
+    })
+}
@@ -0,0 +1,72 @@
+use crate::card::{CardInfo, Color, MainType};
+use crate::stats::{DeckStats, at_least, cards_seen};
+use crate::test_cards::card;
+fn round(odds: f64) -> String {
+    format!("{odds:.4}")
+}
+#[test]
+fn test_stats() {
+    let cards = [
+        card("", "", "Basic Land — Mountain", "").front,
+        card("", "", "Basic Land — Island", "").front,
+        card("", "{R}", "Instant", "").front,
+        card("", "{1}{U/R}", "Instant", "").front,
+        card("", "{3}{R}{R}", "Artifact Creature — Golem", "").front,
+        card("", "{8}{U}", "Sorcery", "").front,
+    ];
+    let stats = DeckStats::new(&cards);
+    assert_eq!(stats.cards, 6);
+    assert_eq!(stats.lands, 2);
+    assert_eq!(stats.curve, [0, 1, 1, 0, 0, 1, 0, 1]);
+    assert_eq!(
+        stats.pips,
+        vec![
+            (Color::White, 0),
+            (Color::Blue, 2),
+            (Color::Black, 0),
+            (Color::Red, 4),
+            (Color::Green, 0),
+        ]
+    );
+    let count = |ty: MainType| {
+        stats
+            .types
+            .iter()
+            .find(|(t, _)| *t == ty)
+            .map(|(_, count)| *count)
+    };
+    assert_eq!(count(MainType::Land), Some(2));
+    assert_eq!(count(MainType::Instant), Some(2));
+    assert_eq!(count(MainType::Creature), Some(1));
+    assert_eq!(count(MainType::Enchantment), None);
+    assert_eq!(stats.average_mana_value().to_string(), "4.25");
+    assert_eq!(DeckStats::new(&[]).average_mana_value().to_string(), "0");
+}
+#[test]
+fn test_odds() {
+    assert_eq!(cards_seen(1, true), 7);
+    assert_eq!(cards_seen(1, false), 8);
+    assert_eq!(round(at_least(60, 4, 7, 1)), "0.3995");
+    assert_eq!(round(at_least(60, 24, 7, 2)), "0.8573");
+    assert_eq!(round(at_least(60, 0, 7, 1)), "0.0000");
+    assert_eq!(round(at_least(60, 24, 7, 0)), "1.0000");
+    assert_eq!(round(at_least(5, 5, 10, 5)), "1.0000");
+    let cards: Vec<CardInfo> = (0..60)
+        .map(|i| {
+            if i < 24 {
+                card("Island", "", "Land", "").front
+            } else if i < 28 {
+                card("Opt", "{U}", "Instant", "").front
+            } else {
+                card("Filler", "{1}", "Instant", "").front
+            }
+        })
+        .collect();
+    let stats = DeckStats::new(&cards);
+    assert_eq!(round(stats.land_odds(2, 1, true)), "0.8573");
+    assert_eq!(stats.copies("Opt"), 4);
+    assert_eq!(round(stats.card_odds("Opt", 1, 1, true)), "0.3995");
+    assert_eq!(round(stats.card_odds("Island", 2, 1, true)), "0.8573");
+    assert_eq!(round(stats.card_odds("Missing", 1, 1, true)), "0.0000");
+    assert!(stats.land_odds(3, 3, false) > stats.land_odds(3, 3, true));
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, LazyLock, Mutex};
+#[cfg(not(target_family = "wasm"))]
+use tokio::task::spawn_blocking;
+pub const CACHE_FOLDER: &str = "cache";
+pub static STORAGE: LazyLock<Arc<dyn Storage>> = LazyLock::new(default_storage);
+pub trait Storage: Send + Sync {
+    fn read(&self, key: &str) -> Option<Vec<u8>>;
+    fn write(&self, key: &str, bytes: &[u8]) -> Option<u64>;
+    fn remove(&self, prefix: &str);
+    fn clear(&self);
+    fn keys(&self) -> Vec<(Box<str>, u64)>;
+    fn size(&self, prefix: &str) -> u64 {
+        self.keys()
+            .into_iter()
+            .filter(|(key, _)| in_folder(key, prefix))
+            .map(|(_, bytes)| bytes)
+            .sum()
+    }
+}
+#[cfg(not(target_family = "wasm"))]
+pub async fn blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> Option<T> {
+    spawn_blocking(f).await.ok()
+}
+#[cfg(target_family = "wasm")]
+pub async fn blocking<T>(f: impl FnOnce() -> T) -> Option<T> {
+    Some(f())
+}
+fn in_folder(key: &str, prefix: &str) -> bool {
+    key.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+#[cfg(not(target_family = "wasm"))]
+fn default_storage() -> Arc<dyn Storage> {
+    match bevy::platform::dirs::preferences_dir() {
+        Some(dir) => Arc::new(FsStorage::new(
+            dir.join(crate::app_name()).join(CACHE_FOLDER),
+        )),
+        None => Arc::new(MemoryStorage::default()),
+    }
+}
+#[cfg(target_family = "wasm")]
+fn default_storage() -> Arc<dyn Storage> {
+    Arc::new(BrowserStorage::new(format!(
+        "{}/{CACHE_FOLDER}/",
+        crate::app_name()
+    )))
+}
+#[derive(Default)]
+pub struct MemoryStorage {
+    entries: Mutex<BTreeMap<Box<str>, Box<[u8]>>>,
+}
+impl Storage for MemoryStorage {
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        self.entries.lock().ok()?.get(key).map(|b| b.to_vec())
+    }
+    fn write(&self, key: &str, bytes: &[u8]) -> Option<u64> {
+        let replaced = self.entries.lock().ok()?.insert(key.into(), bytes.into());
+        Some(replaced.map_or(0, |old| old.len() as u64))
+    }
+    fn remove(&self, prefix: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|key, _| !in_folder(key, prefix));
+        }
+    }
+    fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+    fn keys(&self) -> Vec<(Box<str>, u64)> {
+        self.entries.lock().map_or_else(
+            |_| Vec::new(),
+            |entries| {
+                entries
+                    .iter()
+                    .map(|(key, bytes)| (key.clone(), bytes.len() as u64))
+                    .collect()
+            },
+        )
+    }
+}
+#[cfg(not(target_family = "wasm"))]
+pub struct FsStorage {
+    root: std::path::PathBuf,
+}
+#[cfg(not(target_family = "wasm"))]
+impl FsStorage {
+    pub fn new(root: std::path::PathBuf) -> Self {
+        let _ = std::fs::create_dir_all(&root);
+        Self { root }
+    }
+    fn path(&self, key: &str) -> Option<std::path::PathBuf> {
+        let relative = std::path::Path::new(key);
+        relative
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)))
+            .then(|| self.root.join(relative))
+    }
+    fn walk(path: &std::path::Path, prefix: &str, keys: &mut Vec<(Box<str>, u64)>) {
+        let Ok(dir) = std::fs::read_dir(path) else {
+            return;
+        };
+        for entry in dir.filter_map(Result::ok) {
+            let Some(name) = entry.file_name().to_str().map(str::to_owned) else {
+                continue;
+            };
+            let key = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+            match entry.metadata() {
+                Ok(meta) if meta.is_dir() => Self::walk(&entry.path(), &key, keys),
+                Ok(meta) => keys.push((key.into(), meta.len())),
+                Err(_) => {}
+            }
+        }
+    }
+}
+#[cfg(not(target_family = "wasm"))]
+impl Storage for FsStorage {
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        std::fs::read(self.path(key)?).ok()
+    }
+    fn write(&self, key: &str, bytes: &[u8]) -> Option<u64> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let replaced = std::fs::metadata(&path).map_or(0, |meta| meta.len());
+        std::fs::write(path, bytes).ok()?;
+        Some(replaced)
+    }
+    fn remove(&self, prefix: &str) {
+        let Some(path) = self.path(prefix) else {
+            return;
+        };
+        if path.is_dir() {
+            let _ = std::fs::remove_dir_all(path);
+        } else {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+    fn clear(&self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+        let _ = std::fs::create_dir_all(&self.root);
+    }
+    fn keys(&self) -> Vec<(Box<str>, u64)> {
+        let mut keys = Vec::new();
+        Self::walk(&self.root, "", &mut keys);
+        keys
+    }
+    fn size(&self, prefix: &str) -> u64 {
+        let Some(path) = self.path(prefix) else {
+            return 0;
+        };
+        let mut keys = Vec::new();
+        Self::walk(&path, "", &mut keys);
+        keys.into_iter().map(|(_, bytes)| bytes).sum()
+    }
+}
+#[cfg(target_family = "wasm")]
+pub struct BrowserStorage {
+    namespace: String,
+    index: Mutex<BTreeMap<Box<str>, u64>>,
+}
+#[cfg(target_family = "wasm")]
+impl BrowserStorage {
+    pub fn new(namespace: String) -> Self {
+        let index = Self::local()
+            .map(|local| {
+                (0..local.length().unwrap_or_default())
+                    .filter_map(|i| local.key(i).ok()?)
+                    .filter_map(|key| {
+                        let len = local.get_item(&key).ok()??.len();
+                        let key = key.strip_prefix(&namespace)?;
+                        Some((key.into(), (len / 4 * 3) as u64))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            namespace,
+            index: Mutex::new(index),
+        }
+    }
+    fn local() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+}
+#[cfg(target_family = "wasm")]
+impl Storage for BrowserStorage {
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        let text = Self::local()?
+            .get_item(&format!("{}{key}", self.namespace))
+            .ok()??;
+        base64_decode(&text)
+    }
+    fn write(&self, key: &str, bytes: &[u8]) -> Option<u64> {
+        let local = Self::local()?;
+        let mut index = self.index.lock().ok()?;
+        if let Err(e) = local.set_item(&format!("{}{key}", self.namespace), &base64_encode(bytes)) {
+            bevy::log::warn!("failed to store {key}: {e:?}");
+            return None;
+        }
+        Some(
+            index
+                .insert(key.into(), bytes.len() as u64)
+                .unwrap_or_default(),
+        )
+    }
+    fn remove(&self, prefix: &str) {
+        let (Some(local), Ok(mut index)) = (Self::local(), self.index.lock()) else {
+            return;
+        };
+        index.retain(|key, _| {
+            let remove = in_folder(key, prefix);
+            if remove {
+                let _ = local.remove_item(&format!("{}{key}", self.namespace));
+            }
+            !remove
+        });
+    }
+    fn clear(&self) {
+        let (Some(local), Ok(mut index)) = (Self::local(), self.index.lock()) else {
+            return;
+        };
+        for key in index.keys() {
+            let _ = local.remove_item(&format!("{}{key}", self.namespace));
+        }
+        index.clear();
+    }
+    fn keys(&self) -> Vec<(Box<str>, u64)> {
+        self.index.lock().map_or_else(
+            |_| Vec::new(),
+            |index| {
+                index
+                    .iter()
+                    .map(|(key, bytes)| (key.clone(), *bytes))
+                    .collect()
+            },
+        )
+    }
+}
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(char::from(BASE64[((n >> (18 - 6 * i)) & 63) as usize]));
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+fn base64_digit(c: u8) -> Option<u32> {
+    let digit = match c {
+        b'A'..=b'Z' => c - b'A',
+        b'a'..=b'z' => c - b'a' + 26,
+        b'0'..=b'9' => c - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+    Some(u32::from(digit))
+}
+pub fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let digits = text.trim_end_matches('=').as_bytes();
+    let mut bytes = Vec::with_capacity(digits.len() / 4 * 3);
+    for chunk in digits.chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            n |= base64_digit(c)? << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,54 @@
+use crate::storage::{MemoryStorage, Storage as _, base64_decode, base64_encode};
+#[test]
+fn test_memory_storage() {
+    let storage = MemoryStorage::default();
+    assert_eq!(storage.write("mck/1_a/card.data", &[1, 2, 3]), Some(0));
+    assert_eq!(storage.write("mck/1_a/front.png", &[4; 10]), Some(0));
+    assert_eq!(storage.write("mck/1_ab/card.data", &[5; 2]), Some(0));
+    assert_eq!(storage.write("mck/1_ab/card.data", &[5; 4]), Some(2));
+    assert_eq!(storage.write("index.data", &[6]), Some(0));
+    assert_eq!(storage.read("mck/1_a/card.data"), Some(vec![1, 2, 3]));
+    assert_eq!(storage.read("mck/1_a/back.png"), None);
+    assert_eq!(storage.size("mck/1_a"), 13);
+    assert_eq!(storage.size("mck/1_ab"), 4);
+    storage.remove("mck/1_a");
+    assert_eq!(
+        storage.keys(),
+        vec![
+            (Box::from("index.data"), 1),
+            (Box::from("mck/1_ab/card.data"), 4)
+        ]
+    );
+    storage.clear();
+    assert!(storage.keys().is_empty());
+}
+#[test]
+fn test_base64() {
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    assert_eq!(base64_decode("Zm9vYg=="), Some(b"foob".to_vec()));
+    assert_eq!(base64_decode("Zm9vYmE="), Some(b"fooba".to_vec()));
+    assert_eq!(base64_decode("Zm9v!"), None);
+    assert_eq!(base64_decode("Z"), None);
+    let bytes: Vec<u8> = (0..=255).collect();
+    assert_eq!(base64_decode(&base64_encode(&bytes)), Some(bytes));
+}
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn test_fs_storage_keys() {
+    use crate::storage::FsStorage;
+    let root = std::env::temp_dir().join(format!("rmtg_storage_{}", std::process::id()));
+    let storage = FsStorage::new(root.join("cache"));
+    assert_eq!(storage.write("mck/1_a/card.data", &[1, 2, 3]), Some(0));
+    assert_eq!(storage.read("mck/1_a/card.data"), Some(vec![1, 2, 3]));
+    assert_eq!(storage.write("custom/../../escape_a/card.data", &[1]), None);
+    assert_eq!(storage.write("/tmp/escape_a/card.data", &[1]), None);
+    assert_eq!(storage.read("../cache/mck/1_a/card.data"), None);
+    assert!(!root.join("escape_a").exists());
+    storage.remove("..");
+    assert_eq!(storage.keys().len(), 1);
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,57 @@
+use crate::card::{CardData, CardInfo, Colors, Cost, Legalities, Types};
+use enumset::EnumSet;
+use uuid::Uuid;
+#[must_use]
+pub fn card(name: &str, cost: &str, type_line: &str, colors: &str) -> CardData {
+    let colors = Colors::try_from(colors).unwrap();
+    CardData {
+        front: CardInfo {
+            name: name.into(),
+            mana_cost: Cost::from(cost),
+            type_line: Types::from(type_line),
+            colors,
+            color_identity: colors,
+            ..CardInfo::default()
+        },
+        ..CardData::default()
+    }
+}
+impl CardData {
+    #[must_use]
+    pub fn with_id(mut self, id: u128) -> Self {
+        self.id = Uuid::from_u128(id);
+        self
+    }
+    #[must_use]
+    pub fn with_set_cn(mut self, set_cn: &str) -> Self {
+        self.set_cn = set_cn.into();
+        self
+    }
+    #[must_use]
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.front.oracle_text = text.into();
+        self
+    }
+    #[must_use]
+    pub fn with_stats(mut self, power: u8, toughness: u8) -> Self {
+        self.front.power = Some(power);
+        self.front.toughness = Some(toughness);
+        self
+    }
+    #[must_use]
+    pub fn with_back(mut self, name: &str) -> Self {
+        self.back = Some(Box::new(CardInfo {
+            name: name.into(),
+            ..CardInfo::default()
+        }));
+        self
+    }
+    #[must_use]
+    pub fn with_legal(mut self) -> Self {
+        self.legalities = Legalities {
+            legal: EnumSet::all(),
+            ..Legalities::default()
+        };
+        self
+    }
+}
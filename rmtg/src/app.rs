@@ -1,4 +1,5 @@
 use crate::camera::{camera_rotation, camera_translation};
+use crate::clock::{Clocks, do_clock, follow_turn, tick_clocks, update_clock_texts};
 use crate::drag::drag;
 use crate::events::add_events;
 use crate::events::clipboard::poll_clipboards;
@@ -9,15 +10,28 @@ use crate::events::pile_merge::{DelayPileMerge, delayed_pile_merge};
 use crate::events::roll::{do_roll, update_rolling};
 use crate::events::scale::update_scale;
 use crate::events::scroll::{Scroll, scroll, send_scroll_events};
+use crate::events::spread::{do_gather, do_spread};
 use crate::focus::{Menu, update_focus};
 use crate::keybinds::{Keybind, KeybindsList, update_keybinds};
+use crate::life::{Trackers, do_life, update_life_counters};
 use crate::mat::create_mats;
 use crate::net::{Msg, Peers, net_update, receive_message};
-use crate::pile::register_cards;
+#[cfg(not(target_family = "wasm"))]
+use crate::paste::drop_custom_files;
+use crate::pile::{prioritize_visible, register_cards};
+use crate::profile::{Profiles, update_mat_colors, update_profile};
+use crate::settings::{Settings, apply_keybinds, apply_settings};
 use crate::spatial::{Cursor, update_cursor};
 use crate::startup::{spawn_objects, startup};
-use crate::ui::chat::text_submission;
-use crate::ui::esc_menu::{button_system, toggle_esc_menu};
+use crate::turn::{Turn, do_turn, update_turn};
+use crate::ui::chat::{text_submission, update_chat};
+use crate::ui::esc_menu::{button_system, toggle_esc_menu, update_setting_labels};
+use crate::ui::keybinds::{KeybindCapture, capture_keybind, update_keybind_labels};
+use crate::ui::life::update_life_hud;
+use crate::ui::mulligan::do_mulligan;
+use crate::ui::printings::{do_printings, update_printing_thumbnails};
+use crate::ui::sort::do_sort;
+use crate::ui::stats::do_stats;
 use crate::{APP_NAME, FONT, PHYSICS_SCALE, USER_AGENT};
 use avian3d::PhysicsPlugins;
 use bevy::DefaultPlugins;
@@ -38,6 +52,7 @@ use bevy::window::{Window, WindowPlugin};
 use bevy_framepace::FramepacePlugin;
 use bevy_p2p::plugin::P2PPlugin;
 use bevy_rich_text3d::{LoadFonts, Text3dPlugin};
+use importer::config::Config;
 use importer::scryfall::CACHE;
 #[must_use]
 pub fn app_run() -> AppExit {
@@ -98,6 +113,7 @@ pub fn app_run() -> AppExit {
     );
     app.add_plugins(PhysicsPlugins::default().with_length_unit(PHYSICS_SCALE));
     app.add_plugins(SettingsPlugin::new(APP_NAME));
+    app.init_resource::<Settings>();
     app.add_plugins(P2PPlugin::<Msg>::new(
         #[cfg(feature = "steam")]
         crate::STEAM_APP_ID,
@@ -124,7 +140,12 @@ pub fn app_run() -> AppExit {
     });
     app.init_resource::<Menu>();
     app.init_resource::<KeybindsList>();
+    app.init_resource::<KeybindCapture>();
     app.init_resource::<Peers>();
+    app.init_resource::<Trackers>();
+    app.init_resource::<Profiles>();
+    app.init_resource::<Turn>();
+    app.init_resource::<Clocks>();
     app.init_resource::<Client>();
     app.init_resource::<Cursor>();
     app.init_resource::<ButtonInput<Keybind>>();
@@ -150,6 +171,15 @@ pub fn app_run() -> AppExit {
                 (
                     (do_roll, update_rolling).chain(),
                     do_delete,
+                    do_printings,
+                    do_stats,
+                    do_mulligan,
+                    do_life,
+                    do_turn,
+                    do_clock,
+                    do_spread,
+                    do_gather,
+                    do_sort,
                     drag,
                     update_clone,
                     update_scale,
@@ -158,31 +188,44 @@ pub fn app_run() -> AppExit {
                 .chain(),
             text_submission,
             send_scroll_events,
+            update_chat,
+            (apply_settings, update_setting_labels),
+            (capture_keybind, apply_keybinds, update_keybind_labels).chain(),
+            (update_profile, update_mat_colors).chain(),
+            (update_turn, update_life_counters, update_life_hud),
+            (follow_turn, tick_clocks, update_clock_texts).chain(),
         )
             .chain(),
     );
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(Update, drop_custom_files);
     app.add_systems(PostUpdate, scroll.after(UiSystems::Layout));
     app.add_systems(
         FixedUpdate,
         (
             (net_update, receive_message).chain(),
             poll_clipboards,
-            register_cards,
+            (prioritize_visible, register_cards).chain(),
+            update_printing_thumbnails,
         ),
     );
-    app.run()
+    let exit = app.run();
+    CACHE.blocking_lock().save_index(true);
+    exit
 }
 #[derive(Resource)]
 pub struct Client {
-    pub client: importer::reqwest::Client,
+    pub config: Config,
 }
 impl Default for Client {
     fn default() -> Self {
         Self {
-            client: importer::reqwest::Client::builder()
-                .user_agent(USER_AGENT)
-                .build()
-                .unwrap(),
+            config: Config::new(
+                importer::reqwest::Client::builder()
+                    .user_agent(USER_AGENT)
+                    .build()
+                    .unwrap(),
+            ),
         }
     }
 }
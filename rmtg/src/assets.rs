@@ -2,7 +2,8 @@
 use crate::shapes::deck_outline::DeckOutline;
 use crate::shapes::{NewShape as _, OUTLINE_COLOR, OUTLINE_DEPTH_BIAS, Shape, ShapeOutline as _};
 use crate::{
-    CARD_HEIGHT, CARD_STOCK_COLOR, CARD_STOCK_INBETWEEN_COLOR, CARD_THICKNESS, CARD_WIDTH, PLAYER,
+    CARD_HEIGHT, CARD_STOCK_COLOR, CARD_STOCK_INBETWEEN_COLOR, CARD_THICKNESS, CARD_WIDTH,
+    ORACLE_CARD_COLOR, PLAYER,
 };
 use avian3d::parry::glamx::{Quat, Vec3};
 use bevy::asset::{Assets, Handle};
@@ -48,6 +49,7 @@ pub struct CardBase {
     pub back_image: Handle<Image>,
     pub color: Handle<StandardMaterial>,
     pub inbetween_color: Handle<StandardMaterial>,
+    pub oracle: Handle<StandardMaterial>,
 }
 impl ShapeMeshes {
     pub fn new(meshes: &mut Assets<Mesh>, materials: &mut Assets<StandardMaterial>) -> Self {
@@ -106,6 +108,11 @@ impl CardBase {
             unlit: true,
             ..StandardMaterial::default()
         });
+        let oracle = materials.add(StandardMaterial {
+            base_color: ORACLE_CARD_COLOR,
+            unlit: true,
+            ..StandardMaterial::default()
+        });
         Self {
             stock,
             side: meshes.add(generate_side_mesh()),
@@ -114,6 +121,7 @@ impl CardBase {
             back_image,
             color,
             inbetween_color,
+            oracle,
         }
     }
 }
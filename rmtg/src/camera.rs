@@ -1,6 +1,7 @@
 use crate::focus::{Focus, Menu};
 use crate::keybinds::Keybind;
 use crate::net::{Peer, Peers};
+use crate::settings::Settings;
 use crate::spatial::Spatial;
 use crate::{CARD_HEIGHT, CARD_THICKNESS, MAT_WIDTH, START_Y, W};
 use bevy::camera::Camera3d;
@@ -26,6 +27,7 @@ pub fn camera_translation(
     focus: Focus,
     peers: Res<Peers>,
     time: Res<Time>,
+    settings: Res<Settings>,
     mut spatial: ParamSet<(
         Spatial,
         Single<(&mut Transform, &mut CameraVelocity), With<Camera3d>>,
@@ -40,7 +42,9 @@ pub fn camera_translation(
     else {
         return;
     };
-    let scale = MAT_WIDTH * time.delta_secs() * ray_time.max(CARD_HEIGHT) / W * 2.0;
+    let scale = MAT_WIDTH * time.delta_secs() * ray_time.max(CARD_HEIGHT) / W
+        * 2.0
+        * settings.camera.move_scale();
     let fast_scale = scale * 2.0;
     let mut apply = |keybind: Keybind, fun: fn(&Transform) -> Dir3, scale: f32| {
         if keybinds.pressed(keybind) {
@@ -71,7 +75,8 @@ pub fn camera_translation(
             / 1024.0
             * ray_time.max(CARD_HEIGHT)
             / W
-            * 2.0;
+            * 2.0
+            * settings.camera.zoom_scale();
         if mouse_motion.unit == MouseScrollUnit::Line {
             translate *= MouseScrollUnit::SCROLL_UNIT_CONVERSION_FACTOR;
         }
@@ -109,6 +114,7 @@ pub fn default_cam_pos(n: Peer) -> Transform {
 pub fn camera_rotation(
     keybinds: Res<ButtonInput<Keybind>>,
     mouse_motion: Res<AccumulatedMouseMotion>,
+    settings: Res<Settings>,
     mut spatial: ParamSet<(Spatial, Single<&mut Transform, With<Camera3d>>)>,
 ) {
     if keybinds.pressed(Keybind::Rotate) && mouse_motion.delta != Vec2::ZERO {
@@ -116,8 +122,9 @@ pub fn camera_rotation(
             return;
         };
         let mut camera = spatial.p1();
-        let delta_yaw = -mouse_motion.delta.x * 0.001;
-        let delta_pitch = -mouse_motion.delta.y * 0.001;
+        let sensitivity = settings.camera.rotate_scale();
+        let delta_yaw = -mouse_motion.delta.x * sensitivity;
+        let delta_pitch = -mouse_motion.delta.y * sensitivity;
         let (yaw, pitch, roll) = camera.rotation.to_euler(EulerRot::YXZ);
         let new_yaw = yaw + delta_yaw;
         let new_pitch = (pitch + delta_pitch)
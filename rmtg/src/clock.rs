@@ -0,0 +1,273 @@
+use crate::assets::AssetManager;
+use crate::events::scroll::Scroll;
+use crate::keybinds::Keybind;
+use crate::life::SEATS;
+use crate::net::{Msg, Peers};
+use crate::settings::Settings;
+use crate::turn::{Turn, next_seat};
+use crate::ui::chat::{TextChat, text_node};
+use crate::{CARD_HEIGHT, FONT_SIZE, WORLD_FONT_SIZE};
+use bevy::color::Srgba;
+use bevy::input::ButtonInput;
+use bevy::math::Vec2;
+use bevy::mesh::Mesh3d;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Component, Res, ResMut, Resource, Text, Visibility};
+use bevy::text::{FontSize, TextFont};
+use bevy::time::Time;
+use bevy::ui::{Node, PositionType, Val};
+use bevy::ui_widgets::Activate;
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::lifecycle::Add;
+use bevy_ecs::message::MessageWriter;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Local, Query, Single};
+use bevy_p2p::bitcode::{self, Decode, Encode};
+use bevy_p2p::message::Net;
+use bevy_rich_text3d::{Text3d, Text3dStyling, TextAnchor};
+const CLOCK_SIZE: f32 = CARD_HEIGHT / 5.0;
+const WARNING: f32 = 60.0;
+const ROUND_WARNING: f32 = 300.0;
+#[derive(Resource, Clone, Debug, PartialEq, Encode, Decode)]
+pub struct Clocks {
+    pub remaining: Option<[f32; SEATS]>,
+    pub round: Option<f32>,
+    pub running: usize,
+    pub paused: bool,
+    pub increment: u32,
+}
+impl Default for Clocks {
+    fn default() -> Self {
+        Self {
+            remaining: None,
+            round: None,
+            running: 0,
+            paused: true,
+            increment: 0,
+        }
+    }
+}
+impl Clocks {
+    #[must_use]
+    pub fn new(settings: &Settings, running: usize) -> Self {
+        let minutes = |m: u32| (m != 0).then_some(m as f32 * 60.0);
+        Self {
+            remaining: minutes(settings.clock_minutes).map(|secs| [secs; SEATS]),
+            round: minutes(settings.round_minutes),
+            running,
+            paused: false,
+            increment: settings.clock_increment,
+        }
+    }
+    fn pass(&mut self, seat: usize) {
+        if seat == self.running {
+            return;
+        }
+        if let Some(time) = self
+            .remaining
+            .as_mut()
+            .and_then(|remaining| remaining.get_mut(self.running))
+            && *time > 0.0
+        {
+            *time += self.increment as f32;
+        }
+        self.running = seat;
+    }
+}
+#[must_use]
+pub fn format_time(secs: f32) -> String {
+    let total = secs.max(0.0).ceil() as u32;
+    format!("{}:{:02}", total / 60, total % 60)
+}
+#[derive(Component, Clone, Copy)]
+pub struct ClockText {
+    pub seat: usize,
+}
+impl ClockText {
+    #[must_use]
+    pub fn new(seat: usize) -> Self {
+        Self { seat }
+    }
+    fn label(self, clocks: &Clocks) -> String {
+        let Some(remaining) = clocks.remaining else {
+            return String::new();
+        };
+        let time = format_time(remaining[self.seat]);
+        if clocks.running == self.seat && !clocks.paused {
+            format!("> {time}")
+        } else {
+            time
+        }
+    }
+}
+fn styling(secs: Option<f32>) -> Text3dStyling {
+    Text3dStyling {
+        size: WORLD_FONT_SIZE,
+        anchor: TextAnchor::CENTER,
+        color: if secs.is_some_and(|left| left <= WARNING) {
+            Srgba::RED
+        } else {
+            Srgba::WHITE
+        },
+        world_scale: Some(Vec2::splat(CLOCK_SIZE)),
+        ..Text3dStyling::default()
+    }
+}
+#[derive(Component)]
+pub struct RoundClock;
+#[must_use]
+pub fn round_clock_bundle() -> impl Bundle {
+    (
+        Node {
+            left: Val::Percent(45.0),
+            top: Val::Percent(0.0),
+            position_type: PositionType::Absolute,
+            ..Node::default()
+        },
+        RoundClock,
+        Visibility::Visible,
+        Text::default(),
+        TextFont {
+            font_size: FontSize::Px(FONT_SIZE * 1.5),
+            ..TextFont::default()
+        },
+    )
+}
+pub fn on_clock_text_added(
+    on: On<Add, ClockText>,
+    texts: Query<&ClockText>,
+    clocks: Res<Clocks>,
+    asset: AssetManager,
+    mut commands: Commands,
+) {
+    let text = texts.get(on.entity).unwrap();
+    commands.entity(on.entity).insert((
+        Text3d::new(text.label(&clocks)),
+        Mesh3d::default(),
+        MeshMaterial3d(asset.text_mesh.mesh.clone()),
+        styling(None),
+    ));
+}
+pub fn do_clock(
+    keybinds: Res<ButtonInput<Keybind>>,
+    peers: Res<Peers>,
+    mut clocks: ResMut<Clocks>,
+    net: Net<Msg>,
+) {
+    let old = clocks.clone();
+    if keybinds.just_pressed(Keybind::PauseClock) {
+        clocks.paused = !clocks.paused;
+    } else if keybinds.just_pressed(Keybind::PassClock) {
+        let seat = next_seat(&peers, clocks.running);
+        clocks.pass(seat);
+    }
+    if *clocks != old {
+        net.broadcast(Msg::Clocks(clocks.clone()));
+    }
+}
+pub fn follow_turn(turn: Res<Turn>, mut clocks: ResMut<Clocks>) {
+    if turn.is_changed() && !turn.is_added() && clocks.running != turn.active {
+        clocks.pass(turn.active);
+    }
+}
+fn crossed(old: f32, new: f32, limit: f32) -> bool {
+    old > limit && new <= limit
+}
+fn warning(seat: Option<usize>, secs: f32) -> String {
+    let who = seat.map_or_else(|| "the round".to_owned(), |seat| format!("P{}", seat + 1));
+    if secs <= 0.0 {
+        format!("{who} is out of time")
+    } else {
+        format!("{who} has {} left", format_time(secs))
+    }
+}
+pub fn tick_clocks(
+    time: Res<Time>,
+    peers: Res<Peers>,
+    mut clocks: ResMut<Clocks>,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+    net: Net<Msg>,
+    mut commands: Commands,
+) {
+    if clocks.paused {
+        return;
+    }
+    let delta = time.delta_secs();
+    let running = clocks.running;
+    let mut warnings = Vec::new();
+    let mut second = false;
+    if let Some(time) = clocks
+        .remaining
+        .as_mut()
+        .and_then(|remaining| remaining.get_mut(running))
+    {
+        let old = *time;
+        let new = (old - delta).max(0.0);
+        *time = new;
+        second |= old.ceil() as u32 != new.ceil() as u32;
+        if crossed(old, new, WARNING) || crossed(old, new, 0.0) {
+            warnings.push(warning(Some(running), new));
+        }
+    }
+    if let Some(round) = &mut clocks.round {
+        let old = *round;
+        let new = (old - delta).max(0.0);
+        *round = new;
+        second |= old.ceil() as u32 != new.ceil() as u32;
+        if crossed(old, new, ROUND_WARNING) || crossed(old, new, 0.0) {
+            warnings.push(warning(None, new));
+        }
+    }
+    if second && peers.my_id.unwrap_or_default().id == running {
+        net.broadcast(Msg::Clocks(clocks.clone()));
+    }
+    if !warnings.is_empty() {
+        commands.entity(*text_chat).with_children(|parent| {
+            for line in warnings {
+                parent.spawn(text_node(line));
+            }
+        });
+        msgs.write(Scroll::down(*text_chat));
+    }
+}
+pub fn update_clock_texts(
+    clocks: Res<Clocks>,
+    texts: Query<(Entity, &ClockText)>,
+    mut round_text: Single<&mut Text, With<RoundClock>>,
+    mut last: Local<[String; SEATS]>,
+    mut commands: Commands,
+) {
+    if !clocks.is_changed() {
+        return;
+    }
+    for (entity, text) in texts {
+        let label = text.label(&clocks);
+        if last[text.seat] != label {
+            let secs = clocks.remaining.map(|remaining| remaining[text.seat]);
+            commands
+                .entity(entity)
+                .insert((Text3d::new(label.clone()), styling(secs)));
+            last[text.seat] = label;
+        }
+    }
+    let round = clocks
+        .round
+        .map(|round| format!("round {}", format_time(round)))
+        .unwrap_or_default();
+    if round_text.0 != round {
+        round_text.0 = round;
+    }
+}
+pub fn on_start_clocks(
+    _: On<Activate>,
+    settings: Res<Settings>,
+    turn: Res<Turn>,
+    mut clocks: ResMut<Clocks>,
+    net: Net<Msg>,
+) {
+    *clocks = Clocks::new(&settings, turn.active);
+    net.broadcast(Msg::Clocks(clocks.clone()));
+}
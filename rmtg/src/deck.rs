@@ -0,0 +1,132 @@
+use crate::CARD_WIDTH;
+use crate::app::Client;
+use crate::events::move_up::MoveUp;
+use crate::events::scroll::Scroll;
+use crate::paste::preferred_art;
+use crate::pile::Pile;
+use crate::settings::Settings;
+use crate::spatial::Spatial;
+use crate::ui::chat::{TextChat, text_node};
+use bevy::log::warn;
+use bevy::math::{Quat, Vec3};
+use bevy::prelude::{Commands, Event, Res, Transform};
+use bevy_ecs::entity::Entity;
+use bevy_ecs::message::MessageWriter;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{In, Single};
+use bevy_p2p::runtime::Runtime;
+use importer::card::{Format, SubCard};
+use importer::deck::{DeckCard, DeckEntry, Section, parse_deck, validate};
+use std::f32::consts::PI;
+#[derive(Event, Clone)]
+pub struct ImportDeck {
+    pub text: String,
+}
+type Fetched = Vec<(DeckEntry, Result<SubCard, String>)>;
+pub fn on_import_deck(
+    event: On<ImportDeck>,
+    client: Res<Client>,
+    runtime: Res<Runtime>,
+    settings: Res<Settings>,
+    spatial: Spatial,
+    mut commands: Commands,
+) {
+    let entries = parse_deck(&event.text);
+    if entries.is_empty() {
+        commands
+            .run_system_cached_with(deck_report, vec!["no cards found in deck list".to_owned()]);
+        return;
+    }
+    let pos = spatial.ray().map_or(Vec3::ZERO, |(_, pos, _)| pos);
+    let config = client.config.clone();
+    let quality = settings.quality;
+    let format = settings.deck_format;
+    let art = settings.art_preferences.clone();
+    runtime.spawn_hook(on_deck_fetched, async move {
+        let mut fetched = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let card = match &entry.set_cn {
+                Some(set_cn) => SubCard::get_set_cn(config.clone(), set_cn, quality)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => match SubCard::get_named(config.clone(), &entry.name, quality).await {
+                    Ok(card) => Ok(preferred_art(config.clone(), card, &art, quality).await),
+                    Err(e) => Err(e.to_string()),
+                },
+            };
+            fetched.push((entry, card));
+        }
+        (fetched, format, pos)
+    });
+}
+fn on_deck_fetched(
+    In((fetched, format, pos)): In<(Fetched, Format, Vec3)>,
+    mut commands: Commands,
+) {
+    let mut lines = Vec::new();
+    let mut piles: Vec<(Section, Vec<SubCard>)> = Vec::new();
+    let mut cards = Vec::new();
+    for (entry, card) in fetched {
+        match card {
+            Ok(card) => {
+                cards.push(DeckCard {
+                    count: entry.count,
+                    section: entry.section,
+                    data: card.data.clone(),
+                });
+                let copies = (0..entry.count).map(|_| card.clone());
+                match entry.section {
+                    Section::Main | Section::Sideboard => {
+                        match piles.iter_mut().find(|(s, _)| *s == entry.section) {
+                            Some((_, pile)) => pile.extend(copies),
+                            None => piles.push((entry.section, copies.collect())),
+                        }
+                    }
+                    Section::Commander | Section::Companion => {
+                        piles.extend(copies.map(|card| (entry.section, vec![card])));
+                    }
+                    Section::Maybeboard => {}
+                }
+            }
+            Err(e) => {
+                warn!("{}: {e}", entry.name);
+                lines.push(format!("failed to load {}: {e}", entry.name));
+            }
+        }
+    }
+    let violations = validate(format, &cards);
+    if violations.is_empty() {
+        lines.push(format!("deck is legal in {format}"));
+    } else {
+        lines.push(format!("deck is not legal in {format}:"));
+        lines.extend(violations.iter().map(ToString::to_string));
+    }
+    commands.run_system_cached_with(deck_report, lines);
+    piles.sort_by_key(|(section, _)| *section);
+    for (i, (section, cards)) in piles.into_iter().enumerate() {
+        let mut pile = Pile::new(cards);
+        let offset = Vec3::new(i as f32 * CARD_WIDTH * 1.25, 0.0, 0.0);
+        let transform = if section == Section::Main {
+            pile.shuffle();
+            Transform::from_translation(pos + offset).with_rotation(Quat::from_rotation_z(PI))
+        } else {
+            Transform::from_translation(pos + offset)
+        };
+        let ent = commands.spawn((transform, pile.bundle())).id();
+        commands.trigger(MoveUp::new(ent));
+    }
+}
+fn deck_report(
+    In(lines): In<Vec<String>>,
+    mut commands: Commands,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+) {
+    commands.entity(*text_chat).with_children(|parent| {
+        for line in lines {
+            parent.spawn(text_node(line));
+        }
+    });
+    msgs.write(Scroll::down(*text_chat));
+}
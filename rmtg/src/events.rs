@@ -1,3 +1,5 @@
+use crate::clock::on_clock_text_added;
+use crate::deck::on_import_deck;
 use crate::events::clipboard::{PollClipboard, get_clipboard};
 use crate::events::clone::on_clone;
 use crate::events::delete::on_delete;
@@ -9,10 +11,13 @@ use crate::events::repaint::{on_pile_added, on_repaint};
 use crate::events::roll::on_roll;
 use crate::events::scale::on_scale;
 use crate::events::scroll::insert_scroll_bar;
+use crate::life::{on_adjust_counter, on_life_counter_added, on_reset_tracker, sync_trackers};
 use crate::net::{connect_failed, on_connect, on_disconnect};
-use crate::paste::react_paste_card;
+use crate::paste::{on_paste, on_paste_custom, react_paste_card, sync_custom_cards};
+use crate::profile::{set_name, sync_profile};
 use crate::ui::chat::text_message;
 use crate::ui::esc_menu::on_iroh_bind_copy;
+use crate::ui::printings::on_pick_printing;
 use bevy::app::App;
 pub mod clipboard;
 pub mod clone;
@@ -25,6 +30,7 @@ pub mod repaint;
 pub mod roll;
 pub mod scale;
 pub mod scroll;
+pub mod spread;
 pub fn add_events(app: &mut App) {
     app.add_observer(move_up);
     app.add_observer(get_clipboard);
@@ -41,12 +47,24 @@ pub fn add_events(app: &mut App) {
     app.add_observer(update_box_select_mesh);
     app.add_observer(on_scale);
     app.add_observer(react_paste_card);
+    app.add_observer(on_paste);
+    app.add_observer(on_paste_custom);
     app.add_observer(text_message);
+    app.add_observer(set_name);
     app.add_observer(insert_scroll_bar);
     app.add_observer(on_delete);
     app.add_observer(on_pile_merge);
     app.add_observer(on_pile_added);
     app.add_observer(trigger_pile_merge);
     app.add_observer(on_iroh_bind_copy);
+    app.add_observer(on_pick_printing);
+    app.add_observer(on_import_deck);
+    app.add_observer(on_life_counter_added);
+    app.add_observer(on_adjust_counter);
+    app.add_observer(on_reset_tracker);
+    app.add_observer(sync_trackers);
+    app.add_observer(sync_profile);
+    app.add_observer(sync_custom_cards);
+    app.add_observer(on_clock_text_added);
     app.init_resource::<PollClipboard>();
 }
@@ -1,10 +1,14 @@
 use crate::ALPN;
+use crate::deck::ImportDeck;
+use crate::paste::PasteCustom;
 use bevy::clipboard::{Clipboard, ClipboardError, ClipboardRead};
 use bevy::image::Image;
 use bevy::log::warn;
 use bevy::prelude::{Commands, Event, On, ResMut, Resource};
 use bevy_ecs::system::In;
 use bevy_p2p::iroh_res::IrohConnect;
+use importer::custom::CustomInfo;
+use importer::image::encode_png;
 use std::mem;
 use std::sync::{Arc, Mutex};
 #[derive(Event)]
@@ -36,9 +40,11 @@ pub enum ClipboardData {
     Text(String),
     Image(Image),
 }
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum ClipboardEvent {
     ConnectToEndpoint,
+    CustomCard(CustomInfo),
+    ImportDeck,
 }
 impl ClipboardEvent {
     pub fn run(self, commands: &mut Commands, text: String) {
@@ -46,11 +52,20 @@ impl ClipboardEvent {
             Self::ConnectToEndpoint => {
                 commands.run_system_cached_with(connect_clipboard, text);
             }
+            Self::CustomCard(_) => warn!("clipboard does not contain an image"),
+            Self::ImportDeck => commands.trigger(ImportDeck { text }),
         }
     }
     pub fn run_image(self, commands: &mut Commands, image: Image) {
-        _ = commands;
-        _ = image;
+        match self {
+            Self::ConnectToEndpoint | Self::ImportDeck => {
+                warn!("clipboard does not contain text");
+            }
+            Self::CustomCard(info) => match encode_png(&image) {
+                Some(image) => commands.trigger(PasteCustom { info, image }),
+                None => warn!("unsupported clipboard image format"),
+            },
+        }
     }
 }
 fn connect_clipboard(In(endpoint): In<String>, mut commands: Commands) {
@@ -76,27 +91,28 @@ pub fn get_clipboard(
             let fetch = clipboard.fetch_text();
             match fetch {
                 ClipboardRead::Ready(maybe_value) => match maybe_value {
-                    Ok(mut value) => on.event.run(&mut commands, mem::take(&mut value)),
+                    Ok(mut value) => on.event.clone().run(&mut commands, mem::take(&mut value)),
                     Err(e) => warn!("{e:?}"),
                 },
-                ClipboardRead::Pending(poll) => polls.text.push((poll, on.event)),
+                ClipboardRead::Pending(poll) => polls.text.push((poll, on.event.clone())),
                 ClipboardRead::Taken => unreachable!(),
             }
         }
         ClipboardType::Image =>
         {
             #[cfg(not(target_family = "wasm"))]
-            if let Ok(image) = clipboard.fetch_image() {
-                on.event.run_image(&mut commands, image);
+            match clipboard.fetch_image() {
+                Ok(image) => on.event.clone().run_image(&mut commands, image),
+                Err(e) => warn!("{e:?}"),
             }
         }
     }
 }
 pub fn poll_clipboards(mut polls: ResMut<PollClipboard>, mut commands: Commands) {
-    polls.text.retain_mut(|&mut (ref mut poll, event)| {
+    polls.text.retain_mut(|(poll, event)| {
         if let Some(inner) = &mut *poll.lock().unwrap() {
             match inner {
-                Ok(value) => event.run(&mut commands, mem::take(value)),
+                Ok(value) => event.clone().run(&mut commands, mem::take(value)),
                 Err(e) => warn!("{e:?}"),
             }
             false
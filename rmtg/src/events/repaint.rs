@@ -1,5 +1,6 @@
 use crate::CARD_THICKNESS;
 use crate::assets::AssetManager;
+use crate::oracle::OracleText;
 use crate::pile::Pile;
 use avian3d::prelude::Collider;
 use bevy::math::Vec3;
@@ -47,14 +48,31 @@ pub fn on_repaint(
     mut decks: Query<(&Pile, &Children, &mut Collider)>,
     mut transforms: Query<&mut Transform>,
     mut top: Query<&mut MeshMaterial3d<StandardMaterial>>,
+    tops: Query<&Children>,
+    oracles: Query<&OracleText>,
     mut commands: Commands,
     assets: AssetManager,
 ) {
     let mut pile = decks.get_mut(on.entity).unwrap();
     *pile.collider = pile.pile.collider();
     let mut mat = top.get_mut(pile.children[0]).unwrap();
-    if let Some(new) = pile.pile.first().face_handles() {
-        mat.0 = new.material;
+    mat.0 = pile.pile.top_material(&assets);
+    let card = pile.pile.first();
+    let oracle = pile.pile.is_oracle().then(|| OracleText::from(card));
+    let mut shown = false;
+    for &child in tops.get(pile.children[0]).into_iter().flatten() {
+        if let Ok(text) = oracles.get(child) {
+            if Some(*text) == oracle {
+                shown = true;
+            } else {
+                commands.entity(child).despawn();
+            }
+        }
+    }
+    if !shown && let Some(text) = oracle {
+        commands
+            .entity(pile.children[0])
+            .with_child(text.bundle(card.face().get_oracle(), &assets));
     }
     let [mut up, mut down, mut side, mut outline_up, mut outline_down] = transforms
         .get_many_mut([
@@ -70,6 +88,11 @@ pub fn on_repaint(
     pile.pile.reposition_side(&mut side);
     pile.pile.reposition_up(&mut outline_up);
     pile.pile.reposition_down(&mut outline_down);
+    pile.pile.reorient_up(&mut up);
+    pile.pile.reorient_down(&mut down);
+    pile.pile.reorient(&mut side);
+    pile.pile.reorient(&mut outline_up);
+    pile.pile.reorient(&mut outline_down);
     if pile.children.len() - 5 != pile.pile.len() - 1 {
         for &ent in &pile.children[5..] {
             commands.entity(ent).despawn();
@@ -82,6 +105,7 @@ pub fn on_repaint(
                         (i as f32 - pile.pile.len() as f32 / 2.0) * CARD_THICKNESS,
                         0.0,
                     )
+                    .with_rotation(pile.pile.orientation())
                     .with_scale(Vec3::new(
                         1.0 + 1.0 / 4096.0,
                         1.0 / 4.0,
@@ -92,5 +116,11 @@ pub fn on_repaint(
                 ));
             }
         });
+    } else {
+        for &ent in &pile.children[5..] {
+            if let Ok(mut transform) = transforms.get_mut(ent) {
+                pile.pile.reorient(&mut transform);
+            }
+        }
     }
 }
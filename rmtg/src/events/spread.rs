@@ -0,0 +1,149 @@
+use crate::events::hover::HoveredObject;
+use crate::events::move_up::MoveUp;
+use crate::keybinds::Keybind;
+use crate::pile::{FlippedState, Pile};
+use crate::settings::Settings;
+use crate::spatial::Spatial;
+use crate::{CARD_HEIGHT, CARD_THICKNESS, CARD_WIDTH};
+use bevy::input::ButtonInput;
+use bevy::math::{Quat, Vec3};
+use bevy::prelude::{Transform, With};
+use bevy::reflect::Reflect;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Query, Res};
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+const GAP: f32 = CARD_THICKNESS * 4.0;
+const FAN_ANGLE: f32 = PI / 48.0;
+#[derive(Reflect, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpreadLayout {
+    #[default]
+    Row,
+    Grid,
+    Fan,
+}
+impl SpreadLayout {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Row => "row",
+            Self::Grid => "grid",
+            Self::Fan => "fan",
+        }
+    }
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            Self::Row => Self::Grid,
+            Self::Grid => Self::Fan,
+            Self::Fan => Self::Row,
+        }
+    }
+    fn offsets(self, len: usize) -> Vec<(Vec3, f32)> {
+        let mid = |n: usize| (n as f32 - 1.0) / 2.0;
+        match self {
+            Self::Row => (0..len)
+                .map(|i| {
+                    let x = (i as f32 - mid(len)) * (CARD_WIDTH + GAP);
+                    (Vec3::new(x, 0.0, 0.0), 0.0)
+                })
+                .collect(),
+            Self::Grid => {
+                let cols = (len as f32).sqrt().ceil() as usize;
+                let rows = len.div_ceil(cols);
+                (0..len)
+                    .map(|i| {
+                        let x = ((i % cols) as f32 - mid(cols)) * (CARD_WIDTH + GAP);
+                        let z = ((i / cols) as f32 - mid(rows)) * (CARD_HEIGHT + GAP);
+                        (Vec3::new(x, 0.0, z), 0.0)
+                    })
+                    .collect()
+            }
+            Self::Fan => (0..len)
+                .map(|i| {
+                    let offset = i as f32 - mid(len);
+                    let pos = Vec3::new(
+                        offset * CARD_WIDTH / 3.0,
+                        i as f32 * CARD_THICKNESS * 2.0,
+                        offset * offset * CARD_THICKNESS * 4.0,
+                    );
+                    (pos, -offset * FAN_ANGLE)
+                })
+                .collect(),
+        }
+    }
+}
+fn yaw(rotation: Quat) -> Quat {
+    let forward = rotation * Vec3::Z;
+    Quat::from_rotation_y(forward.x.atan2(forward.z))
+}
+#[must_use]
+pub fn reading_order(camera: Quat, a: &Transform, b: &Transform) -> Ordering {
+    let view = yaw(camera).inverse();
+    let key = |transform: &Transform| {
+        let local = view * transform.translation;
+        ((local.z / CARD_HEIGHT).round() as i32, local.x)
+    };
+    let (a_row, a_col) = key(a);
+    let (b_row, b_col) = key(b);
+    a_row.cmp(&b_row).then(a_col.total_cmp(&b_col))
+}
+pub fn do_spread(
+    mut hovered: Query<(Entity, &mut Pile, &Transform), With<HoveredObject>>,
+    keybinds: Res<ButtonInput<Keybind>>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    if !keybinds.just_pressed(Keybind::Spread) {
+        return;
+    }
+    let Some((entity, mut pile, transform)) = hovered.iter_mut().next() else {
+        return;
+    };
+    if pile.len() <= 1 {
+        return;
+    }
+    let len = pile.len();
+    let cards = pile.take_n_card(transform.rotation, len);
+    let facing = yaw(transform.rotation);
+    let offsets = settings.spread_layout.offsets(len);
+    for (card, (offset, angle)) in cards.into_iter().zip(offsets) {
+        let single = Pile::from(card);
+        let spawn = Transform::from_translation(transform.translation + facing * offset)
+            .with_rotation(facing * Quat::from_rotation_y(angle));
+        let ent = commands.spawn((spawn, single.bundle())).id();
+        commands.trigger(MoveUp::new(ent));
+    }
+    commands.entity(entity).despawn();
+}
+pub fn do_gather(
+    mut hovered: Query<(Entity, &mut Pile, &Transform), With<HoveredObject>>,
+    keybinds: Res<ButtonInput<Keybind>>,
+    spatial: Spatial,
+    mut commands: Commands,
+) {
+    if !keybinds.just_pressed(Keybind::Gather) || hovered.iter().count() <= 1 {
+        return;
+    }
+    let camera = spatial.camera.transform.rotation;
+    let mut piles: Vec<_> = hovered.iter_mut().collect();
+    piles.sort_by(|(_, _, a), (_, _, b)| reading_order(camera, a, b));
+    let (_, _, first) = &piles[0];
+    let transform = **first;
+    let mut cards = Vec::new();
+    for (entity, pile, pile_transform) in piles.iter_mut().rev() {
+        let len = pile.len();
+        cards.extend(
+            pile.take_n_card(pile_transform.rotation, len)
+                .into_iter()
+                .rev(),
+        );
+        commands.entity(*entity).despawn();
+    }
+    if FlippedState::from(transform.rotation).flipped() {
+        cards.reverse();
+    }
+    let pile = Pile::new(cards);
+    let ent = commands.spawn((transform, pile.bundle())).id();
+    commands.trigger(MoveUp::new(ent));
+}
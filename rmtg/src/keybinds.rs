@@ -3,6 +3,7 @@ use crate::focus::{Focus, Menu};
 use bevy::ecs::system::SystemParam;
 use bevy::input::ButtonInput;
 use bevy::prelude::{Deref, DerefMut, KeyCode, MouseButton, Res, ResMut, Resource};
+use bevy::reflect::Reflect;
 use enum_map::{Enum, EnumMap, enum_map};
 use enumset::{EnumSet, EnumSetType, enum_set};
 use std::fmt;
@@ -61,7 +62,7 @@ impl Keybinds<'_, '_> {
         }
     }
 }
-#[derive(Enum, EnumSetType, Debug, Hash)]
+#[derive(Enum, EnumSetType, Debug, Hash, Reflect)]
 pub enum Keybind {
     Select,
     HoldSelect,
@@ -83,6 +84,20 @@ pub enum Keybind {
     Rotate,
     ScaleUp,
     ScaleDown,
+    Printings,
+    Stats,
+    Mulligan,
+    LifeUp,
+    LifeDown,
+    PassTurn,
+    TakeTurn,
+    NextPhase,
+    PassClock,
+    PauseClock,
+    Spread,
+    Gather,
+    Sort,
+    SortMenu,
 }
 #[derive(Resource, Deref, DerefMut)]
 pub struct KeybindsList(EnumMap<Keybind, Bind>);
@@ -90,7 +105,6 @@ impl Default for KeybindsList {
     fn default() -> Self {
         let ctrl = Modifier::Control;
         let alt = Modifier::Alt;
-        _ = alt;
         let shift = Modifier::Shift;
         let map = enum_map! {
             Keybind::Select =>      Bind::new(enum_set!(),      enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  MouseButton::Left),
@@ -113,10 +127,67 @@ impl Default for KeybindsList {
             Keybind::Reset =>       Bind::new(enum_set!(),      enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::Space),
             Keybind::ScaleUp =>     Bind::new(enum_set!(),      enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::Equal),
             Keybind::ScaleDown =>   Bind::new(enum_set!(),      enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::Minus),
+            Keybind::Printings =>   Bind::new(ctrl | shift,     enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyO),
+            Keybind::Stats =>       Bind::new(ctrl | shift,     enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyI),
+            Keybind::Mulligan =>    Bind::new(ctrl | shift,     enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyM),
+            Keybind::LifeUp =>      Bind::new(enum_set!(ctrl),  enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::Equal),
+            Keybind::LifeDown =>    Bind::new(enum_set!(ctrl),  enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::Minus),
+            Keybind::PassTurn =>    Bind::new(enum_set!(),      enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyX),
+            Keybind::TakeTurn =>    Bind::new(enum_set!(ctrl),  enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyX),
+            Keybind::NextPhase =>   Bind::new(enum_set!(shift), enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyX),
+            Keybind::PassClock =>   Bind::new(ctrl | shift,     enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyX),
+            Keybind::PauseClock =>  Bind::new(ctrl | shift,     enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyP),
+            Keybind::Spread =>      Bind::new(ctrl | alt | shift, enum_set!(Menu::World | Menu::Side | Menu::Counter),           true,  true,  KeyCode::KeyS),
+            Keybind::Gather =>      Bind::new(ctrl | alt | shift, enum_set!(Menu::World | Menu::Side | Menu::Counter),           true,  true,  KeyCode::KeyG),
+            Keybind::Sort =>        Bind::new(enum_set!(alt),   enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyS),
+            Keybind::SortMenu =>    Bind::new(ctrl | shift,     enum_set!(Menu::World | Menu::Side | Menu::Counter),             true,  true,  KeyCode::KeyS),
         };
         Self(map)
     }
 }
+impl KeybindsList {
+    #[must_use]
+    pub fn from_saved(saved: &[SavedBind]) -> Self {
+        let mut list = Self::default();
+        for bind in saved {
+            let rebound = list[bind.keybind].with_input(bind.modifiers(), bind.key);
+            list[bind.keybind] = rebound;
+        }
+        list
+    }
+    #[must_use]
+    pub fn conflicts(&self, keybind: Keybind) -> Vec<Keybind> {
+        self.iter()
+            .filter(|(other, bind)| *other != keybind && bind.conflicts(&self[keybind]))
+            .map(|(other, _)| other)
+            .collect()
+    }
+}
+pub fn save_bind(saved: &mut Vec<SavedBind>, keybind: Keybind, bind: &Bind) {
+    saved.retain(|other| other.keybind != keybind);
+    if !KeybindsList::default()[keybind].same_input(bind) {
+        saved.push(SavedBind::new(keybind, bind));
+    }
+}
+#[derive(Reflect, Clone, Debug, PartialEq)]
+pub struct SavedBind {
+    pub keybind: Keybind,
+    pub modifiers: Vec<Modifier>,
+    pub key: Key,
+}
+impl SavedBind {
+    #[must_use]
+    pub fn new(keybind: Keybind, bind: &Bind) -> Self {
+        Self {
+            keybind,
+            modifiers: bind.modifiers.iter().collect(),
+            key: bind.key,
+        }
+    }
+    fn modifiers(&self) -> EnumSet<Modifier> {
+        self.modifiers.iter().copied().collect()
+    }
+}
 impl Display for KeybindsList {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
@@ -131,19 +202,30 @@ impl Display for KeybindsList {
 }
 impl Display for Bind {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        for m in self.modifiers.iter() {
-            write!(f, "{m:?}+")?;
+        let mut parts: Vec<String> = self.modifiers.iter().map(|m| format!("{m:?}")).collect();
+        if self.key != Key::None {
+            parts.push(self.key.to_string());
         }
-        write!(f, "{:?}", self.key)
+        write!(f, "{}", parts.join("+"))
     }
 }
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug, Reflect)]
 pub enum Key {
     KeyCode(KeyCode),
     Mouse(MouseButton),
     Numeric,
     None,
 }
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyCode(key) => write!(f, "{key:?}"),
+            Self::Mouse(button) => write!(f, "Mouse{button:?}"),
+            Self::Numeric => write!(f, "0-9"),
+            Self::None => Ok(()),
+        }
+    }
+}
 impl From<KeyCode> for Key {
     fn from(value: KeyCode) -> Self {
         Self::KeyCode(value)
@@ -154,7 +236,7 @@ impl From<MouseButton> for Key {
         Self::Mouse(value)
     }
 }
-#[derive(EnumSetType, Debug)]
+#[derive(EnumSetType, Debug, Reflect)]
 pub enum Modifier {
     Alt,
     Control,
@@ -193,7 +275,7 @@ impl TryFrom<&KeyCode> for Modifier {
         })
     }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Bind {
     modifiers: EnumSet<Modifier>,
     key: Key,
@@ -249,6 +331,18 @@ impl Bind {
         }
     }
     #[must_use]
+    pub fn with_input(&self, modifiers: EnumSet<Modifier>, key: Key) -> Self {
+        Self::new(modifiers, self.menus, self.strict, self.lock, key)
+    }
+    #[must_use]
+    pub fn same_input(&self, other: &Self) -> bool {
+        self.modifiers == other.modifiers && self.key == other.key
+    }
+    #[must_use]
+    pub fn conflicts(&self, other: &Self) -> bool {
+        self.same_input(other) && !(self.menus & other.menus).is_empty()
+    }
+    #[must_use]
     pub fn modifiers_pressed(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
         self.modifiers.iter().all(|m| m.pressed(keyboard))
             && (!self.strict
@@ -4,25 +4,30 @@
 #![feature(associated_type_defaults)]
 extern crate core;
 use bevy::color::Color;
-use importer::scryfall::Quality;
 pub mod app;
 pub mod assets;
 pub mod camera;
 pub mod card_spot;
+pub mod clock;
+pub mod deck;
 pub mod drag;
 pub mod events;
 pub mod focus;
 pub mod keybinds;
+pub mod life;
 pub mod mat;
 pub mod net;
+pub mod oracle;
 pub mod paste;
 pub mod physics;
 pub mod pile;
+pub mod profile;
+pub mod settings;
 pub mod shapes;
 pub mod spatial;
 pub mod startup;
+pub mod turn;
 pub mod ui;
-//TODO oracle card
 pub const APP_NAME: &str = "com.github.bgkillas.rmtg";
 pub const USER_AGENT: &str = concat!("com.github.bgkillas.rmtg/", env!("CARGO_PKG_VERSION"));
 pub const ALPN: &[u8] = USER_AGENT.as_bytes();
@@ -41,10 +46,10 @@ pub const PLAYER3: Color = Color::srgb_u8(85, 255, 85);
 pub const PLAYER4: Color = Color::srgb_u8(85, 255, 255);
 pub const PLAYER5: Color = Color::srgb_u8(255, 255, 85);
 pub const PLAYER: [Color; 6] = [PLAYER0, PLAYER1, PLAYER2, PLAYER3, PLAYER4, PLAYER5];
+pub const PLAYER_NAMES: [&str; 6] = ["red", "blue", "magenta", "green", "cyan", "yellow"];
 pub const MAT_WIDTH: f32 = 8.0;
 pub const MAT_HEIGHT: f32 = MAT_WIDTH * 9.0 / 16.0;
 pub const MAT_BAR: f32 = MAT_HEIGHT / 64.0;
-pub const QUALITY: Quality = Quality::Large;
 pub const T: f32 = W / 2.0;
 pub const W: f32 = MAT_WIDTH * 2.0;
 pub const CEILING_COLOR: Color = Color::srgb_u8(103, 73, 40);
@@ -52,6 +57,7 @@ pub const WALL_COLOR: Color = Color::srgb_u8(103, 73, 40);
 pub const FLOOR_COLOR: Color = Color::srgb_u8(103, 73, 40);
 pub const CARD_STOCK_COLOR: Color = Color::srgb_u8(0, 0, 0);
 pub const CARD_STOCK_INBETWEEN_COLOR: Color = Color::srgb_u8(64, 64, 64);
+pub const ORACLE_CARD_COLOR: Color = Color::srgb_u8(232, 224, 208);
 pub const SCROLLBAR: Color = Color::srgb(0.486, 0.486, 0.529);
 pub const SCROLLBAR_OUTLINE: Color = Color::srgb(0.71, 0.71, 0.772);
 pub const SCROLLBAR_HOVER: Color = Color::WHITE;
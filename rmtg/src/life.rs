@@ -0,0 +1,222 @@
+use crate::assets::AssetManager;
+use crate::keybinds::Keybind;
+use crate::net::{Msg, Peers};
+use crate::settings::Settings;
+use crate::{CARD_HEIGHT, WORLD_FONT_SIZE};
+use bevy::color::Srgba;
+use bevy::input::ButtonInput;
+use bevy::math::Vec2;
+use bevy::mesh::Mesh3d;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Component, Event, Res, ResMut, Resource};
+use bevy_ecs::entity::Entity;
+use bevy_ecs::lifecycle::Add;
+use bevy_ecs::observer::On;
+use bevy_ecs::system::{Commands, Query};
+use bevy_p2p::bitcode::{self, Decode, Encode};
+use bevy_p2p::events::PeerConnected;
+use bevy_p2p::message::Net;
+use bevy_rich_text3d::{Text3d, Text3dStyling, TextAnchor};
+use importer::card::Format;
+pub const SEATS: usize = 4;
+const LIFE_SIZE: f32 = CARD_HEIGHT / 3.0;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    Life,
+    Poison,
+    Energy,
+    Experience,
+    Commander { seat: usize, alt: bool },
+}
+impl Counter {
+    pub const PLAYER: [Self; 4] = [Self::Life, Self::Poison, Self::Energy, Self::Experience];
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Life => "life",
+            Self::Poison => "poison",
+            Self::Energy => "energy",
+            Self::Experience => "experience",
+            Self::Commander { alt: false, .. } => "commander",
+            Self::Commander { alt: true, .. } => "partner",
+        }
+    }
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct Tracker {
+    pub life: i32,
+    pub poison: i32,
+    pub energy: i32,
+    pub experience: i32,
+    pub commander: [[i32; 2]; SEATS],
+}
+impl Tracker {
+    #[must_use]
+    pub fn new(life: i32) -> Self {
+        Self {
+            life,
+            poison: 0,
+            energy: 0,
+            experience: 0,
+            commander: [[0; 2]; SEATS],
+        }
+    }
+    #[must_use]
+    pub fn get(&self, counter: Counter) -> i32 {
+        match counter {
+            Counter::Life => self.life,
+            Counter::Poison => self.poison,
+            Counter::Energy => self.energy,
+            Counter::Experience => self.experience,
+            Counter::Commander { seat, alt } => self.commander[seat][usize::from(alt)],
+        }
+    }
+    pub fn get_mut(&mut self, counter: Counter) -> &mut i32 {
+        match counter {
+            Counter::Life => &mut self.life,
+            Counter::Poison => &mut self.poison,
+            Counter::Energy => &mut self.energy,
+            Counter::Experience => &mut self.experience,
+            Counter::Commander { seat, alt } => &mut self.commander[seat][usize::from(alt)],
+        }
+    }
+    #[must_use]
+    pub fn summary(&self) -> String {
+        Counter::PLAYER
+            .iter()
+            .filter(|counter| **counter == Counter::Life || self.get(**counter) != 0)
+            .map(|counter| format!("{} {}", self.get(*counter), counter.name()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+#[derive(Resource)]
+pub struct Trackers {
+    pub seats: [Tracker; SEATS],
+}
+impl Default for Trackers {
+    fn default() -> Self {
+        Self {
+            seats: std::array::from_fn(|_| Tracker::new(Format::default().starting_life())),
+        }
+    }
+}
+#[derive(Component, Clone, Copy)]
+pub struct LifeCounter {
+    pub seat: usize,
+}
+impl LifeCounter {
+    #[must_use]
+    pub fn new(seat: usize) -> Self {
+        Self { seat }
+    }
+    fn text(self, trackers: &Trackers) -> Text3d {
+        let tracker = &trackers.seats[self.seat];
+        let extra: Vec<String> = Counter::PLAYER[1..]
+            .iter()
+            .filter(|counter| tracker.get(**counter) != 0)
+            .map(|counter| format!("{} {}", tracker.get(*counter), counter.name()))
+            .collect();
+        if extra.is_empty() {
+            Text3d::new(tracker.life.to_string())
+        } else {
+            Text3d::new(format!("{}\n{}", tracker.life, extra.join(" ")))
+        }
+    }
+}
+#[derive(Event, Clone, Copy)]
+pub struct AdjustCounter {
+    pub seat: usize,
+    pub counter: Counter,
+    pub delta: i32,
+}
+#[derive(Event, Clone, Copy)]
+pub struct ResetTracker {
+    pub seat: usize,
+}
+pub fn on_life_counter_added(
+    on: On<Add, LifeCounter>,
+    counters: Query<&LifeCounter>,
+    trackers: Res<Trackers>,
+    asset: AssetManager,
+    mut commands: Commands,
+) {
+    let counter = counters.get(on.entity).unwrap();
+    commands.entity(on.entity).insert((
+        counter.text(&trackers),
+        Mesh3d::default(),
+        MeshMaterial3d(asset.text_mesh.mesh.clone()),
+        Text3dStyling {
+            size: WORLD_FONT_SIZE,
+            anchor: TextAnchor::CENTER,
+            color: Srgba::WHITE,
+            world_scale: Some(Vec2::splat(LIFE_SIZE)),
+            ..Text3dStyling::default()
+        },
+    ));
+}
+pub fn update_life_counters(
+    trackers: Res<Trackers>,
+    counters: Query<(Entity, &LifeCounter)>,
+    mut commands: Commands,
+) {
+    if !trackers.is_changed() {
+        return;
+    }
+    for (entity, counter) in counters {
+        commands.entity(entity).insert(counter.text(&trackers));
+    }
+}
+pub fn do_life(keybinds: Res<ButtonInput<Keybind>>, peers: Res<Peers>, mut commands: Commands) {
+    let delta = i32::from(keybinds.just_pressed(Keybind::LifeUp))
+        - i32::from(keybinds.just_pressed(Keybind::LifeDown));
+    if delta != 0 {
+        commands.trigger(AdjustCounter {
+            seat: peers.my_id.unwrap_or_default().id,
+            counter: Counter::Life,
+            delta,
+        });
+    }
+}
+pub fn on_adjust_counter(event: On<AdjustCounter>, mut trackers: ResMut<Trackers>, net: Net<Msg>) {
+    let Some(tracker) = trackers.seats.get_mut(event.seat) else {
+        return;
+    };
+    *tracker.get_mut(event.counter) += event.delta;
+    if matches!(event.counter, Counter::Commander { .. }) {
+        tracker.life -= event.delta;
+    }
+    net.broadcast(Msg::Tracker {
+        seat: event.seat,
+        tracker: tracker.clone(),
+    });
+}
+pub fn on_reset_tracker(
+    event: On<ResetTracker>,
+    mut trackers: ResMut<Trackers>,
+    settings: Res<Settings>,
+    net: Net<Msg>,
+) {
+    let Some(tracker) = trackers.seats.get_mut(event.seat) else {
+        return;
+    };
+    *tracker = Tracker::new(settings.deck_format.starting_life());
+    net.broadcast(Msg::Tracker {
+        seat: event.seat,
+        tracker: tracker.clone(),
+    });
+}
+pub fn sync_trackers(
+    _: On<PeerConnected>,
+    peers: Res<Peers>,
+    trackers: Res<Trackers>,
+    net: Net<Msg>,
+) {
+    let seat = peers.my_id.unwrap_or_default().id;
+    if let Some(tracker) = trackers.seats.get(seat) {
+        net.broadcast(Msg::Tracker {
+            seat,
+            tracker: tracker.clone(),
+        });
+    }
+}
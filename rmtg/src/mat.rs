@@ -1,14 +1,45 @@
 use crate::card_spot::{CardSpot, SpotType};
+use crate::clock::ClockText;
+use crate::life::LifeCounter;
 use crate::net::Peer;
 use crate::{CARD_HEIGHT, CARD_THICKNESS, CARD_WIDTH, MAT_BAR, MAT_HEIGHT, MAT_WIDTH, PLAYER};
-use bevy::asset::Assets;
-use bevy::color::Color;
+use bevy::asset::{Assets, Handle};
+use bevy::color::{Color, Mix as _};
 use bevy::math::Vec3;
 use bevy::mesh::{Mesh, Mesh3d};
 use bevy::pbr::{MeshMaterial3d, StandardMaterial};
-use bevy::prelude::{Commands, InheritedVisibility, Rectangle, Transform};
+use bevy::prelude::{Commands, Component, InheritedVisibility, Rectangle, Transform};
 use bevy_ecs::system::ResMut;
 use std::f32::consts::PI;
+#[derive(Component)]
+pub struct Mat {
+    pub color: Color,
+    pub material: Handle<StandardMaterial>,
+    pub highlight: Handle<StandardMaterial>,
+}
+impl Mat {
+    #[must_use]
+    pub fn new(materials: &mut Assets<StandardMaterial>, color: Color) -> Self {
+        Self {
+            color,
+            material: materials.add(StandardMaterial {
+                unlit: true,
+                base_color: color,
+                ..StandardMaterial::default()
+            }),
+            highlight: materials.add(StandardMaterial {
+                unlit: true,
+                base_color: color.mix(&Color::WHITE, 0.5),
+                ..StandardMaterial::default()
+            }),
+        }
+    }
+    #[must_use]
+    pub fn contains(transform: &Transform, pos: Vec3) -> bool {
+        let local = transform.rotation.inverse() * (pos - transform.translation);
+        local.x.abs() < MAT_WIDTH / 2.0 && local.z.abs() < MAT_HEIGHT / 2.0
+    }
+}
 pub fn create_mats(
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -49,16 +80,13 @@ fn make_mat(
     color: Color,
     player: Peer,
 ) {
-    let mat = materials.add(StandardMaterial {
-        unlit: true,
-        base_color: color,
-        ..StandardMaterial::default()
-    });
+    let component = Mat::new(materials, color);
+    let mat = component.material.clone();
     let trans = |x: f32, y: f32, z: f32| -> Transform {
         Transform::from_xyz(if right { x } else { -x }, y, z)
     };
     commands
-        .spawn((transform, InheritedVisibility::VISIBLE))
+        .spawn((transform, InheritedVisibility::VISIBLE, component, player))
         .with_children(|p| {
             p.spawn((
                 Mesh3d(meshes.add(Rectangle::new(MAT_WIDTH, MAT_BAR))),
@@ -117,6 +145,24 @@ fn make_mat(
                     player,
                 ));
             }
+            p.spawn((
+                trans(
+                    MAT_BAR + CARD_WIDTH / 2.0 - MAT_WIDTH / 2.0,
+                    CARD_THICKNESS / 2.0,
+                    MAT_HEIGHT / 2.0 - MAT_BAR - CARD_HEIGHT / 2.0,
+                )
+                .looking_to(Vec3::NEG_Y, Vec3::NEG_Z),
+                LifeCounter::new(player.id),
+            ));
+            p.spawn((
+                trans(
+                    MAT_BAR + CARD_WIDTH * 1.75 - MAT_WIDTH / 2.0,
+                    CARD_THICKNESS / 2.0,
+                    MAT_HEIGHT / 2.0 - MAT_BAR - CARD_HEIGHT / 2.0,
+                )
+                .looking_to(Vec3::NEG_Y, Vec3::NEG_Z),
+                ClockText::new(player.id),
+            ));
             p.spawn((
                 Mesh3d(meshes.add(Rectangle::new(MAT_BAR, MAT_HEIGHT))),
                 MeshMaterial3d(mat.clone()),
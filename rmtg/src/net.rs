@@ -1,13 +1,23 @@
+use crate::app::Client;
+use crate::clock::Clocks;
+use crate::life::{SEATS, Tracker, Trackers};
+use crate::paste::on_custom_received;
+use crate::profile::{Profile, Profiles};
 use crate::spatial::Spatial;
-use bevy::log::info;
+use crate::turn::Turn;
+use bevy::log::{info, warn};
 use bevy::math::Vec3;
-use bevy::prelude::{Component, PopulatedMessageReader, Resource};
+use bevy::prelude::{Component, PopulatedMessageReader, Res, ResMut, Resource};
 use bevy_ecs::observer::On;
 use bevy_p2p::bitcode::{self, Decode, Encode};
 use bevy_p2p::events::{ConnectFailed, PeerConnected, PeerDisconnected};
 use bevy_p2p::iroh::EndpointId;
+use bevy_p2p::iroh_res::IrohResource;
 use bevy_p2p::message::{MessageReceived, Net};
+use bevy_p2p::runtime::Runtime;
+use importer::card::CardData;
 use importer::coder::DataCoder;
+use importer::custom::{MAX_IMAGE_BYTES, receive_custom};
 use rustc_hash::FxBuildHasher;
 use std::collections::HashMap;
 #[derive(Encode, Decode)]
@@ -18,6 +28,20 @@ pub enum Msg {
         #[bitcode(with = "DataCoder<Vec3>")]
         cursor: Vec3,
     },
+    CustomCard {
+        data: CardData,
+        image: Vec<u8>,
+    },
+    Tracker {
+        seat: usize,
+        tracker: Tracker,
+    },
+    Turn(Turn),
+    Clocks(Clocks),
+    Profile {
+        seat: usize,
+        profile: Profile,
+    },
 }
 pub fn net_update(net: Net<Msg>, spatial: Spatial) {
     if let Some((_, cursor, _)) = spatial.ray() {
@@ -25,13 +49,52 @@ pub fn net_update(net: Net<Msg>, spatial: Spatial) {
         net.broadcast(Msg::Camera { camera, cursor });
     }
 }
-pub fn receive_message(mut reader: PopulatedMessageReader<MessageReceived<Msg>>) {
+pub fn receive_message(
+    mut reader: PopulatedMessageReader<MessageReceived<Msg>>,
+    runtime: Res<Runtime>,
+    client: Res<Client>,
+    mut trackers: ResMut<Trackers>,
+    mut turn: ResMut<Turn>,
+    mut clocks: ResMut<Clocks>,
+    mut profiles: ResMut<Profiles>,
+) {
     for msg in reader.read() {
         match &msg.message {
             Msg::Camera { camera, cursor } => {
                 _ = camera;
                 _ = cursor;
             }
+            Msg::CustomCard { data, image } if image.len() <= MAX_IMAGE_BYTES => {
+                let custom = receive_custom(client.config.clone(), data.clone(), image.clone());
+                runtime.spawn_hook(on_custom_received, custom);
+            }
+            Msg::CustomCard { data, image } => {
+                warn!(
+                    "{}: custom image too large ({} bytes)",
+                    data.set_cn,
+                    image.len()
+                );
+            }
+            Msg::Tracker { seat, tracker } => {
+                if let Some(old) = trackers.seats.get_mut(*seat) {
+                    old.clone_from(tracker);
+                }
+            }
+            Msg::Turn(new) => {
+                if new.active < SEATS {
+                    *turn = *new;
+                }
+            }
+            Msg::Clocks(new) => {
+                if new.running < SEATS {
+                    clocks.clone_from(new);
+                }
+            }
+            Msg::Profile { seat, profile } => {
+                if let Some(old) = profiles.seats.get_mut(*seat) {
+                    old.clone_from(profile);
+                }
+            }
         }
     }
 }
@@ -39,7 +102,7 @@ pub fn receive_message(mut reader: PopulatedMessageReader<MessageReceived<Msg>>)
 pub struct Endpoint {
     pub peer: EndpointId,
 }
-#[derive(Component, Default, Clone, Copy)]
+#[derive(Component, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct Peer {
     pub id: usize,
 }
@@ -59,9 +122,57 @@ pub struct Peers {
 pub fn connect_failed(event: On<ConnectFailed>) {
     info!("{} failed", event.peer.fmt_short());
 }
-pub fn on_connect(event: On<PeerConnected>) {
+impl Peers {
+    fn free_seat(&self) -> Peer {
+        let seat = (0..SEATS)
+            .find(|seat| !self.id_to_peer.contains_key(&Peer::new(*seat)))
+            .unwrap_or(SEATS);
+        Peer::new(seat)
+    }
+    fn seat(&mut self, endpoint: EndpointId) {
+        if self.peer_to_id.contains_key(&endpoint) {
+            return;
+        }
+        let me = self.my_endpoint.map(|me| me.peer);
+        let mut unseated = vec![endpoint];
+        if self.my_id.is_none()
+            && let Some(me) = me
+        {
+            unseated.push(me);
+        }
+        unseated.sort_unstable_by_key(|endpoint| *endpoint.as_bytes());
+        for endpoint in unseated {
+            let peer = self.free_seat();
+            if Some(endpoint) == me {
+                self.my_id = Some(peer);
+            } else {
+                self.peer_to_id.insert(endpoint, peer);
+            }
+            if peer.id < SEATS {
+                self.id_to_peer.insert(peer, endpoint);
+            }
+        }
+    }
+    fn unseat(&mut self, endpoint: EndpointId) {
+        if let Some(peer) = self.peer_to_id.remove(&endpoint)
+            && self.id_to_peer.get(&peer) == Some(&endpoint)
+        {
+            self.id_to_peer.remove(&peer);
+        }
+    }
+}
+pub fn on_connect(
+    event: On<PeerConnected>,
+    iroh: Option<Res<IrohResource<Msg>>>,
+    mut peers: ResMut<Peers>,
+) {
     info!("{} connect", event.peer.fmt_short());
+    if let Some(iroh) = iroh {
+        peers.my_endpoint = Some(Endpoint { peer: iroh.my_id });
+    }
+    peers.seat(event.peer);
 }
-pub fn on_disconnect(event: On<PeerDisconnected>) {
+pub fn on_disconnect(event: On<PeerDisconnected>, mut peers: ResMut<Peers>) {
     info!("{} disconnect", event.peer.fmt_short());
+    peers.unseat(event.peer);
 }
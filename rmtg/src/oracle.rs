@@ -0,0 +1,105 @@
+use crate::assets::AssetManager;
+use crate::{CARD_HEIGHT, CARD_THICKNESS, CARD_WIDTH, WORLD_FONT_SIZE};
+use bevy::color::Srgba;
+use bevy::ecs::children;
+use bevy::math::Vec2;
+use bevy::mesh::Mesh3d;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Bundle, Component, InheritedVisibility, Transform};
+use bevy_rich_text3d::{Text3d, Text3dStyling, TextAnchor};
+use importer::card::SubCard;
+use importer::oracle_card::OracleFace;
+use importer::uuid::Uuid;
+const MARGIN: f32 = CARD_WIDTH / 16.0;
+const TITLE_SIZE: f32 = CARD_HEIGHT / 20.0;
+const TEXT_SIZE: f32 = CARD_HEIGHT / 26.0;
+const LIFT: f32 = CARD_THICKNESS / 64.0;
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct OracleText {
+    pub id: Uuid,
+    pub flipped: bool,
+}
+impl From<&SubCard> for OracleText {
+    fn from(card: &SubCard) -> Self {
+        Self {
+            id: card.data.id,
+            flipped: card.flipped,
+        }
+    }
+}
+fn section(
+    text: String,
+    pos: Vec2,
+    anchor: TextAnchor,
+    size: f32,
+    asset: &AssetManager,
+) -> impl Bundle + use<> {
+    (
+        Transform::from_xyz(pos.x, pos.y, LIFT),
+        Text3d::new(text),
+        Mesh3d::default(),
+        MeshMaterial3d(asset.text_mesh.mesh.clone()),
+        Text3dStyling {
+            size: WORLD_FONT_SIZE,
+            anchor,
+            color: Srgba::BLACK,
+            world_scale: Some(Vec2::splat(size)),
+            ..Text3dStyling::default()
+        },
+    )
+}
+fn lines(text: &str) -> f32 {
+    text.lines().count().max(1) as f32
+}
+impl OracleText {
+    #[must_use]
+    pub fn bundle(self, face: OracleFace, asset: &AssetManager) -> impl Bundle + use<> {
+        let left = MARGIN - CARD_WIDTH / 2.0;
+        let right = CARD_WIDTH / 2.0 - MARGIN;
+        let top = CARD_HEIGHT / 2.0 - MARGIN;
+        let type_top = top - lines(&face.name) * TITLE_SIZE - MARGIN / 2.0;
+        let text_top = type_top - lines(&face.type_line) * TEXT_SIZE - MARGIN;
+        (
+            self,
+            Transform::default(),
+            InheritedVisibility::VISIBLE,
+            children![
+                section(
+                    face.name,
+                    Vec2::new(left, top),
+                    TextAnchor::TOP_LEFT,
+                    TITLE_SIZE,
+                    asset
+                ),
+                section(
+                    face.cost,
+                    Vec2::new(right, top),
+                    TextAnchor::TOP_RIGHT,
+                    TEXT_SIZE,
+                    asset
+                ),
+                section(
+                    face.type_line,
+                    Vec2::new(left, type_top),
+                    TextAnchor::TOP_LEFT,
+                    TEXT_SIZE,
+                    asset
+                ),
+                section(
+                    face.text,
+                    Vec2::new(left, text_top),
+                    TextAnchor::TOP_LEFT,
+                    TEXT_SIZE,
+                    asset
+                ),
+                section(
+                    face.stats.unwrap_or_default(),
+                    Vec2::new(right, MARGIN - CARD_HEIGHT / 2.0),
+                    TextAnchor::BOTTOM_RIGHT,
+                    TITLE_SIZE,
+                    asset
+                ),
+            ],
+        )
+    }
+}
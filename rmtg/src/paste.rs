@@ -1,73 +1,232 @@
-use crate::QUALITY;
 use crate::app::Client;
+use crate::events::clipboard::{ClipboardEvent, GetClipboard};
 use crate::events::move_up::MoveUp;
+use crate::events::scroll::Scroll;
+use crate::net::Msg;
 use crate::pile::Pile;
+use crate::settings::Settings;
 use crate::spatial::Spatial;
-use crate::ui::chat::TextSubmission;
+use crate::ui::chat::{TextChat, TextSource, TextSubmission, text_node};
+use crate::ui::esc_menu::button;
 use bevy::log::warn;
 use bevy::math::Vec3;
-use bevy::prelude::{Commands, Res, Transform};
+use bevy::prelude::{Commands, Event, Res, Transform};
+use bevy::ui_widgets::{Activate, observe};
+#[cfg(not(target_family = "wasm"))]
+use bevy::window::FileDragAndDrop;
+use bevy_ecs::entity::Entity;
+#[cfg(not(target_family = "wasm"))]
+use bevy_ecs::message::MessageReader;
+use bevy_ecs::message::MessageWriter;
 use bevy_ecs::observer::On;
-use bevy_ecs::system::In;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{In, Single};
+use bevy_p2p::events::PeerConnected;
+use bevy_p2p::message::Net;
 use bevy_p2p::runtime::Runtime;
-use importer::card::SubCard;
+use importer::card::{CardData, SubCard};
+use importer::config::Config;
+use importer::custom::{CustomInfo, MAX_IMAGE_BYTES, add_custom, custom_cards};
+use importer::scryfall::{CardError, NameError, Quality};
 use importer::uuid::Uuid;
+use std::collections::HashMap;
+use std::fmt::Display;
 use std::str::FromStr as _;
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Identifier {
     Uuid(Uuid),
     SetCn(String),
+    Name(String),
+    List(Vec<Uuid>),
     None,
 }
-pub fn react_paste_card(
-    event: On<TextSubmission>,
+#[derive(Event, Clone)]
+pub struct Paste {
+    pub identifier: Identifier,
+    pub pos: Vec3,
+    pub prints: bool,
+}
+#[derive(Event, Clone)]
+pub struct PasteCustom {
+    pub info: CustomInfo,
+    pub image: Vec<u8>,
+}
+pub fn react_paste_card(event: On<TextSubmission>, spatial: Spatial, mut commands: Commands) {
+    if !matches!(event.source, TextSource::Chat) {
+        return;
+    }
+    if event.string.trim() == "deck" {
+        commands.trigger(GetClipboard::text(ClipboardEvent::ImportDeck));
+        return;
+    }
+    if let Some(rest) = event.string.strip_prefix("custom")
+        && (rest.is_empty() || rest.starts_with(' '))
+    {
+        let info = CustomInfo::parse(rest);
+        commands.trigger(GetClipboard::image(ClipboardEvent::CustomCard(info)));
+        return;
+    }
+    let Some((_, pos, _)) = spatial.ray() else {
+        return;
+    };
+    let (rest, prints) = if let Some(rest) = event.string.strip_prefix("prints ") {
+        (rest, true)
+    } else if let Some(rest) = event.string.strip_prefix("card ") {
+        (rest, false)
+    } else {
+        (event.string.as_str(), false)
+    };
+    let identifier = get_identifier(rest);
+    if !matches!(identifier, Identifier::None) {
+        commands.trigger(Paste {
+            identifier,
+            pos,
+            prints,
+        });
+    }
+}
+pub fn on_paste(
+    event: On<Paste>,
     client: Res<Client>,
     runtime: Res<Runtime>,
+    settings: Res<Settings>,
+) {
+    let config = client.config.clone();
+    let quality = settings.quality;
+    let pos = event.pos;
+    match (event.identifier.clone(), event.prints) {
+        (Identifier::Uuid(uuid), true) => {
+            runtime.spawn_hook(on_paste_card_prints_uuid, async move {
+                (SubCard::get_prints_id(config, uuid, quality).await, pos)
+            });
+        }
+        (Identifier::SetCn(set_cn), true) => {
+            runtime.spawn_hook(on_paste_card_prints_set, async move {
+                (
+                    SubCard::get_prints_set_cn(config, &set_cn, quality).await,
+                    pos,
+                )
+            });
+        }
+        (Identifier::Name(name), true) => {
+            runtime.spawn_hook(on_paste_card_prints_named, async move {
+                (SubCard::get_prints_named(config, &name, quality).await, pos)
+            });
+        }
+        (Identifier::Uuid(uuid), false) => {
+            runtime.spawn_hook(on_paste_card_uuid, async move {
+                (SubCard::get(config, uuid, quality).await, pos)
+            });
+        }
+        (Identifier::SetCn(set_cn), false) => {
+            runtime.spawn_hook(on_paste_card_set, async move {
+                (SubCard::get_set_cn(config, &set_cn, quality).await, pos)
+            });
+        }
+        (Identifier::Name(name), false) => {
+            let art = settings.art_preferences.clone();
+            runtime.spawn_hook(on_paste_card_named, async move {
+                let card = match SubCard::get_named(config.clone(), &name, quality).await {
+                    Ok(card) => Ok(preferred_art(config, card, &art, quality).await),
+                    Err(e) => Err(e),
+                };
+                (card, pos)
+            });
+        }
+        (Identifier::List(uuids), _) => {
+            runtime.spawn_hook(on_paste_card_prints, async move {
+                (SubCard::get_list(config, &uuids, quality).await, pos)
+            });
+        }
+        (Identifier::None, _) => {}
+    }
+}
+pub async fn preferred_art(
+    config: Config,
+    card: SubCard,
+    art: &HashMap<Uuid, Uuid>,
+    quality: Quality,
+) -> SubCard {
+    match art.get(&card.data.front.oracle_id) {
+        Some(&id) if id != card.data.id => SubCard::get(config, id, quality)
+            .await
+            .inspect_err(|e| warn!("{e}"))
+            .unwrap_or(card),
+        _ => card,
+    }
+}
+pub fn on_paste_custom(
+    event: On<PasteCustom>,
     spatial: Spatial,
+    client: Res<Client>,
+    runtime: Res<Runtime>,
 ) {
+    if event.image.len() > MAX_IMAGE_BYTES {
+        warn!("custom card image too large ({} bytes)", event.image.len());
+        return;
+    }
     let Some((_, pos, _)) = spatial.ray() else {
         return;
     };
-    if let Some(rest) = event.string.strip_prefix("prints ") {
-        match get_identifier(rest) {
-            Identifier::Uuid(uuid) => {
-                let client_owned = client.client.clone();
-                runtime.spawn_hook(on_paste_card_prints_uuid, async move {
-                    (
-                        SubCard::get_prints_id(client_owned, uuid, QUALITY).await,
-                        pos,
-                    )
-                });
-            }
-            Identifier::SetCn(set_cn) => {
-                let client_owned = client.client.clone();
-                runtime.spawn_hook(on_paste_card_prints_set, async move {
-                    (
-                        SubCard::get_prints_set_cn(client_owned, &set_cn, QUALITY).await,
-                        pos,
-                    )
-                });
-            }
-            Identifier::None => {}
-        }
-    } else {
-        match get_identifier(&event.string) {
-            Identifier::Uuid(uuid) => {
-                let client_owned = client.client.clone();
-                runtime.spawn_hook(on_paste_card_uuid, async move {
-                    (SubCard::get(client_owned, uuid, QUALITY).await, pos)
-                });
-            }
-            Identifier::SetCn(set_cn) => {
-                let client_owned = client.client.clone();
-                runtime.spawn_hook(on_paste_card_set, async move {
-                    (
-                        SubCard::get_set_cn(client_owned, &set_cn, QUALITY).await,
-                        pos,
-                    )
-                });
+    let config = client.config.clone();
+    let data = event.info.card_data(&event.image);
+    let image = event.image.clone();
+    runtime.spawn_hook(on_paste_custom_card, async move {
+        (add_custom(config, data, image.clone()).await, image, pos)
+    });
+}
+fn on_paste_custom_card(
+    In((card, image, pos)): In<(Option<SubCard>, Vec<u8>, Vec3)>,
+    mut commands: Commands,
+    net: Net<Msg>,
+) {
+    let Some(card) = card else {
+        warn!("custom card image could not be decoded");
+        return;
+    };
+    net.broadcast(Msg::CustomCard {
+        data: (*card.data).clone(),
+        image,
+    });
+    commands.run_system_cached_with(on_paste_card, (card, pos));
+}
+pub fn sync_custom_cards(_: On<PeerConnected>, client: Res<Client>, runtime: Res<Runtime>) {
+    runtime.spawn_hook(send_custom_cards, custom_cards(client.config.clone()));
+}
+fn send_custom_cards(In(cards): In<Vec<(CardData, Vec<u8>)>>, net: Net<Msg>) {
+    for (data, image) in cards {
+        net.broadcast(Msg::CustomCard { data, image });
+    }
+}
+pub fn on_custom_received(
+    In(card): In<Option<SubCard>>,
+    mut commands: Commands,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+) {
+    if let Some(card) = card {
+        commands.entity(*text_chat).with_child(text_node(format!(
+            "received custom card: {}",
+            card.data.front.name
+        )));
+        msgs.write(Scroll::down(*text_chat));
+    }
+}
+#[cfg(not(target_family = "wasm"))]
+pub fn drop_custom_files(mut drops: MessageReader<FileDragAndDrop>, mut commands: Commands) {
+    for drop in drops.read() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = drop {
+            match std::fs::read(path_buf) {
+                Ok(image) => {
+                    let name = path_buf.file_stem().map(|s| s.to_string_lossy());
+                    let info = CustomInfo {
+                        name: name.unwrap_or_default().into_owned(),
+                        ..CustomInfo::default()
+                    };
+                    commands.trigger(PasteCustom { info, image });
+                }
+                Err(e) => warn!("{}: {e}", path_buf.display()),
             }
-            Identifier::None => {}
         }
     }
 }
@@ -84,65 +243,197 @@ fn get_identifier(string: &str) -> Identifier {
         && let Ok(uuid) = Uuid::from_str(rest)
     {
         Identifier::Uuid(uuid)
-    } else {
+    } else if string.trim().is_empty() {
         Identifier::None
+    } else {
+        Identifier::Name(string.trim().to_owned())
     }
 }
-fn on_paste_card_uuid(In((is_ok, pos)): In<(Result<SubCard, Uuid>, Vec3)>, mut commands: Commands) {
+fn on_failed<T: Display>(
+    commands: &mut Commands,
+    e: &CardError<T>,
+    identifier: Identifier,
+    pos: Vec3,
+    prints: bool,
+) {
+    warn!("{e}");
+    let retry = e.error.is_retryable().then_some(Paste {
+        identifier,
+        pos,
+        prints,
+    });
+    commands.run_system_cached_with(on_fetch_failed, (vec![e.to_string()], retry));
+}
+fn on_paste_card_uuid(
+    In((is_ok, pos)): In<(Result<SubCard, CardError<Uuid>>, Vec3)>,
+    mut commands: Commands,
+) {
     match is_ok {
         Ok(val) => commands.run_system_cached_with(on_paste_card, (val, pos)),
-        Err(e) => warn!("{e:?}"),
+        Err(e) => on_failed(&mut commands, &e, Identifier::Uuid(e.id), pos, false),
     }
 }
 fn on_paste_card_set(
-    In((is_ok, pos)): In<(Result<SubCard, Box<str>>, Vec3)>,
+    In((is_ok, pos)): In<(Result<SubCard, CardError<Box<str>>>, Vec3)>,
     mut commands: Commands,
 ) {
     match is_ok {
         Ok(val) => commands.run_system_cached_with(on_paste_card, (val, pos)),
-        Err(e) => warn!("{e:?}"),
+        Err(e) => {
+            let identifier = Identifier::SetCn(e.id.clone().into_string());
+            on_failed(&mut commands, &e, identifier, pos, false);
+        }
     }
 }
+fn on_name_error(commands: &mut Commands, error: NameError, pos: Vec3, prints: bool) {
+    match error {
+        NameError::Ambiguous(names) => {
+            commands.run_system_cached_with(on_ambiguous_name, (names, pos, prints));
+        }
+        NameError::Fetch(e) => {
+            let identifier = Identifier::Name(e.id.clone().into_string());
+            on_failed(commands, &e, identifier, pos, prints);
+        }
+    }
+}
+fn on_paste_card_named(
+    In((is_ok, pos)): In<(Result<SubCard, NameError>, Vec3)>,
+    mut commands: Commands,
+) {
+    match is_ok {
+        Ok(val) => commands.run_system_cached_with(on_paste_card, (val, pos)),
+        Err(e) => on_name_error(&mut commands, e, pos, false),
+    }
+}
+fn on_ambiguous_name(
+    In((names, pos, prints)): In<(Vec<Box<str>>, Vec3, bool)>,
+    mut commands: Commands,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+) {
+    commands.entity(*text_chat).with_children(|parent| {
+        parent.spawn(text_node("did you mean:".to_owned()));
+        for name in names {
+            let paste = Paste {
+                identifier: Identifier::Name(name.clone().into_string()),
+                pos,
+                prints,
+            };
+            parent.spawn((
+                button(&name),
+                observe(move |_: On<Activate>, mut commands: Commands| {
+                    commands.trigger(paste.clone());
+                }),
+            ));
+        }
+    });
+    msgs.write(Scroll::down(*text_chat));
+}
+fn on_fetch_failed(
+    In((failed, retry)): In<(Vec<String>, Option<Paste>)>,
+    mut commands: Commands,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+) {
+    commands.entity(*text_chat).with_children(|parent| {
+        parent.spawn(text_node(format!(
+            "failed to load {} card(s):",
+            failed.len()
+        )));
+        for line in failed {
+            parent.spawn(text_node(line));
+        }
+        if let Some(paste) = retry {
+            parent.spawn((
+                button("retry failed"),
+                observe(move |_: On<Activate>, mut commands: Commands| {
+                    commands.trigger(paste.clone());
+                }),
+            ));
+        }
+    });
+    msgs.write(Scroll::down(*text_chat));
+}
 fn on_paste_card(In((card, pos)): In<(SubCard, Vec3)>, mut commands: Commands) {
+    let pile = Pile::from(card);
     let ent = commands
-        .spawn((Transform::from_translation(pos), Pile::from(card).bundle()))
+        .spawn((Transform::from_translation(pos), pile.bundle()))
         .id();
     commands.trigger(MoveUp::new(ent));
 }
 fn on_paste_card_prints_uuid(
-    In((is_ok, pos)): In<(Result<Vec<Result<SubCard, Uuid>>, Uuid>, Vec3)>,
+    In((is_ok, pos)): In<(
+        Result<Vec<Result<SubCard, CardError<Uuid>>>, CardError<Uuid>>,
+        Vec3,
+    )>,
     mut commands: Commands,
 ) {
     match is_ok {
         Ok(val) => commands.run_system_cached_with(on_paste_card_prints, (val, pos)),
-        Err(e) => warn!("{e:?}"),
+        Err(e) => on_failed(&mut commands, &e, Identifier::Uuid(e.id), pos, true),
     }
 }
 fn on_paste_card_prints_set(
-    In((is_ok, pos)): In<(Result<Vec<Result<SubCard, Uuid>>, Box<str>>, Vec3)>,
+    In((is_ok, pos)): In<(
+        Result<Vec<Result<SubCard, CardError<Uuid>>>, CardError<Box<str>>>,
+        Vec3,
+    )>,
     mut commands: Commands,
 ) {
     match is_ok {
         Ok(val) => commands.run_system_cached_with(on_paste_card_prints, (val, pos)),
-        Err(e) => warn!("{e:?}"),
+        Err(e) => {
+            let identifier = Identifier::SetCn(e.id.clone().into_string());
+            on_failed(&mut commands, &e, identifier, pos, true);
+        }
+    }
+}
+fn on_paste_card_prints_named(
+    In((is_ok, pos)): In<(
+        Result<Vec<Result<SubCard, CardError<Uuid>>>, NameError>,
+        Vec3,
+    )>,
+    mut commands: Commands,
+) {
+    match is_ok {
+        Ok(val) => commands.run_system_cached_with(on_paste_card_prints, (val, pos)),
+        Err(e) => on_name_error(&mut commands, e, pos, true),
     }
 }
 fn on_paste_card_prints(
-    In((cards, pos)): In<(Vec<Result<SubCard, Uuid>>, Vec3)>,
+    In((cards, pos)): In<(Vec<Result<SubCard, CardError<Uuid>>>, Vec3)>,
     mut commands: Commands,
 ) {
+    let mut failed = Vec::new();
+    let mut retry_ids = Vec::new();
     let pile: Vec<_> = cards
         .into_iter()
         .filter_map(|c| match c {
             Ok(v) => Some(v),
             Err(e) => {
-                warn!("{e:?}");
+                warn!("{e}");
+                if e.error.is_retryable() {
+                    retry_ids.push(e.id);
+                }
+                failed.push(e.to_string());
                 None
             }
         })
         .collect();
+    if !failed.is_empty() {
+        let retry = (!retry_ids.is_empty()).then_some(Paste {
+            identifier: Identifier::List(retry_ids),
+            pos,
+            prints: false,
+        });
+        commands.run_system_cached_with(on_fetch_failed, (failed, retry));
+    }
+    if pile.is_empty() {
+        return;
+    }
+    let cards = Pile::new(pile);
     let ent = commands
-        .spawn((Transform::from_translation(pos), Pile::new(pile).bundle()))
+        .spawn((Transform::from_translation(pos), cards.bundle()))
         .id();
     commands.trigger(MoveUp::new(ent));
 }
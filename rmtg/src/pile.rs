@@ -4,19 +4,21 @@ use crate::events::repaint::Repaint;
 use crate::physics::physics_base;
 use crate::{CARD_HEIGHT, CARD_THICKNESS, CARD_WIDTH};
 use avian3d::prelude::{Collider, CollisionEventsEnabled};
-use bevy::asset::Assets;
+use bevy::asset::{Assets, Handle};
+use bevy::camera::{Camera, Camera3d};
 use bevy::image::Image;
-use bevy::math::{Dir3, Quat, Vec3};
+use bevy::math::{Dir3, Quat, Vec2, Vec3};
 use bevy::mesh::Mesh3d;
 use bevy::pbr::{MeshMaterial3d, StandardMaterial};
-use bevy::prelude::{Bundle, Component, InheritedVisibility, Transform};
+use bevy::prelude::{Bundle, Component, GlobalTransform, InheritedVisibility, Single, Transform};
 use bevy_ecs::entity::Entity;
-use bevy_ecs::query::With;
+use bevy_ecs::query::{With, Without};
 use bevy_ecs::system::{Commands, Query, ResMut};
 use bevy_query_fn_macro::query_fn;
 use bitcode::{Decode, Encode};
 use importer::bitcode;
 use importer::card::{Card, CardIter, CardIterMut, MaybeHandles, SubCard};
+use importer::decode::DECODE_POOL;
 use importer::scryfall::{CACHE, IMAGES_TO_PROCESS};
 use itertools::Either;
 use rand::make_rng;
@@ -25,6 +27,7 @@ use rand::seq::SliceRandom as _;
 use rustc_hash::FxBuildHasher;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::f32::consts::FRAC_PI_2;
 use std::ops::{Bound, RangeBounds};
 use std::slice::{Iter, IterMut};
 use std::{iter, mem};
@@ -129,11 +132,7 @@ impl Pile {
         (
             Transform::from_xyz(0.0, self.thickness() / 2.0, 0.0)
                 .looking_to(Dir3::NEG_Y, Dir3::NEG_Z),
-            MeshMaterial3d(
-                self.first()
-                    .face_handles()
-                    .map_or_else(|| asset.card.back.clone(), |h| h.material),
-            ),
+            MeshMaterial3d(self.top_material(asset)),
             Mesh3d(asset.card.stock.clone()),
             CardTop,
         )
@@ -148,15 +147,41 @@ impl Pile {
         )
     }
     #[must_use]
+    pub fn orientation(&self) -> Quat {
+        if self.first().is_sideways() {
+            Quat::from_rotation_y(-FRAC_PI_2)
+        } else {
+            Quat::IDENTITY
+        }
+    }
+    pub fn reorient_up(&self, transform: &mut Transform) {
+        transform.look_to(Dir3::NEG_Y, self.orientation() * Dir3::NEG_Z);
+    }
+    pub fn reorient_down(&self, transform: &mut Transform) {
+        transform.look_to(Dir3::Y, self.orientation() * Dir3::NEG_Z);
+    }
+    pub fn reorient(&self, transform: &mut Transform) {
+        transform.rotation = self.orientation();
+    }
+    #[must_use]
     pub fn is_oracle(&self) -> bool {
-        matches!(
-            self.first().face_maybe_handles(),
-            MaybeHandles::None | MaybeHandles::Waiting
-        )
+        matches!(self.first().face_maybe_handles(), MaybeHandles::None)
+    }
+    #[must_use]
+    pub fn top_material(&self, asset: &AssetManager) -> Handle<StandardMaterial> {
+        match self.first().face_maybe_handles() {
+            MaybeHandles::Some(handles) => handles.material.clone(),
+            MaybeHandles::Waiting => asset.card.back.clone(),
+            MaybeHandles::None => asset.card.oracle.clone(),
+        }
     }
     #[must_use]
     pub fn collider(&self) -> Collider {
-        Collider::cuboid(CARD_WIDTH, self.thickness(), CARD_HEIGHT)
+        if self.first().is_sideways() {
+            Collider::cuboid(CARD_HEIGHT, self.thickness(), CARD_WIDTH)
+        } else {
+            Collider::cuboid(CARD_WIDTH, self.thickness(), CARD_HEIGHT)
+        }
     }
     #[must_use]
     pub fn thickness(&self) -> f32 {
@@ -550,9 +575,32 @@ impl From<SubCard> for Pile {
         Self::Single(Box::new(Card::from(value)))
     }
 }
+pub fn prioritize_visible(
+    query: Query<(&Pile, &Transform), With<PendingCards>>,
+    view: Single<(&Camera, &Transform), With<Camera3d>>,
+) {
+    if query.is_empty() || DECODE_POOL.queued() == 0 {
+        return;
+    }
+    let (camera, camera_transform) = *view;
+    let global = GlobalTransform::from(*camera_transform);
+    let Some(size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let visible = query
+        .iter()
+        .filter(|(_, transform)| {
+            camera
+                .world_to_viewport(&global, transform.translation)
+                .is_ok_and(|pos| pos.cmpge(Vec2::ZERO).all() && pos.cmple(size).all())
+        })
+        .flat_map(|(pile, _)| pile.iter().map(|card| card.data.id));
+    DECODE_POOL.set_visible(visible);
+}
 #[query_fn]
 pub fn register_cards(
     query: Query<(Entity, &mut Pile), With<PendingCards>>,
+    settled: Query<(Entity, &mut Pile), Without<PendingCards>>,
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -575,10 +623,26 @@ pub fn register_cards(
             .collect::<HashMap<_, _, FxBuildHasher>>();
         drop(new_images);
         let mut cache = CACHE.blocking_lock();
-        for (uuid, (front, back)) in map {
-            let card = cache.cards.get_mut(&uuid).unwrap();
-            card.face_handles = front.map_or(MaybeHandles::None, MaybeHandles::Some);
-            card.back_handles = back.map_or(MaybeHandles::None, MaybeHandles::Some);
+        for (uuid, (front, back)) in &map {
+            let card = cache.cards.get_mut(uuid).unwrap();
+            card.face_handles = front.clone().map_or(MaybeHandles::None, MaybeHandles::Some);
+            card.back_handles = back.clone().map_or(MaybeHandles::None, MaybeHandles::Some);
+        }
+        drop(cache);
+        for mut pile in settled {
+            let mut repaint = false;
+            for card in &mut pile.pile {
+                if let Some((Some(front), back)) = map.get(&card.data.id) {
+                    repaint = true;
+                    card.face_handles = MaybeHandles::Some(front.clone());
+                    if let Some(back) = back {
+                        card.back_handles = MaybeHandles::Some(back.clone());
+                    }
+                }
+            }
+            if repaint {
+                commands.trigger(Repaint::new(pile.entity));
+            }
         }
     }
     for mut pile in query {
@@ -0,0 +1,120 @@
+use crate::life::SEATS;
+use crate::mat::Mat;
+use crate::net::{Msg, Peer, Peers};
+use crate::settings::Settings;
+use crate::ui::chat::{TextSource, TextSubmission};
+use crate::{PLAYER, PLAYER_NAMES};
+use bevy::asset::Assets;
+use bevy::color::Color;
+use bevy::pbr::{MeshMaterial3d, StandardMaterial};
+use bevy::prelude::{Res, ResMut, Resource};
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::observer::On;
+use bevy_ecs::system::Query;
+use bevy_p2p::bitcode::{self, Decode, Encode};
+use bevy_p2p::events::PeerConnected;
+use bevy_p2p::message::Net;
+const NAME_LEN: usize = 24;
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub struct Profile {
+    pub name: String,
+    pub color: Option<usize>,
+}
+impl Profile {
+    #[must_use]
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            name: settings.network.name.clone(),
+            color: settings.network.color,
+        }
+    }
+    #[must_use]
+    pub fn color(&self, seat: usize) -> Color {
+        PLAYER[self.color.unwrap_or(seat) % PLAYER.len()]
+    }
+    #[must_use]
+    pub fn label(&self, seat: usize) -> String {
+        if self.name.is_empty() {
+            format!("P{}", seat + 1)
+        } else {
+            format!("P{} {}", seat + 1, self.name)
+        }
+    }
+}
+#[must_use]
+pub fn color_name(color: Option<usize>) -> &'static str {
+    color.map_or("seat", |i| PLAYER_NAMES[i % PLAYER_NAMES.len()])
+}
+#[derive(Resource, Default)]
+pub struct Profiles {
+    pub seats: [Profile; SEATS],
+}
+pub fn update_profile(
+    settings: Res<Settings>,
+    peers: Res<Peers>,
+    mut profiles: ResMut<Profiles>,
+    net: Net<Msg>,
+) {
+    if !settings.is_changed() && !peers.is_changed() {
+        return;
+    }
+    let seat = peers.my_id.unwrap_or_default().id;
+    let profile = Profile::new(&settings);
+    let Some(old) = profiles.seats.get_mut(seat) else {
+        return;
+    };
+    if *old != profile {
+        old.clone_from(&profile);
+        net.broadcast(Msg::Profile { seat, profile });
+    }
+}
+pub fn set_name(event: On<TextSubmission>, mut settings: ResMut<Settings>) {
+    if matches!(event.source, TextSource::Name) {
+        settings.network.name = event.string.trim().chars().take(NAME_LEN).collect();
+    }
+}
+pub fn sync_profile(
+    _: On<PeerConnected>,
+    peers: Res<Peers>,
+    profiles: Res<Profiles>,
+    net: Net<Msg>,
+) {
+    let seat = peers.my_id.unwrap_or_default().id;
+    if let Some(profile) = profiles.seats.get(seat) {
+        net.broadcast(Msg::Profile {
+            seat,
+            profile: profile.clone(),
+        });
+    }
+}
+pub fn update_mat_colors(
+    profiles: Res<Profiles>,
+    mats: Query<(&mut Mat, &Peer, &Children)>,
+    mut children: Query<&mut MeshMaterial3d<StandardMaterial>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !profiles.is_changed() {
+        return;
+    }
+    for (mut mat, peer, mat_children) in mats {
+        let Some(profile) = profiles.seats.get(peer.id) else {
+            continue;
+        };
+        let color = profile.color(peer.id);
+        if mat.color == color {
+            continue;
+        }
+        let new = Mat::new(&mut materials, color);
+        for child in mat_children {
+            let Ok(mut material) = children.get_mut(*child) else {
+                continue;
+            };
+            if material.0 == mat.material {
+                material.0 = new.material.clone();
+            } else if material.0 == mat.highlight {
+                material.0 = new.highlight.clone();
+            }
+        }
+        *mat = new;
+    }
+}
@@ -0,0 +1,167 @@
+use crate::app::Client;
+use crate::events::spread::SpreadLayout;
+use crate::keybinds::{KeybindsList, SavedBind};
+use crate::{FONT_SIZE, PLAYER};
+use bevy::image::{CompressedImageFormatSupport, CompressedImageFormats};
+use bevy::prelude::{ReflectDefault, ReflectResource, Res, ResMut, Resource};
+use bevy::reflect::Reflect;
+use bevy::settings::{ReflectSettingsGroup, SettingsGroup};
+use bevy::ui::UiScale;
+use bevy_framepace::{FramepaceSettings, Limiter};
+use enumset::EnumSet;
+use importer::card::Format;
+use importer::scryfall::Quality;
+use importer::sort::SortBy;
+use importer::uuid::Uuid;
+use std::collections::HashMap;
+#[derive(Resource, SettingsGroup, Reflect, Default, Clone)]
+#[reflect(Resource, SettingsGroup, Default)]
+pub struct Settings {
+    pub quality: Quality,
+    pub compress_textures: bool,
+    pub remember_art: bool,
+    pub deck_format: Format,
+    pub free_mulligan: bool,
+    pub track_phases: bool,
+    pub auto_untap: bool,
+    pub clock_minutes: u32,
+    pub clock_increment: u32,
+    pub round_minutes: u32,
+    pub spread_layout: SpreadLayout,
+    pub sort_keys: Vec<SortBy>,
+    pub keybinds: Vec<SavedBind>,
+    pub graphics: GraphicsSettings,
+    pub camera: CameraSettings,
+    pub network: NetworkSettings,
+    pub chat: ChatSettings,
+    pub art_preferences: HashMap<Uuid, Uuid>,
+}
+#[derive(Reflect, Clone)]
+#[reflect(Default)]
+pub struct GraphicsSettings {
+    pub frame_limit: u32,
+    pub ui_scale: u32,
+}
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            frame_limit: 0,
+            ui_scale: 100,
+        }
+    }
+}
+#[derive(Reflect, Clone)]
+#[reflect(Default)]
+pub struct CameraSettings {
+    pub move_speed: u32,
+    pub zoom_speed: u32,
+    pub sensitivity: u32,
+}
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            move_speed: 100,
+            zoom_speed: 100,
+            sensitivity: 100,
+        }
+    }
+}
+impl CameraSettings {
+    #[must_use]
+    pub fn move_scale(&self) -> f32 {
+        self.move_speed as f32 / 100.0
+    }
+    #[must_use]
+    pub fn zoom_scale(&self) -> f32 {
+        self.zoom_speed as f32 / 100.0
+    }
+    #[must_use]
+    pub fn rotate_scale(&self) -> f32 {
+        self.sensitivity as f32 / 100_000.0
+    }
+}
+#[derive(Reflect, Clone, Default)]
+#[reflect(Default)]
+pub struct NetworkSettings {
+    pub name: String,
+    pub color: Option<usize>,
+}
+#[derive(Reflect, Clone)]
+#[reflect(Default)]
+pub struct ChatSettings {
+    pub font_size: u32,
+    pub history: u32,
+}
+impl Default for ChatSettings {
+    fn default() -> Self {
+        Self {
+            font_size: FONT_SIZE as u32,
+            history: 200,
+        }
+    }
+}
+#[must_use]
+pub fn next_quality(quality: Quality) -> Quality {
+    match quality {
+        Quality::Small => Quality::Normal,
+        Quality::Normal => Quality::Large,
+        Quality::Large => Quality::Png,
+        Quality::Png => Quality::Small,
+    }
+}
+#[must_use]
+pub fn next_option(value: u32, options: &[u32]) -> u32 {
+    options
+        .iter()
+        .copied()
+        .find(|option| *option > value)
+        .unwrap_or(0)
+}
+#[must_use]
+pub fn next_step(value: u32, steps: &[u32]) -> u32 {
+    steps
+        .iter()
+        .copied()
+        .find(|step| *step > value)
+        .unwrap_or(steps[0])
+}
+#[must_use]
+pub fn next_color(color: Option<usize>) -> Option<usize> {
+    match color {
+        None => Some(0),
+        Some(i) if i + 1 < PLAYER.len() => Some(i + 1),
+        Some(_) => None,
+    }
+}
+#[must_use]
+pub fn next_format(format: Format) -> Format {
+    let formats = EnumSet::<Format>::all();
+    formats
+        .iter()
+        .skip_while(|f| *f != format)
+        .nth(1)
+        .or_else(|| formats.iter().next())
+        .unwrap_or(format)
+}
+pub fn apply_settings(
+    settings: Res<Settings>,
+    support: Option<Res<CompressedImageFormatSupport>>,
+    mut client: ResMut<Client>,
+    mut framepace: ResMut<FramepaceSettings>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    if settings.is_changed() {
+        let supported = support.is_some_and(|s| s.0.contains(CompressedImageFormats::BC));
+        client.config.compress = settings.compress_textures && supported;
+        framepace.limiter = match settings.graphics.frame_limit {
+            0 => Limiter::Auto,
+            fps => Limiter::from_framerate(f64::from(fps)),
+        };
+        ui_scale.0 = settings.graphics.ui_scale as f32 / 100.0;
+    }
+}
+pub fn apply_keybinds(settings: Res<Settings>, mut keybinds: ResMut<KeybindsList>) {
+    if settings.is_changed() {
+        *keybinds = KeybindsList::from_saved(&settings.keybinds);
+    }
+}
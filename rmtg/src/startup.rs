@@ -1,5 +1,6 @@
 use crate::assets::{AssetManager, CardBase, OutlineMaterials, ShapeMeshes, TextMesh};
 use crate::camera::{CameraVelocity, default_cam_pos};
+use crate::clock::round_clock_bundle;
 use crate::net::Peer;
 use crate::physics::WorldLayer;
 use crate::pile::Pile;
@@ -13,6 +14,7 @@ use crate::shapes::tetrahedron::Tetrahedron;
 use crate::shapes::trapezohedron::Trapezohedron;
 use crate::ui::chat::chat_bundle;
 use crate::ui::esc_menu::esc_menu_bundle;
+use crate::ui::life::life_hud_bundle;
 use crate::{
     CARD_HEIGHT, CARD_THICKNESS, CARD_WIDTH, CEILING_COLOR, FLOOR_COLOR, FONT, MAT_WIDTH, T, W,
     WALL_COLOR,
@@ -82,6 +84,8 @@ pub fn startup(
         CameraVelocity::default(),
     ));
     commands.spawn(chat_bundle());
+    commands.spawn(life_hud_bundle());
+    commands.spawn(round_clock_bundle());
     commands.spawn(esc_menu_bundle());
 }
 pub fn spawn_objects(
@@ -0,0 +1,212 @@
+use crate::events::scroll::Scroll;
+use crate::keybinds::Keybind;
+use crate::life::SEATS;
+use crate::mat::Mat;
+use crate::net::{Msg, Peer, Peers};
+use crate::pile::{Pile, TapState};
+use crate::settings::Settings;
+use crate::ui::chat::{TextChat, text_node};
+use bevy::input::ButtonInput;
+use bevy::math::{Quat, Vec3};
+use bevy::pbr::{MeshMaterial3d, StandardMaterial};
+use bevy::prelude::{Res, ResMut, Resource, Transform};
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::message::MessageWriter;
+use bevy_ecs::query::{With, Without};
+use bevy_ecs::system::{Commands, Query, Single};
+use bevy_p2p::bitcode::{self, Decode, Encode};
+use bevy_p2p::message::Net;
+use std::fmt::{Display, Formatter};
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub enum Phase {
+    #[default]
+    Untap,
+    Upkeep,
+    Draw,
+    FirstMain,
+    BeginCombat,
+    DeclareAttackers,
+    DeclareBlockers,
+    CombatDamage,
+    EndCombat,
+    SecondMain,
+    End,
+    Cleanup,
+}
+impl Phase {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Untap => "untap",
+            Self::Upkeep => "upkeep",
+            Self::Draw => "draw",
+            Self::FirstMain => "precombat main",
+            Self::BeginCombat => "beginning of combat",
+            Self::DeclareAttackers => "declare attackers",
+            Self::DeclareBlockers => "declare blockers",
+            Self::CombatDamage => "combat damage",
+            Self::EndCombat => "end of combat",
+            Self::SecondMain => "postcombat main",
+            Self::End => "end",
+            Self::Cleanup => "cleanup",
+        }
+    }
+    #[must_use]
+    pub fn next(self) -> Option<Self> {
+        Some(match self {
+            Self::Untap => Self::Upkeep,
+            Self::Upkeep => Self::Draw,
+            Self::Draw => Self::FirstMain,
+            Self::FirstMain => Self::BeginCombat,
+            Self::BeginCombat => Self::DeclareAttackers,
+            Self::DeclareAttackers => Self::DeclareBlockers,
+            Self::DeclareBlockers => Self::CombatDamage,
+            Self::CombatDamage => Self::EndCombat,
+            Self::EndCombat => Self::SecondMain,
+            Self::SecondMain => Self::End,
+            Self::End => Self::Cleanup,
+            Self::Cleanup => return None,
+        })
+    }
+}
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct Turn {
+    pub active: usize,
+    pub number: u32,
+    pub phase: Phase,
+}
+impl Default for Turn {
+    fn default() -> Self {
+        Self {
+            active: 0,
+            number: 1,
+            phase: Phase::Untap,
+        }
+    }
+}
+impl Turn {
+    fn start(&mut self, seat: usize) {
+        self.active = seat;
+        self.number += 1;
+        self.phase = Phase::Untap;
+    }
+    #[must_use]
+    pub fn label(&self, phases: bool) -> String {
+        if phases {
+            format!("turn {}: P{}, {}", self.number, self.active + 1, self.phase)
+        } else {
+            format!("turn {}: P{}", self.number, self.active + 1)
+        }
+    }
+}
+#[must_use]
+pub fn turn_order(peers: &Peers) -> Vec<usize> {
+    let mut seats: Vec<usize> = peers
+        .peer_to_id
+        .values()
+        .chain(peers.my_id.as_ref())
+        .map(|peer| peer.id)
+        .filter(|seat| *seat < SEATS)
+        .collect();
+    seats.sort_unstable();
+    seats.dedup();
+    seats
+}
+#[must_use]
+pub fn next_seat(peers: &Peers, active: usize) -> usize {
+    let seats = turn_order(peers);
+    seats
+        .iter()
+        .copied()
+        .find(|seat| *seat > active)
+        .or_else(|| seats.first().copied())
+        .unwrap_or(active)
+}
+pub fn do_turn(
+    keybinds: Res<ButtonInput<Keybind>>,
+    peers: Res<Peers>,
+    settings: Res<Settings>,
+    mut turn: ResMut<Turn>,
+    net: Net<Msg>,
+) {
+    let me = peers.my_id.unwrap_or_default().id;
+    if me >= SEATS {
+        return;
+    }
+    let old = *turn;
+    if keybinds.just_pressed(Keybind::TakeTurn) && turn.active != me {
+        turn.start(me);
+    } else if keybinds.just_pressed(Keybind::PassTurn) && turn.active == me {
+        turn.start(next_seat(&peers, me));
+    } else if keybinds.just_pressed(Keybind::NextPhase)
+        && settings.track_phases
+        && turn.active == me
+    {
+        match turn.phase.next() {
+            Some(phase) => turn.phase = phase,
+            None => turn.start(next_seat(&peers, me)),
+        }
+    }
+    if *turn != old {
+        net.broadcast(Msg::Turn(*turn));
+    }
+}
+pub fn update_turn(
+    turn: Res<Turn>,
+    settings: Res<Settings>,
+    mats: Query<(&Mat, &Peer, &Transform, &Children)>,
+    mut piles: Query<&mut Transform, (With<Pile>, Without<Mat>)>,
+    mut materials: Query<&mut MeshMaterial3d<StandardMaterial>>,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+    mut commands: Commands,
+) {
+    if !turn.is_changed() {
+        return;
+    }
+    for (mat, peer, mat_transform, children) in mats {
+        let (from, to) = if peer.id == turn.active {
+            (&mat.material, &mat.highlight)
+        } else {
+            (&mat.highlight, &mat.material)
+        };
+        for child in children {
+            if let Ok(mut material) = materials.get_mut(*child)
+                && material.0 == *from
+            {
+                material.0 = to.clone();
+            }
+        }
+        if peer.id == turn.active
+            && turn.phase == Phase::Untap
+            && settings.auto_untap
+            && !turn.is_added()
+        {
+            for mut transform in &mut piles {
+                if Mat::contains(mat_transform, transform.translation) {
+                    untap(mat_transform, &mut transform);
+                }
+            }
+        }
+    }
+    if !turn.is_added() {
+        commands
+            .entity(*text_chat)
+            .with_child(text_node(turn.label(settings.track_phases)));
+        msgs.write(Scroll::down(*text_chat));
+    }
+}
+fn untap(mat: &Transform, transform: &mut Transform) {
+    let relative = mat.rotation.inverse() * transform.rotation;
+    if !TapState::from(relative).tapped() {
+        return;
+    }
+    let forward = relative * Vec3::Z;
+    transform.rotation = Quat::from_rotation_y(-forward.x.atan2(forward.z)) * transform.rotation;
+}
@@ -1,2 +1,9 @@
 pub mod chat;
 pub mod esc_menu;
+pub mod keybinds;
+pub mod life;
+pub mod mulligan;
+pub mod printings;
+pub mod settings;
+pub mod sort;
+pub mod stats;
@@ -1,5 +1,6 @@
 use crate::events::scroll::{Scroll, Scrollable};
 use crate::keybinds::Keybind;
+use crate::settings::Settings;
 use crate::{FONT_HEIGHT, FONT_SIZE};
 use bevy::color::Color;
 use bevy::input::ButtonInput;
@@ -8,9 +9,11 @@ use bevy::prelude::{BackgroundColor, Event, FlexDirection, Text, Visibility, Win
 use bevy::text::{EditableText, FontSize, TextCursorStyle, TextFont};
 use bevy::ui::{Display, Node, Overflow, PositionType, Val};
 use bevy_ecs::bundle::Bundle;
+use bevy_ecs::change_detection::Ref;
 use bevy_ecs::children;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
 use bevy_ecs::message::MessageWriter;
 use bevy_ecs::observer::On;
 use bevy_ecs::query::With;
@@ -25,6 +28,24 @@ pub struct TextInput;
 #[derive(Component, Clone, Copy)]
 pub enum TextSource {
     Chat,
+    Name,
+}
+#[must_use]
+pub fn text_field(source: TextSource) -> impl Bundle {
+    (
+        EditableText::default(),
+        TextCursorStyle {
+            color: Color::WHITE,
+            selection_color: Color::srgb_u8(53, 132, 228),
+            unfocused_selection_color: Color::srgb_u8(176, 176, 176),
+            selected_text_color: None,
+        },
+        TextFont {
+            font_size: FontSize::Px(FONT_SIZE),
+            ..TextFont::default()
+        },
+        source,
+    )
 }
 #[must_use]
 pub fn chat_bundle() -> impl Bundle {
@@ -49,20 +70,9 @@ pub fn chat_bundle() -> impl Bundle {
                     height: Val::Px(FONT_HEIGHT),
                     ..Node::default()
                 },
-                EditableText::default(),
-                TextCursorStyle {
-                    color: Color::WHITE,
-                    selection_color: Color::srgb_u8(53, 132, 228),
-                    unfocused_selection_color: Color::srgb_u8(176, 176, 176),
-                    selected_text_color: None,
-                },
-                TextFont {
-                    font_size: FontSize::Px(FONT_SIZE),
-                    ..TextFont::default()
-                },
+                text_field(TextSource::Chat),
                 TextInput,
                 Visibility::Inherited,
-                TextSource::Chat
             ),
             (
                 Node {
@@ -109,11 +119,33 @@ pub fn text_message(
     text_chat: Single<Entity, With<TextChat>>,
     mut msgs: MessageWriter<Scroll>,
 ) {
+    if !matches!(event.source, TextSource::Chat) {
+        return;
+    }
     commands
         .entity(*text_chat)
         .with_child(text_node(event.string.clone()));
     msgs.write(Scroll::down(*text_chat));
 }
+pub fn update_chat(
+    settings: Res<Settings>,
+    chat: Single<Ref<Children>, With<TextChat>>,
+    mut fonts: Query<&mut TextFont>,
+    mut commands: Commands,
+) {
+    if !settings.is_changed() && !chat.is_changed() {
+        return;
+    }
+    let excess = chat.len().saturating_sub(settings.chat.history as usize);
+    for child in &chat[..excess] {
+        commands.entity(*child).despawn();
+    }
+    for child in &chat[excess..] {
+        if let Ok(mut font) = fonts.get_mut(*child) {
+            font.font_size = FontSize::Px(settings.chat.font_size as f32);
+        }
+    }
+}
 #[query_fn]
 pub fn text_submission(
     window: Single<Entity, With<Window>>,
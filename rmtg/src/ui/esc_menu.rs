@@ -1,7 +1,15 @@
+use crate::app::Client;
+use crate::clock::on_start_clocks;
 use crate::events::clipboard::{ClipboardEvent, GetClipboard};
+use crate::events::scroll::Scroll;
 use crate::focus::Menu;
 use crate::keybinds::Keybind;
 use crate::net::Msg;
+use crate::profile::color_name;
+use crate::settings::Settings;
+use crate::ui::chat::{TextChat, text_node};
+use crate::ui::keybinds::on_keybinds;
+use crate::ui::settings::on_settings;
 use crate::{ALPN, BUTTON_BACKGROUND, BUTTON_BORDER, BUTTON_HOVER, FONT_SIZE};
 use bevy::app::AppExit;
 use bevy::clipboard::Clipboard;
@@ -18,17 +26,46 @@ use bevy::ui_widgets::{Activate, Button, observe};
 use bevy_ecs::bundle::Bundle;
 use bevy_ecs::children;
 use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
 use bevy_ecs::message::MessageWriter;
 use bevy_ecs::observer::On;
 use bevy_ecs::query::{Changed, With};
-use bevy_ecs::system::{Commands, If, Query, Res, ResMut, Single};
+use bevy_ecs::system::{Commands, If, In, Query, Res, ResMut, Single};
 use bevy_p2p::events::Binded;
 use bevy_p2p::iroh_res::{IrohBind, IrohResource, IrohUnbind};
+use bevy_p2p::runtime::Runtime;
 use bevy_query_fn_macro::query_fn;
+use importer::card_cache::{CacheStats, clear_cache, prune_cache};
+use importer::custom::CustomInfo;
 #[derive(Component)]
 pub struct EscMenu;
 #[derive(Component)]
 pub struct Exit;
+#[derive(Component)]
+pub struct EscPage;
+#[derive(Component, Clone, Copy)]
+pub enum SettingButton {
+    Quality,
+    Compress,
+    RememberArt,
+    DeckFormat,
+    FreeMulligan,
+    TrackPhases,
+    AutoUntap,
+    ClockMinutes,
+    ClockIncrement,
+    RoundMinutes,
+    SpreadLayout,
+    FrameLimit,
+    UiScale,
+    MoveSpeed,
+    ZoomSpeed,
+    Sensitivity,
+    Name,
+    Color,
+    ChatFontSize,
+    ChatHistory,
+}
 #[must_use]
 pub fn esc_menu_bundle() -> impl Bundle {
     (
@@ -56,6 +93,13 @@ pub fn esc_menu_bundle() -> impl Bundle {
                 (button("Copy Endpoint"), observe(on_copy)),
                 (button("Connect To Clipboard"), observe(on_connect)),
                 (button("Disconnect"), observe(on_disconnect)),
+                (button("Paste Custom Card"), observe(on_paste_custom)),
+                (button("Import Deck"), observe(on_import_deck)),
+                (button("Start Clocks"), observe(on_start_clocks)),
+                (button("Settings"), observe(on_settings)),
+                (button("Keybinds"), observe(on_keybinds)),
+                (button("Prune Cache"), observe(on_prune_cache)),
+                (button("Clear Cache"), observe(on_clear_cache)),
                 (button("Exit"), observe(on_exit)),
             ]
         )],
@@ -94,9 +138,99 @@ pub fn on_iroh_bind_copy(
 fn on_connect(_: On<Activate>, mut commands: Commands) {
     commands.trigger(GetClipboard::text(ClipboardEvent::ConnectToEndpoint));
 }
+fn on_paste_custom(_: On<Activate>, mut commands: Commands) {
+    let event = ClipboardEvent::CustomCard(CustomInfo::default());
+    commands.trigger(GetClipboard::image(event));
+}
+fn on_import_deck(_: On<Activate>, mut commands: Commands) {
+    commands.trigger(GetClipboard::text(ClipboardEvent::ImportDeck));
+}
 fn on_disconnect(_: On<Activate>, mut commands: Commands) {
     commands.trigger(IrohUnbind);
 }
+fn minutes(value: u32) -> String {
+    if value == 0 {
+        "off".to_owned()
+    } else {
+        format!("{value}m")
+    }
+}
+fn on_off(value: bool) -> &'static str {
+    if value { "on" } else { "off" }
+}
+#[must_use]
+pub fn setting_label(button: &SettingButton, settings: &Settings) -> String {
+    match button {
+        SettingButton::Quality => format!("Image Quality: {}", settings.quality.name()),
+        SettingButton::Compress => {
+            format!("Compress Textures: {}", on_off(settings.compress_textures))
+        }
+        SettingButton::RememberArt => format!("Remember Art: {}", on_off(settings.remember_art)),
+        SettingButton::DeckFormat => format!("Deck Format: {}", settings.deck_format),
+        SettingButton::FreeMulligan => {
+            format!("Free Mulligan: {}", on_off(settings.free_mulligan))
+        }
+        SettingButton::TrackPhases => format!("Phases: {}", on_off(settings.track_phases)),
+        SettingButton::AutoUntap => format!("Auto Untap: {}", on_off(settings.auto_untap)),
+        SettingButton::ClockMinutes => format!("Clock: {}", minutes(settings.clock_minutes)),
+        SettingButton::ClockIncrement => format!("Increment: {}s", settings.clock_increment),
+        SettingButton::RoundMinutes => format!("Round: {}", minutes(settings.round_minutes)),
+        SettingButton::SpreadLayout => format!("Spread: {}", settings.spread_layout.name()),
+        SettingButton::FrameLimit => match settings.graphics.frame_limit {
+            0 => "Frame Limit: auto".to_owned(),
+            fps => format!("Frame Limit: {fps} fps"),
+        },
+        SettingButton::UiScale => format!("UI Scale: {}%", settings.graphics.ui_scale),
+        SettingButton::MoveSpeed => format!("Move Speed: {}%", settings.camera.move_speed),
+        SettingButton::ZoomSpeed => format!("Zoom Speed: {}%", settings.camera.zoom_speed),
+        SettingButton::Sensitivity => format!("Rotate Speed: {}%", settings.camera.sensitivity),
+        SettingButton::Name if settings.network.name.is_empty() => "Name: none".to_owned(),
+        SettingButton::Name => format!("Name: {}", settings.network.name),
+        SettingButton::Color => format!("Color: {}", color_name(settings.network.color)),
+        SettingButton::ChatFontSize => format!("Chat Font: {}px", settings.chat.font_size),
+        SettingButton::ChatHistory => format!("Chat History: {} lines", settings.chat.history),
+    }
+}
+pub fn update_setting_labels(
+    settings: Res<Settings>,
+    buttons: Query<(&SettingButton, &Children)>,
+    mut text: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (button, children) in buttons {
+        let label = setting_label(button, &settings);
+        for child in children {
+            if let Ok(mut text) = text.get_mut(*child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}
+fn on_prune_cache(_: On<Activate>, client: Res<Client>, runtime: Res<Runtime>) {
+    runtime.spawn_hook(on_cache_stats, prune_cache(client.config.clone()));
+}
+fn on_clear_cache(_: On<Activate>, client: Res<Client>, runtime: Res<Runtime>) {
+    runtime.spawn_hook(on_cache_stats, clear_cache(client.config.clone()));
+}
+fn on_cache_stats(
+    In(stats): In<CacheStats>,
+    mut commands: Commands,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+) {
+    const MIB: u64 = 1 << 20;
+    commands.entity(*text_chat).with_children(|parent| {
+        parent.spawn(text_node(format!(
+            "cache: {} cards, {} MiB / {} MiB",
+            stats.count,
+            stats.bytes / MIB,
+            stats.limit / MIB
+        )));
+    });
+    msgs.write(Scroll::down(*text_chat));
+}
 fn on_exit(_: On<Activate>, mut writer: MessageWriter<AppExit>) {
     writer.write(AppExit::Success);
 }
@@ -106,6 +240,7 @@ pub fn button(str: &str) -> impl Bundle {
             width: Val::Percent(100.0),
             min_width: Val::Percent(100.0),
             height: Val::Px(FONT_SIZE + 4.0),
+            flex_shrink: 0.0,
             ..Node::default()
         },
         BorderColor::all(BUTTON_BORDER),
@@ -0,0 +1,200 @@
+use crate::events::scroll::Scrollable;
+use crate::keybinds::{Bind, Keybind, KeybindsList, save_bind};
+use crate::settings::Settings;
+use crate::ui::chat::text_node;
+use crate::ui::esc_menu::{EscMenu, EscPage, button};
+use crate::{BUTTON_BACKGROUND, BUTTON_BORDER, FONT_SIZE};
+use bevy::color::Color;
+use bevy::input::ButtonInput;
+use bevy::prelude::{
+    BackgroundColor, Component, FlexDirection, KeyCode, MouseButton, Resource, Text, Visibility,
+};
+use bevy::text::{FontSize, TextFont};
+use bevy::ui::{BorderColor, Node, Overflow, PositionType, Val};
+use bevy::ui_widgets::{Activate, Button, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::children;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Query, Res, ResMut, Single};
+use enumset::EnumSet;
+#[derive(Component)]
+pub struct KeybindMenu;
+#[derive(Component, Clone, Copy)]
+pub struct KeybindButton(Keybind);
+#[derive(Resource, Default)]
+pub struct KeybindCapture(pub Option<Keybind>);
+fn keybind_label(keybind: Keybind, keybinds: &KeybindsList, capture: &KeybindCapture) -> String {
+    if capture.0 == Some(keybind) {
+        return format!("{keybind:?}: press new keys");
+    }
+    let conflicts = keybinds.conflicts(keybind);
+    if conflicts.is_empty() {
+        format!("{keybind:?}: {}", keybinds[keybind])
+    } else {
+        let names: Vec<String> = conflicts.iter().map(|other| format!("{other:?}")).collect();
+        format!(
+            "{keybind:?}: {} (conflicts with {})",
+            keybinds[keybind],
+            names.join(", ")
+        )
+    }
+}
+fn keybind_row(label: String, keybind: Keybind) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Row,
+            width: Val::Percent(100.0),
+            flex_shrink: 0.0,
+            ..Node::default()
+        },
+        Visibility::Inherited,
+        children![
+            (
+                Node {
+                    flex_grow: 1.0,
+                    height: Val::Px(FONT_SIZE + 4.0),
+                    ..Node::default()
+                },
+                BorderColor::all(BUTTON_BORDER),
+                BackgroundColor(BUTTON_BACKGROUND),
+                Visibility::Inherited,
+                Button,
+                KeybindButton(keybind),
+                children![(
+                    Node::default(),
+                    Visibility::Inherited,
+                    Text(label),
+                    TextFont {
+                        font_size: FontSize::Px(FONT_SIZE),
+                        ..TextFont::default()
+                    },
+                )],
+                observe(
+                    move |_: On<Activate>, mut capture: ResMut<KeybindCapture>| {
+                        capture.0 = Some(keybind);
+                    }
+                ),
+            ),
+            (
+                Node {
+                    width: Val::Px(FONT_SIZE * 4.0),
+                    height: Val::Px(FONT_SIZE + 4.0),
+                    flex_shrink: 0.0,
+                    ..Node::default()
+                },
+                BorderColor::all(BUTTON_BORDER),
+                BackgroundColor(BUTTON_BACKGROUND),
+                Visibility::Inherited,
+                Button,
+                children![text_node("reset".to_owned())],
+                observe(move |_: On<Activate>, mut settings: ResMut<Settings>| {
+                    settings.keybinds.retain(|saved| saved.keybind != keybind);
+                }),
+            ),
+        ],
+    )
+}
+pub fn on_keybinds(
+    _: On<Activate>,
+    esc: Single<Entity, With<EscMenu>>,
+    menus: Query<(), With<KeybindMenu>>,
+    pages: Query<Entity, With<EscPage>>,
+    keybinds: Res<KeybindsList>,
+    mut capture: ResMut<KeybindCapture>,
+    mut commands: Commands,
+) {
+    capture.0 = None;
+    for entity in pages {
+        commands.entity(entity).despawn();
+    }
+    if !menus.is_empty() {
+        return;
+    }
+    let list = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                flex_grow: 1.0,
+                overflow: Overflow::scroll_y(),
+                ..Node::default()
+            },
+            Visibility::Inherited,
+            Scrollable,
+        ))
+        .with_children(|parent| {
+            for keybind in EnumSet::<Keybind>::all() {
+                let label = keybind_label(keybind, &keybinds, &capture);
+                parent.spawn(keybind_row(label, keybind));
+            }
+        })
+        .id();
+    let panel = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.0),
+                height: Val::Percent(90.0),
+                left: Val::Percent(57.5),
+                top: Val::Percent(5.0),
+                position_type: PositionType::Absolute,
+                ..Node::default()
+            },
+            KeybindMenu,
+            EscPage,
+            Visibility::Inherited,
+            BackgroundColor(Color::srgba_u8(0, 0, 0, 128)),
+            children![
+                text_node("click a binding then press the new keys, escape cancels".to_owned()),
+                (button("Reset All"), observe(on_reset_all)),
+            ],
+        ))
+        .add_child(list)
+        .id();
+    commands.entity(*esc).add_child(panel);
+}
+fn on_reset_all(
+    _: On<Activate>,
+    mut settings: ResMut<Settings>,
+    mut capture: ResMut<KeybindCapture>,
+) {
+    settings.keybinds.clear();
+    capture.0 = None;
+}
+pub fn capture_keybind(
+    mut capture: ResMut<KeybindCapture>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut settings: ResMut<Settings>,
+) {
+    let Some(keybind) = capture.0 else {
+        return;
+    };
+    if keyboard.just_pressed(KeyCode::Escape) {
+        capture.0 = None;
+    } else if let Some(bind) = Bind::new_from(&keyboard, &mouse) {
+        save_bind(&mut settings.keybinds, keybind, &bind);
+        capture.0 = None;
+    }
+}
+pub fn update_keybind_labels(
+    keybinds: Res<KeybindsList>,
+    capture: Res<KeybindCapture>,
+    buttons: Query<(&KeybindButton, &Children)>,
+    mut text: Query<&mut Text>,
+) {
+    if !keybinds.is_changed() && !capture.is_changed() {
+        return;
+    }
+    for (button, children) in buttons {
+        let label = keybind_label(button.0, &keybinds, &capture);
+        for child in children {
+            if let Ok(mut text) = text.get_mut(*child) {
+                text.0.clone_from(&label);
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::card_spot::{CardSpot, SpotType};
+use crate::life::{AdjustCounter, Counter, ResetTracker, SEATS, Trackers};
+use crate::net::{Peer, Peers};
+use crate::pile::Pile;
+use crate::profile::Profiles;
+use crate::settings::Settings;
+use crate::turn::Turn;
+use crate::ui::chat::text_node;
+use crate::ui::esc_menu::button;
+use crate::{BUTTON_BACKGROUND, BUTTON_BORDER, CARD_WIDTH, FONT_SIZE};
+use bevy::color::Color;
+use bevy::math::Vec3Swizzles as _;
+use bevy::prelude::{BackgroundColor, Component, FlexDirection, GlobalTransform, Text, Visibility};
+use bevy::text::{FontSize, TextFont};
+use bevy::ui::{BorderColor, Node, PositionType, Val};
+use bevy::ui_widgets::{Activate, Button, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::children;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Local, Query, Res, Single};
+type Names = [[Option<String>; 2]; SEATS];
+#[derive(Component)]
+pub struct LifeHud;
+#[must_use]
+pub fn life_hud_bundle() -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            width: Val::Percent(20.0),
+            left: Val::Percent(0.0),
+            top: Val::Percent(0.0),
+            position_type: PositionType::Absolute,
+            ..Node::default()
+        },
+        LifeHud,
+        Visibility::Visible,
+        BackgroundColor(Color::srgba_u8(0, 0, 0, 64)),
+    )
+}
+fn commander_names(
+    spots: &Query<(&CardSpot, &Peer, &GlobalTransform)>,
+    piles: &Query<(&Pile, &GlobalTransform)>,
+) -> Names {
+    let mut names = Names::default();
+    for (spot, peer, spot_transform) in spots {
+        let alt = match spot.spot_type {
+            SpotType::CommanderMain => false,
+            SpotType::CommanderAlt => true,
+            _ => continue,
+        };
+        let center = spot_transform.translation().xz();
+        names[peer.id][usize::from(alt)] = piles
+            .iter()
+            .find(|(_, transform)| transform.translation().xz().distance(center) < CARD_WIDTH / 2.0)
+            .map(|(pile, transform)| {
+                pile.get_card(transform.rotation())
+                    .data
+                    .front
+                    .name
+                    .to_string()
+            });
+    }
+    names
+}
+fn step_button(label: &'static str, seat: usize, counter: Counter, delta: i32) -> impl Bundle {
+    (
+        Node {
+            width: Val::Px(FONT_SIZE * 2.0),
+            height: Val::Px(FONT_SIZE + 4.0),
+            flex_shrink: 0.0,
+            ..Node::default()
+        },
+        BorderColor::all(BUTTON_BORDER),
+        BackgroundColor(BUTTON_BACKGROUND),
+        Visibility::Inherited,
+        Button,
+        children![(
+            Node::default(),
+            Visibility::Inherited,
+            Text::new(label),
+            TextFont {
+                font_size: FontSize::Px(FONT_SIZE),
+                ..TextFont::default()
+            },
+        )],
+        observe(move |_: On<Activate>, mut commands: Commands| {
+            commands.trigger(AdjustCounter {
+                seat,
+                counter,
+                delta,
+            });
+        }),
+    )
+}
+fn counter_row(label: String, seat: usize, counter: Counter) -> impl Bundle {
+    (
+        Node {
+            flex_direction: FlexDirection::Row,
+            width: Val::Percent(100.0),
+            flex_shrink: 0.0,
+            ..Node::default()
+        },
+        Visibility::Inherited,
+        children![
+            (
+                Node {
+                    flex_grow: 1.0,
+                    ..Node::default()
+                },
+                Visibility::Inherited,
+                Text(label),
+                TextFont {
+                    font_size: FontSize::Px(FONT_SIZE),
+                    ..TextFont::default()
+                },
+            ),
+            step_button("-", seat, counter, -1),
+            step_button("+", seat, counter, 1),
+        ],
+    )
+}
+pub fn update_life_hud(
+    trackers: Res<Trackers>,
+    peers: Res<Peers>,
+    turn: Res<Turn>,
+    settings: Res<Settings>,
+    profiles: Res<Profiles>,
+    spots: Query<(&CardSpot, &Peer, &GlobalTransform)>,
+    piles: Query<(&Pile, &GlobalTransform)>,
+    hud: Single<Entity, With<LifeHud>>,
+    mut last: Local<Names>,
+    mut commands: Commands,
+) {
+    let names = commander_names(&spots, &piles);
+    if !trackers.is_changed()
+        && !peers.is_changed()
+        && !turn.is_changed()
+        && !settings.is_changed()
+        && !profiles.is_changed()
+        && names == *last
+    {
+        return;
+    }
+    let me = peers.my_id.unwrap_or_default().id;
+    let tracker = trackers.seats.get(me);
+    commands
+        .entity(*hud)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            parent.spawn(text_node(turn.label(settings.track_phases)));
+            for (seat, (other, profile)) in trackers.seats.iter().zip(&profiles.seats).enumerate() {
+                let you = if seat == me { " (you)" } else { "" };
+                parent.spawn(text_node(format!(
+                    "{}{you}: {}",
+                    profile.label(seat),
+                    other.summary()
+                )));
+            }
+            let Some(tracker) = tracker else {
+                parent.spawn(text_node("spectating".to_owned()));
+                return;
+            };
+            for counter in Counter::PLAYER {
+                let label = format!("{}: {}", counter.name(), tracker.get(counter));
+                parent.spawn(counter_row(label, me, counter));
+            }
+            parent.spawn(text_node("commander damage taken:".to_owned()));
+            for seat in (0..SEATS).filter(|seat| *seat != me) {
+                for alt in [false, true] {
+                    let counter = Counter::Commander { seat, alt };
+                    let damage = tracker.get(counter);
+                    let name = &names[seat][usize::from(alt)];
+                    if alt && name.is_none() && damage == 0 {
+                        continue;
+                    }
+                    let source = name
+                        .clone()
+                        .unwrap_or_else(|| format!("P{} {}", seat + 1, counter.name()));
+                    parent.spawn(counter_row(format!("{source}: {damage}"), me, counter));
+                }
+            }
+            parent.spawn((button("Reset"), observe(on_reset)));
+        });
+    *last = names;
+}
+fn on_reset(_: On<Activate>, peers: Res<Peers>, mut commands: Commands) {
+    commands.trigger(ResetTracker {
+        seat: peers.my_id.unwrap_or_default().id,
+    });
+}
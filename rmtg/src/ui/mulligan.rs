@@ -0,0 +1,303 @@
+use crate::events::hover::HoveredObject;
+use crate::events::move_up::MoveUp;
+use crate::events::repaint::Repaint;
+use crate::focus::Menu;
+use crate::keybinds::Keybind;
+use crate::pile::{FlippedState, Pile};
+use crate::settings::Settings;
+use crate::ui::chat::text_node;
+use crate::ui::esc_menu::{SettingButton, button, setting_label};
+use crate::{BUTTON_BACKGROUND, BUTTON_BORDER, CARD_HEIGHT, CARD_WIDTH, IMAGE_HEIGHT, IMAGE_WIDTH};
+use bevy::color::Color;
+use bevy::input::ButtonInput;
+use bevy::math::{Quat, Vec3};
+use bevy::prelude::{
+    BackgroundColor, Component, FlexDirection, FlexWrap, Resource, Transform, Visibility,
+};
+use bevy::ui::widget::ImageNode;
+use bevy::ui::{BorderColor, Node, Overflow, PositionType, Val};
+use bevy::ui_widgets::{Activate, Button, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::children;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Query, Res, ResMut};
+use importer::card::SubCard;
+use std::f32::consts::PI;
+use std::mem;
+pub const HAND_SIZE: usize = 7;
+#[derive(Component)]
+pub struct MulliganMenu;
+#[derive(Resource)]
+pub struct Mulligan {
+    pub library: Entity,
+    pub hand: Vec<SubCard>,
+    pub mulligans: usize,
+    pub bottom: Vec<usize>,
+    pub choosing: bool,
+}
+impl Mulligan {
+    fn to_bottom(&self, free: bool) -> usize {
+        self.mulligans
+            .saturating_sub(usize::from(free))
+            .min(self.hand.len())
+    }
+}
+pub fn do_mulligan(
+    hovered: Query<(Entity, &Pile), With<HoveredObject>>,
+    keybinds: Res<ButtonInput<Keybind>>,
+    mulligan: Option<Res<Mulligan>>,
+    mut commands: Commands,
+) {
+    if !keybinds.just_pressed(Keybind::Mulligan) || mulligan.is_some() {
+        return;
+    }
+    let Some((library, pile)) = hovered.iter().next() else {
+        return;
+    };
+    if pile.len() <= HAND_SIZE {
+        return;
+    }
+    commands.insert_resource(Mulligan {
+        library,
+        hand: Vec::new(),
+        mulligans: 0,
+        bottom: Vec::new(),
+        choosing: false,
+    });
+    commands.run_system_cached(draw_hand);
+}
+fn return_hand(mulligan: &mut Mulligan, pile: &mut Pile) {
+    if !mulligan.hand.is_empty() {
+        pile.extend(Pile::new(mem::take(&mut mulligan.hand)));
+    }
+    mulligan.bottom.clear();
+}
+fn draw_hand(
+    mut mulligan: ResMut<Mulligan>,
+    mut piles: Query<(&mut Pile, &Transform)>,
+    mut commands: Commands,
+) {
+    let Ok((mut pile, transform)) = piles.get_mut(mulligan.library) else {
+        commands.run_system_cached(close_mulligan);
+        return;
+    };
+    return_hand(&mut mulligan, &mut pile);
+    pile.shuffle();
+    mulligan.hand = pile.take_n_card(transform.rotation, HAND_SIZE);
+    commands.trigger(Repaint::new(mulligan.library));
+    commands.run_system_cached(show_mulligan);
+}
+fn show_mulligan(
+    mulligan: Res<Mulligan>,
+    settings: Res<Settings>,
+    menus: Query<Entity, With<MulliganMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    let to_bottom = mulligan.to_bottom(settings.free_mulligan);
+    let grid = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_grow: 1.0,
+                flex_wrap: FlexWrap::Wrap,
+                overflow: Overflow::scroll_y(),
+                ..Node::default()
+            },
+            Visibility::Inherited,
+        ))
+        .with_children(|parent| {
+            for (i, card) in mulligan.hand.iter().enumerate() {
+                let order = mulligan.bottom.iter().position(|b| *b == i);
+                let mut option = parent.spawn(hand_card_bundle(i, card, order));
+                if let Some(handles) = card.face_handles() {
+                    option.insert(ImageNode::new(handles.image));
+                }
+            }
+        })
+        .id();
+    let free = SettingButton::FreeMulligan;
+    let label = setting_label(&free, &settings);
+    let panel = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.0),
+                height: Val::Percent(75.0),
+                right: Val::Percent(0.0),
+                top: Val::Percent(0.0),
+                position_type: PositionType::Absolute,
+                ..Node::default()
+            },
+            MulliganMenu,
+            Visibility::Visible,
+            BackgroundColor(Color::srgba_u8(0, 0, 0, 128)),
+            children![(button(&label), free, observe(on_free_mulligan))],
+        ))
+        .id();
+    commands.entity(panel).with_children(|parent| {
+        if mulligan.choosing {
+            parent.spawn(text_node(format!(
+                "choose {to_bottom} card(s) for the bottom, first chosen goes deepest"
+            )));
+            if mulligan.bottom.len() == to_bottom {
+                parent.spawn((button("Confirm"), observe(on_confirm)));
+            }
+        } else {
+            parent.spawn(text_node(format!(
+                "mulligans: {}, keeping {}",
+                mulligan.mulligans,
+                mulligan.hand.len() - to_bottom
+            )));
+            parent.spawn((button("Keep"), observe(on_keep)));
+            parent.spawn((button("Mulligan"), observe(on_mulligan)));
+        }
+        parent.spawn((button("Cancel"), observe(on_cancel)));
+    });
+    commands.entity(panel).add_child(grid);
+    *menu = Menu::Side;
+}
+fn hand_card_bundle(index: usize, card: &SubCard, order: Option<usize>) -> impl Bundle {
+    let label = match order {
+        Some(order) => format!("{} ({})", card.data.front.name, order + 1),
+        None if card.face_handles().is_some() => String::new(),
+        None => card.data.front.name.to_string(),
+    };
+    (
+        Node {
+            width: Val::Percent(100.0 / 3.0),
+            aspect_ratio: Some(IMAGE_WIDTH / IMAGE_HEIGHT),
+            ..Node::default()
+        },
+        BorderColor::all(BUTTON_BORDER),
+        BackgroundColor(BUTTON_BACKGROUND),
+        Visibility::Inherited,
+        Button,
+        children![text_node(label)],
+        observe(
+            move |_: On<Activate>,
+                  mut mulligan: ResMut<Mulligan>,
+                  settings: Res<Settings>,
+                  mut commands: Commands| {
+                if !mulligan.choosing {
+                    return;
+                }
+                if let Some(i) = mulligan.bottom.iter().position(|b| *b == index) {
+                    mulligan.bottom.remove(i);
+                } else if mulligan.bottom.len() < mulligan.to_bottom(settings.free_mulligan) {
+                    mulligan.bottom.push(index);
+                }
+                commands.run_system_cached(show_mulligan);
+            },
+        ),
+    )
+}
+fn on_free_mulligan(
+    _: On<Activate>,
+    mut mulligan: ResMut<Mulligan>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+) {
+    settings.free_mulligan = !settings.free_mulligan;
+    let to_bottom = mulligan.to_bottom(settings.free_mulligan);
+    mulligan.bottom.truncate(to_bottom);
+    commands.run_system_cached(show_mulligan);
+}
+fn on_mulligan(_: On<Activate>, mut mulligan: ResMut<Mulligan>, mut commands: Commands) {
+    mulligan.mulligans += 1;
+    commands.run_system_cached(draw_hand);
+}
+fn on_keep(
+    _: On<Activate>,
+    mut mulligan: ResMut<Mulligan>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    if mulligan.to_bottom(settings.free_mulligan) == 0 {
+        commands.run_system_cached(keep_hand);
+    } else {
+        mulligan.choosing = true;
+        commands.run_system_cached(show_mulligan);
+    }
+}
+fn on_confirm(_: On<Activate>, mut commands: Commands) {
+    commands.run_system_cached(keep_hand);
+}
+fn on_cancel(
+    _: On<Activate>,
+    mut mulligan: ResMut<Mulligan>,
+    mut piles: Query<&mut Pile>,
+    mut commands: Commands,
+) {
+    if let Ok(mut pile) = piles.get_mut(mulligan.library) {
+        return_hand(&mut mulligan, &mut pile);
+        pile.shuffle();
+        commands.trigger(Repaint::new(mulligan.library));
+    }
+    commands.run_system_cached(close_mulligan);
+}
+fn keep_hand(
+    mut mulligan: ResMut<Mulligan>,
+    mut piles: Query<(&mut Pile, &Transform)>,
+    mut commands: Commands,
+) {
+    let Ok((mut pile, transform)) = piles.get_mut(mulligan.library) else {
+        commands.run_system_cached(close_mulligan);
+        return;
+    };
+    let mut hand: Vec<Option<SubCard>> = mem::take(&mut mulligan.hand)
+        .into_iter()
+        .map(Some)
+        .collect();
+    let mut bottom: Vec<SubCard> = mulligan
+        .bottom
+        .iter()
+        .filter_map(|i| hand.get_mut(*i).and_then(Option::take))
+        .collect();
+    if !bottom.is_empty() {
+        if FlippedState::from(transform.rotation).flipped() {
+            bottom.reverse();
+            let len = pile.len();
+            pile.splice_at(len, Pile::new(bottom));
+        } else {
+            pile.splice_at(0, Pile::new(bottom));
+        }
+        commands.trigger(Repaint::new(mulligan.library));
+    }
+    let origin = transform.translation;
+    for (i, card) in hand.into_iter().flatten().enumerate() {
+        let single = Pile::from(card);
+        let offset = Vec3::new(
+            (i as f32 - HAND_SIZE as f32 / 2.0) * CARD_WIDTH * 1.1,
+            0.0,
+            CARD_HEIGHT * 1.25,
+        );
+        let ent = commands
+            .spawn((
+                Transform::from_translation(origin + offset)
+                    .with_rotation(Quat::from_rotation_z(PI)),
+                single.bundle(),
+            ))
+            .id();
+        commands.trigger(MoveUp::new(ent));
+    }
+    commands.run_system_cached(close_mulligan);
+}
+fn close_mulligan(
+    menus: Query<Entity, With<MulliganMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<Mulligan>();
+    if *menu == Menu::Side {
+        *menu = Menu::World;
+    }
+}
@@ -0,0 +1,218 @@
+use crate::app::Client;
+use crate::events::hover::HoveredObject;
+use crate::events::repaint::Repaint;
+use crate::events::scroll::{Scroll, Scrollable};
+use crate::focus::Menu;
+use crate::keybinds::Keybind;
+use crate::pile::{PendingCards, Pile};
+use crate::settings::Settings;
+use crate::ui::chat::{TextChat, text_node};
+use crate::ui::esc_menu::{SettingButton, button, setting_label};
+use crate::{BUTTON_BACKGROUND, BUTTON_BORDER, IMAGE_HEIGHT, IMAGE_WIDTH};
+use bevy::color::Color;
+use bevy::input::ButtonInput;
+use bevy::log::warn;
+use bevy::prelude::{
+    BackgroundColor, Component, Event, FlexDirection, FlexWrap, Transform, Visibility,
+};
+use bevy::ui::widget::ImageNode;
+use bevy::ui::{BorderColor, Node, Overflow, PositionType, Val};
+use bevy::ui_widgets::{Activate, Button, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::children;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::hierarchy::Children;
+use bevy_ecs::message::MessageWriter;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::{With, Without};
+use bevy_ecs::system::{Commands, In, Query, Res, ResMut, Single};
+use bevy_p2p::runtime::Runtime;
+use importer::card::{MaybeHandles, SubCard};
+use importer::custom::is_custom;
+use importer::scryfall::{CACHE, CardError};
+use importer::uuid::Uuid;
+#[derive(Component)]
+pub struct PrintingsMenu;
+#[derive(Component)]
+pub struct PrintingOption {
+    pub id: Uuid,
+}
+#[derive(Event)]
+pub struct PickPrinting {
+    pub target: Entity,
+    pub card: SubCard,
+}
+type Prints = Result<Vec<Result<SubCard, CardError<Uuid>>>, CardError<Uuid>>;
+pub fn do_printings(
+    hovered: Query<(Entity, &Pile, &Transform), With<HoveredObject>>,
+    keybinds: Res<ButtonInput<Keybind>>,
+    client: Res<Client>,
+    runtime: Res<Runtime>,
+    settings: Res<Settings>,
+) {
+    if !keybinds.just_pressed(Keybind::Printings) {
+        return;
+    }
+    let Some((target, pile, transform)) = hovered.iter().next() else {
+        return;
+    };
+    let data = &pile.get_card(transform.rotation).data;
+    if is_custom(&data.set_cn) {
+        return;
+    }
+    let oracle = data.front.oracle_id;
+    let config = client.config.clone();
+    let quality = settings.quality;
+    runtime.spawn_hook(on_printings, async move {
+        (target, SubCard::get_prints(config, oracle, quality).await)
+    });
+}
+fn on_printings(
+    In((target, prints)): In<(Entity, Prints)>,
+    mut commands: Commands,
+    menus: Query<Entity, With<PrintingsMenu>>,
+    settings: Res<Settings>,
+    mut menu: ResMut<Menu>,
+    text_chat: Single<Entity, With<TextChat>>,
+    mut msgs: MessageWriter<Scroll>,
+) {
+    let cards: Vec<SubCard> = match prints {
+        Ok(cards) => cards
+            .into_iter()
+            .filter_map(|card| card.inspect_err(|e| warn!("{e}")).ok())
+            .collect(),
+        Err(e) => {
+            warn!("{e}");
+            commands
+                .entity(*text_chat)
+                .with_child(text_node(format!("failed to load printings: {e}")));
+            msgs.write(Scroll::down(*text_chat));
+            return;
+        }
+    };
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    let grid = commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_grow: 1.0,
+                flex_wrap: FlexWrap::Wrap,
+                overflow: Overflow::scroll_y(),
+                ..Node::default()
+            },
+            Visibility::Inherited,
+            Scrollable,
+        ))
+        .with_children(|parent| {
+            for card in cards {
+                parent.spawn(option_bundle(target, card));
+            }
+        })
+        .id();
+    let remember = SettingButton::RememberArt;
+    let label = setting_label(&remember, &settings);
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.0),
+                height: Val::Percent(75.0),
+                right: Val::Percent(0.0),
+                top: Val::Percent(0.0),
+                position_type: PositionType::Absolute,
+                ..Node::default()
+            },
+            PrintingsMenu,
+            Visibility::Visible,
+            BackgroundColor(Color::srgba_u8(0, 0, 0, 128)),
+            children![
+                (button(&label), remember, observe(on_remember_art)),
+                (button("Close"), observe(on_close)),
+            ],
+        ))
+        .add_child(grid);
+    *menu = Menu::Side;
+}
+fn option_bundle(target: Entity, card: SubCard) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.0 / 3.0),
+            aspect_ratio: Some(IMAGE_WIDTH / IMAGE_HEIGHT),
+            ..Node::default()
+        },
+        BorderColor::all(BUTTON_BORDER),
+        BackgroundColor(BUTTON_BACKGROUND),
+        Visibility::Inherited,
+        Button,
+        PrintingOption { id: card.data.id },
+        children![text_node(card.data.set_cn.to_string())],
+        observe(move |_: On<Activate>, mut commands: Commands| {
+            commands.trigger(PickPrinting {
+                target,
+                card: card.clone(),
+            });
+        }),
+    )
+}
+pub fn update_printing_thumbnails(
+    options: Query<(Entity, &PrintingOption), Without<ImageNode>>,
+    mut commands: Commands,
+) {
+    if options.is_empty() {
+        return;
+    }
+    let cache = CACHE.blocking_lock();
+    for (entity, option) in options {
+        if let Some(card) = cache.cards.get(&option.id)
+            && let MaybeHandles::Some(handles) = &card.face_handles
+        {
+            commands
+                .entity(entity)
+                .despawn_related::<Children>()
+                .insert(ImageNode::new(handles.image.clone()));
+        }
+    }
+}
+pub fn on_pick_printing(
+    event: On<PickPrinting>,
+    mut piles: Query<(&mut Pile, &Transform)>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+) {
+    let new = &event.card;
+    if let Ok((mut pile, transform)) = piles.get_mut(event.target) {
+        let card = pile.get_mut_card(transform.rotation);
+        *card = SubCard {
+            flipped: card.flipped && new.data.back.is_some(),
+            ..new.clone()
+        };
+        commands.entity(event.target).insert(PendingCards);
+        commands.trigger(Repaint::new(event.target));
+    }
+    if settings.remember_art {
+        settings
+            .art_preferences
+            .insert(new.data.front.oracle_id, new.data.id);
+    }
+    commands.run_system_cached(close_printings);
+}
+fn on_remember_art(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.remember_art = !settings.remember_art;
+}
+fn on_close(_: On<Activate>, mut commands: Commands) {
+    commands.run_system_cached(close_printings);
+}
+fn close_printings(
+    menus: Query<Entity, With<PrintingsMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    if *menu == Menu::Side {
+        *menu = Menu::World;
+    }
+}
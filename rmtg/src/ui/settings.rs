@@ -0,0 +1,219 @@
+use crate::FONT_HEIGHT;
+use crate::events::scroll::Scrollable;
+use crate::settings::{Settings, next_color, next_format, next_option, next_quality, next_step};
+use crate::ui::chat::{TextSource, text_field, text_node};
+use crate::ui::esc_menu::{EscMenu, EscPage, SettingButton, button, setting_label};
+use crate::ui::keybinds::KeybindCapture;
+use bevy::color::Color;
+use bevy::input_focus::{FocusCause, InputFocus};
+use bevy::prelude::{BackgroundColor, Component, FlexDirection, Visibility};
+use bevy::ui::{Node, Overflow, PositionType, Val};
+use bevy::ui_widgets::{Activate, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Query, Res, ResMut, Single};
+#[derive(Component)]
+pub struct SettingsMenu;
+#[derive(Component)]
+struct NameInput;
+const SPEEDS: [u32; 5] = [50, 75, 100, 150, 200];
+fn setting(kind: SettingButton, settings: &Settings) -> impl Bundle {
+    (button(&setting_label(&kind, settings)), kind)
+}
+pub fn on_settings(
+    _: On<Activate>,
+    esc: Single<Entity, With<EscMenu>>,
+    menus: Query<(), With<SettingsMenu>>,
+    pages: Query<Entity, With<EscPage>>,
+    settings: Res<Settings>,
+    mut capture: ResMut<KeybindCapture>,
+    mut commands: Commands,
+) {
+    capture.0 = None;
+    for entity in pages {
+        commands.entity(entity).despawn();
+    }
+    if !menus.is_empty() {
+        return;
+    }
+    let list = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                flex_grow: 1.0,
+                overflow: Overflow::scroll_y(),
+                ..Node::default()
+            },
+            Visibility::Inherited,
+            Scrollable,
+        ))
+        .with_children(|parent| {
+            parent.spawn(text_node("game".to_owned()));
+            parent.spawn((
+                setting(SettingButton::DeckFormat, &settings),
+                observe(on_deck_format),
+            ));
+            parent.spawn((
+                setting(SettingButton::TrackPhases, &settings),
+                observe(on_track_phases),
+            ));
+            parent.spawn((
+                setting(SettingButton::AutoUntap, &settings),
+                observe(on_auto_untap),
+            ));
+            parent.spawn((
+                setting(SettingButton::ClockMinutes, &settings),
+                observe(on_clock_minutes),
+            ));
+            parent.spawn((
+                setting(SettingButton::ClockIncrement, &settings),
+                observe(on_clock_increment),
+            ));
+            parent.spawn((
+                setting(SettingButton::RoundMinutes, &settings),
+                observe(on_round_minutes),
+            ));
+            parent.spawn((
+                setting(SettingButton::SpreadLayout, &settings),
+                observe(on_spread_layout),
+            ));
+            parent.spawn(text_node("graphics".to_owned()));
+            parent.spawn((
+                setting(SettingButton::Quality, &settings),
+                observe(on_quality),
+            ));
+            parent.spawn((
+                setting(SettingButton::Compress, &settings),
+                observe(on_compress),
+            ));
+            parent.spawn((
+                setting(SettingButton::FrameLimit, &settings),
+                observe(on_frame_limit),
+            ));
+            parent.spawn((
+                setting(SettingButton::UiScale, &settings),
+                observe(on_ui_scale),
+            ));
+            parent.spawn(text_node("camera".to_owned()));
+            parent.spawn((
+                setting(SettingButton::MoveSpeed, &settings),
+                observe(on_move_speed),
+            ));
+            parent.spawn((
+                setting(SettingButton::ZoomSpeed, &settings),
+                observe(on_zoom_speed),
+            ));
+            parent.spawn((
+                setting(SettingButton::Sensitivity, &settings),
+                observe(on_sensitivity),
+            ));
+            parent.spawn(text_node("network".to_owned()));
+            parent.spawn((setting(SettingButton::Name, &settings), observe(on_name)));
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(FONT_HEIGHT),
+                    flex_shrink: 0.0,
+                    ..Node::default()
+                },
+                BackgroundColor(Color::srgba_u8(0, 0, 0, 32)),
+                text_field(TextSource::Name),
+                NameInput,
+                Visibility::Inherited,
+            ));
+            parent.spawn((setting(SettingButton::Color, &settings), observe(on_color)));
+            parent.spawn(text_node("audio".to_owned()));
+            parent.spawn(text_node("the game has no sound".to_owned()));
+            parent.spawn(text_node("chat".to_owned()));
+            parent.spawn((
+                setting(SettingButton::ChatFontSize, &settings),
+                observe(on_chat_font_size),
+            ));
+            parent.spawn((
+                setting(SettingButton::ChatHistory, &settings),
+                observe(on_chat_history),
+            ));
+        })
+        .id();
+    let panel = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.0),
+                height: Val::Percent(90.0),
+                left: Val::Percent(57.5),
+                top: Val::Percent(5.0),
+                position_type: PositionType::Absolute,
+                ..Node::default()
+            },
+            SettingsMenu,
+            EscPage,
+            Visibility::Inherited,
+            BackgroundColor(Color::srgba_u8(0, 0, 0, 128)),
+        ))
+        .add_child(list)
+        .id();
+    commands.entity(*esc).add_child(panel);
+}
+fn on_quality(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.quality = next_quality(settings.quality);
+}
+fn on_compress(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.compress_textures = !settings.compress_textures;
+}
+fn on_deck_format(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.deck_format = next_format(settings.deck_format);
+}
+fn on_track_phases(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.track_phases = !settings.track_phases;
+}
+fn on_auto_untap(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.auto_untap = !settings.auto_untap;
+}
+fn on_clock_minutes(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.clock_minutes = next_option(settings.clock_minutes, &[10, 15, 20, 25, 30, 45, 60]);
+}
+fn on_clock_increment(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.clock_increment = next_option(settings.clock_increment, &[5, 10, 15, 30]);
+}
+fn on_round_minutes(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.round_minutes = next_option(settings.round_minutes, &[30, 50, 60, 80]);
+}
+fn on_spread_layout(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.spread_layout = settings.spread_layout.next();
+}
+fn on_frame_limit(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.graphics.frame_limit =
+        next_option(settings.graphics.frame_limit, &[30, 60, 120, 144, 240]);
+}
+fn on_ui_scale(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.graphics.ui_scale = next_step(settings.graphics.ui_scale, &[75, 100, 125, 150, 200]);
+}
+fn on_move_speed(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.camera.move_speed = next_step(settings.camera.move_speed, &SPEEDS);
+}
+fn on_zoom_speed(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.camera.zoom_speed = next_step(settings.camera.zoom_speed, &SPEEDS);
+}
+fn on_sensitivity(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.camera.sensitivity = next_step(settings.camera.sensitivity, &SPEEDS);
+}
+fn on_name(
+    _: On<Activate>,
+    field: Single<Entity, With<NameInput>>,
+    mut active_input: ResMut<InputFocus>,
+) {
+    active_input.set(*field, FocusCause::Pressed);
+}
+fn on_color(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.network.color = next_color(settings.network.color);
+}
+fn on_chat_font_size(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.chat.font_size = next_step(settings.chat.font_size, &[12, 14, 16, 20, 24]);
+}
+fn on_chat_history(_: On<Activate>, mut settings: ResMut<Settings>) {
+    settings.chat.history = next_step(settings.chat.history, &[50, 100, 200, 500, 1000]);
+}
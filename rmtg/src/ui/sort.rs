@@ -0,0 +1,197 @@
+use crate::events::hover::HoveredObject;
+use crate::events::repaint::Repaint;
+use crate::events::spread::reading_order;
+use crate::focus::Menu;
+use crate::keybinds::Keybind;
+use crate::pile::{FlippedState, Pile};
+use crate::settings::Settings;
+use crate::spatial::Spatial;
+use crate::ui::chat::text_node;
+use crate::ui::esc_menu::button;
+use bevy::color::Color;
+use bevy::input::ButtonInput;
+use bevy::math::Quat;
+use bevy::prelude::{BackgroundColor, Component, FlexDirection, Resource, Transform, Visibility};
+use bevy::ui::{Node, PositionType, Val};
+use bevy::ui_widgets::{Activate, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Query, Res, ResMut};
+use importer::sort::{SortBy, SortKey, compare};
+use std::mem;
+#[derive(Component)]
+pub struct SortMenu;
+#[derive(Resource)]
+pub struct SortTargets(Vec<Entity>);
+fn sort_piles(
+    targets: &[Entity],
+    keys: &[SortBy],
+    piles: &mut Query<(&mut Pile, &Transform)>,
+    camera: Quat,
+    commands: &mut Commands,
+) {
+    let default = [SortBy::new(SortKey::Name, false)];
+    let keys = if keys.is_empty() { &default[..] } else { keys };
+    let mut hand: Vec<(Entity, Transform)> = targets
+        .iter()
+        .filter_map(|entity| {
+            let (pile, transform) = piles.get(*entity).ok()?;
+            (pile.len() == 1).then_some((*entity, *transform))
+        })
+        .collect();
+    if hand.len() > 1 && hand.len() == targets.len() {
+        hand.sort_by(|(_, a), (_, b)| reading_order(camera, a, b));
+        let mut cards: Vec<Pile> = hand
+            .iter()
+            .map(|(entity, _)| mem::take(&mut *piles.get_mut(*entity).unwrap().0))
+            .collect();
+        cards.sort_by(|a, b| compare(keys, &a.first().data, &b.first().data));
+        for ((entity, _), card) in hand.into_iter().zip(cards) {
+            *piles.get_mut(entity).unwrap().0 = card;
+            commands.trigger(Repaint::new(entity));
+        }
+        return;
+    }
+    for entity in targets {
+        let Ok((mut pile, transform)) = piles.get_mut(*entity) else {
+            continue;
+        };
+        if pile.len() <= 1 {
+            continue;
+        }
+        if FlippedState::from(transform.rotation).flipped() {
+            pile.sort_by(|a, b| compare(keys, &a.data, &b.data));
+        } else {
+            pile.sort_by(|a, b| compare(keys, &b.data, &a.data));
+        }
+        commands.trigger(Repaint::new(*entity));
+    }
+}
+pub fn do_sort(
+    hovered: Query<Entity, With<HoveredObject>>,
+    mut piles: Query<(&mut Pile, &Transform)>,
+    keybinds: Res<ButtonInput<Keybind>>,
+    settings: Res<Settings>,
+    spatial: Spatial,
+    mut commands: Commands,
+) {
+    if keybinds.just_pressed(Keybind::SortMenu) {
+        let targets: Vec<Entity> = hovered.iter().filter(|e| piles.contains(*e)).collect();
+        if !targets.is_empty() {
+            commands.insert_resource(SortTargets(targets));
+            commands.run_system_cached(show_sort);
+        }
+    } else if keybinds.just_pressed(Keybind::Sort) {
+        let targets: Vec<Entity> = hovered.iter().collect();
+        let camera = spatial.camera.transform.rotation;
+        sort_piles(
+            &targets,
+            &settings.sort_keys,
+            &mut piles,
+            camera,
+            &mut commands,
+        );
+    }
+}
+fn key_label(key: SortKey, keys: &[SortBy]) -> String {
+    match keys.iter().position(|sort| sort.key == key) {
+        Some(i) if keys[i].descending => format!("{}: desc ({})", key.name(), i + 1),
+        Some(i) => format!("{}: asc ({})", key.name(), i + 1),
+        None => format!("{}: off", key.name()),
+    }
+}
+fn cycle_key(keys: &mut Vec<SortBy>, key: SortKey) {
+    match keys.iter().position(|sort| sort.key == key) {
+        Some(i) if keys[i].descending => {
+            keys.remove(i);
+        }
+        Some(i) => keys[i].descending = true,
+        None => keys.push(SortBy::new(key, false)),
+    }
+}
+fn key_button(key: SortKey, keys: &[SortBy]) -> impl Bundle {
+    (
+        button(&key_label(key, keys)),
+        observe(
+            move |_: On<Activate>, mut settings: ResMut<Settings>, mut commands: Commands| {
+                cycle_key(&mut settings.sort_keys, key);
+                commands.run_system_cached(show_sort);
+            },
+        ),
+    )
+}
+fn show_sort(
+    settings: Res<Settings>,
+    menus: Query<Entity, With<SortMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.0),
+                right: Val::Percent(0.0),
+                top: Val::Percent(0.0),
+                position_type: PositionType::Absolute,
+                ..Node::default()
+            },
+            SortMenu,
+            Visibility::Visible,
+            BackgroundColor(Color::srgba_u8(0, 0, 0, 128)),
+        ))
+        .with_children(|parent| {
+            parent.spawn(text_node(
+                "sort keys, earlier keys take priority".to_owned(),
+            ));
+            for key in SortKey::ALL {
+                parent.spawn(key_button(key, &settings.sort_keys));
+            }
+            parent.spawn((button("Sort"), observe(on_sort)));
+            parent.spawn((button("Clear"), observe(on_clear)));
+            parent.spawn((button("Close"), observe(on_close)));
+        });
+    *menu = Menu::Side;
+}
+fn on_sort(
+    _: On<Activate>,
+    targets: Res<SortTargets>,
+    settings: Res<Settings>,
+    mut piles: Query<(&mut Pile, &Transform)>,
+    spatial: Spatial,
+    mut commands: Commands,
+) {
+    let camera = spatial.camera.transform.rotation;
+    sort_piles(
+        &targets.0,
+        &settings.sort_keys,
+        &mut piles,
+        camera,
+        &mut commands,
+    );
+}
+fn on_clear(_: On<Activate>, mut settings: ResMut<Settings>, mut commands: Commands) {
+    settings.sort_keys.clear();
+    commands.run_system_cached(show_sort);
+}
+fn on_close(_: On<Activate>, mut commands: Commands) {
+    commands.run_system_cached(close_sort);
+}
+fn close_sort(
+    menus: Query<Entity, With<SortMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<SortTargets>();
+    if *menu == Menu::Side {
+        *menu = Menu::World;
+    }
+}
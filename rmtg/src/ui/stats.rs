@@ -0,0 +1,214 @@
+use crate::events::hover::HoveredObject;
+use crate::events::scroll::Scrollable;
+use crate::focus::Menu;
+use crate::keybinds::Keybind;
+use crate::pile::Pile;
+use crate::ui::chat::text_node;
+use crate::ui::esc_menu::button;
+use bevy::color::Color;
+use bevy::input::ButtonInput;
+use bevy::prelude::{BackgroundColor, Component, FlexDirection, Resource, Visibility};
+use bevy::ui::{Node, Overflow, PositionType, Val};
+use bevy::ui_widgets::{Activate, observe};
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::observer::On;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Query, Res, ResMut};
+use importer::stats::{CURVE_LEN, DeckStats, cards_seen};
+#[derive(Component)]
+pub struct StatsMenu;
+#[derive(Resource)]
+pub struct StatsTarget {
+    stats: DeckStats,
+    card: usize,
+    wanted: u32,
+    lands: u32,
+    turn: u32,
+    on_play: bool,
+}
+pub fn do_stats(
+    hovered: Query<&Pile, With<HoveredObject>>,
+    keybinds: Res<ButtonInput<Keybind>>,
+    mut commands: Commands,
+) {
+    if !keybinds.just_pressed(Keybind::Stats) {
+        return;
+    }
+    let Some(pile) = hovered.iter().next() else {
+        return;
+    };
+    commands.insert_resource(StatsTarget {
+        stats: DeckStats::new(pile.iter().map(|card| &card.data.front)),
+        card: 0,
+        wanted: 1,
+        lands: 2,
+        turn: 2,
+        on_play: true,
+    });
+    commands.run_system_cached(show_stats);
+}
+fn adjust<F>(label: &str, f: F) -> impl Bundle
+where
+    F: Fn(&mut StatsTarget) + Send + Sync + 'static,
+{
+    (
+        button(label),
+        observe(
+            move |_: On<Activate>, mut target: ResMut<StatsTarget>, mut commands: Commands| {
+                f(&mut target);
+                commands.run_system_cached(show_stats);
+            },
+        ),
+    )
+}
+fn show_stats(
+    target: Res<StatsTarget>,
+    menus: Query<Entity, With<StatsMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    let stats = &target.stats;
+    let lines = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                flex_grow: 1.0,
+                overflow: Overflow::scroll_y(),
+                ..Node::default()
+            },
+            Visibility::Inherited,
+            Scrollable,
+        ))
+        .with_children(|parent| {
+            for line in stats_lines(stats) {
+                parent.spawn(text_node(line));
+            }
+            parent.spawn(text_node("pick a card:".to_owned()));
+            for (i, (name, count)) in stats.copies.iter().enumerate() {
+                let marker = if i == target.card { ">" } else { " " };
+                parent.spawn(adjust(&format!("{marker} {count} {name}"), move |target| {
+                    target.card = i;
+                }));
+            }
+        })
+        .id();
+    let odds = odds_lines(&target);
+    commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(25.0),
+                height: Val::Percent(75.0),
+                right: Val::Percent(0.0),
+                top: Val::Percent(0.0),
+                position_type: PositionType::Absolute,
+                ..Node::default()
+            },
+            StatsMenu,
+            Visibility::Visible,
+            BackgroundColor(Color::srgba_u8(0, 0, 0, 128)),
+        ))
+        .add_child(lines)
+        .with_children(|parent| {
+            for line in odds {
+                parent.spawn(text_node(line));
+            }
+            parent.spawn(adjust("turn +", |target| target.turn += 1));
+            parent.spawn(adjust("turn -", |target| {
+                target.turn = target.turn.saturating_sub(1).max(1);
+            }));
+            parent.spawn(adjust("lands +", |target| target.lands += 1));
+            parent.spawn(adjust("lands -", |target| {
+                target.lands = target.lands.saturating_sub(1);
+            }));
+            parent.spawn(adjust("copies +", |target| target.wanted += 1));
+            parent.spawn(adjust("copies -", |target| {
+                target.wanted = target.wanted.saturating_sub(1);
+            }));
+            let play = if target.on_play {
+                "on the play"
+            } else {
+                "on the draw"
+            };
+            parent.spawn(adjust(play, |target| target.on_play = !target.on_play));
+            parent.spawn((button("Close"), observe(on_close)));
+        });
+    *menu = Menu::Side;
+}
+fn percent(odds: f64) -> String {
+    format!("{:.0}%", odds * 100.0)
+}
+fn stats_lines(stats: &DeckStats) -> Vec<String> {
+    let mut lines = vec![
+        format!("cards: {}, lands: {}", stats.cards, stats.lands),
+        format!("average mana value: {:.2}", stats.average_mana_value()),
+        "curve:".to_owned(),
+    ];
+    lines.extend(stats.curve.iter().enumerate().map(|(mv, count)| {
+        if mv == CURVE_LEN - 1 {
+            format!("  {mv}+: {count}")
+        } else {
+            format!("  {mv}: {count}")
+        }
+    }));
+    lines.push("pips:".to_owned());
+    lines.extend(
+        stats
+            .pips
+            .iter()
+            .filter(|(_, count)| *count != 0)
+            .map(|(color, count)| format!("  {}: {count}", color.symbol())),
+    );
+    lines.push("types:".to_owned());
+    lines.extend(
+        stats
+            .types
+            .iter()
+            .map(|(ty, count)| format!("  {ty}: {count}")),
+    );
+    lines
+}
+fn odds_lines(target: &StatsTarget) -> Vec<String> {
+    let stats = &target.stats;
+    let (turn, on_play) = (target.turn, target.on_play);
+    let mut lines = vec![
+        format!(
+            "by turn {turn}, seeing {} cards:",
+            cards_seen(turn, on_play)
+        ),
+        format!(
+            "  {}+ lands: {}",
+            target.lands,
+            percent(stats.land_odds(target.lands, turn, on_play))
+        ),
+    ];
+    if let Some((name, _)) = stats.copies.get(target.card) {
+        lines.push(format!(
+            "  {}+ {name}: {}",
+            target.wanted,
+            percent(stats.card_odds(name, target.wanted, turn, on_play))
+        ));
+    }
+    lines
+}
+fn on_close(_: On<Activate>, mut commands: Commands) {
+    commands.run_system_cached(close_stats);
+}
+fn close_stats(
+    menus: Query<Entity, With<StatsMenu>>,
+    mut menu: ResMut<Menu>,
+    mut commands: Commands,
+) {
+    for entity in menus {
+        commands.entity(entity).despawn();
+    }
+    commands.remove_resource::<StatsTarget>();
+    if *menu == Menu::Side {
+        *menu = Menu::World;
+    }
+}
@@ -105,7 +105,7 @@ pub fn generate_types(_item: TokenStream) -> TokenStream {
         "Creature",
         "Artifact",
         "Enchantment",
-        "PlanesWalker",
+        "Planeswalker",
         "Conspiracy",
         "Battle",
         "Dungeon",